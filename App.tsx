@@ -181,11 +181,8 @@ export default function App() {
   });
   const [user, setUser] = useState<User | null>(null);
 
-  // Centralized Data Source State
-  const [connections, setConnections] = useState<DbConnection[]>(() => {
-    const saved = localStorage.getItem('db_connections');
-    return saved ? JSON.parse(saved) : [];
-  });
+  // Centralized Data Source State (连接配置保存在后端注册表，密码不回到前端)
+  const [connections, setConnections] = useState<DbConnection[]>([]);
 
   // Menu Configuration State
   const [menuConfig, setMenuConfig] = useState<Record<string, boolean>>(() => {
@@ -262,23 +259,34 @@ export default function App() {
     localStorage.setItem('monitorEnabled', JSON.stringify(monitorEnabled));
   }, [monitorEnabled]);
 
-  // Persist connections
+  // 加载后端注册表中的连接；旧版保存在 localStorage 的连接 (含明文密码) 先迁移到后端
   useEffect(() => {
-    localStorage.setItem('db_connections', JSON.stringify(connections));
-  }, [connections]);
+    const loadConnections = async () => {
+      const legacy: DbConnection[] = JSON.parse(localStorage.getItem('db_connections') || '[]');
+      for (const conn of legacy) {
+        await invoke('db_save_connection', { payload: conn });
+      }
+      localStorage.removeItem('db_connections');
+      setConnections(await invoke<DbConnection[]>('db_list_connections'));
+    };
+    loadConnections().catch((e: any) => console.error('Failed to load connections:', e));
+  }, []);
 
   // Persist dolphin configs
   useEffect(() => {
     localStorage.setItem('dolphin_configs', JSON.stringify(dolphinConfigs));
   }, [dolphinConfigs]);
 
-  const handleAddConnection = (conn: Omit<DbConnection, 'id'>) => {
-    setConnections(prev => [...prev, { ...conn, id: Date.now().toString() }]);
+  const handleAddConnection = async (conn: Omit<DbConnection, 'id'>) => {
+    const saved = await invoke<DbConnection>('db_save_connection', { payload: conn });
+    setConnections(prev => [...prev, saved]);
   };
-  const handleUpdateConnection = (conn: DbConnection) => {
-    setConnections(prev => prev.map(c => c.id === conn.id ? conn : c));
+  const handleUpdateConnection = async (conn: DbConnection) => {
+    const saved = await invoke<DbConnection>('db_save_connection', { payload: conn });
+    setConnections(prev => prev.map(c => c.id === saved.id ? saved : c));
   };
-  const handleDeleteConnection = (id: string) => {
+  const handleDeleteConnection = async (id: string) => {
+    await invoke('db_delete_connection', { id });
     setConnections(prev => prev.filter(c => c.id !== id));
   };
 
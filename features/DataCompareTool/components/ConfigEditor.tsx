@@ -75,10 +75,9 @@ export const ConfigEditor: React.FC<ConfigEditorProps> = ({
                 const conn = connections.find(c => c.id === sourceConfig.connId);
                 if (!conn) return;
 
-                const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
                 try {
                     const detail = await invoke<TableDetail>('db_get_table_schema', {
-                        id: connStr,
+                        id: conn.id,
                         db: sourceConfig.db,
                         table: sourceConfig.table
                     });
@@ -25,9 +25,8 @@ export const useTableSelection = (
         const conn = connections.find(c => c.id === connId);
         if (!conn) return;
 
-        const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
         try {
-            const dbs = await invoke<string[]>('db_get_databases', { id: connStr });
+            const dbs = await invoke<string[]>('db_get_databases', { id: conn.id });
             side === 'source' ? setSourceDbs(dbs) : setTargetDbs(dbs);
         } catch (e) {
             console.error('Failed to fetch databases:', e);
@@ -39,9 +38,8 @@ export const useTableSelection = (
         const conn = connections.find(c => c.id === connId);
         if (!conn) return;
 
-        const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
         try {
-            const tables = await invoke<TableInfo[]>('db_get_tables', { id: connStr, db });
+            const tables = await invoke<TableInfo[]>('db_get_tables', { id: conn.id, db });
             side === 'source' ? setSourceTables(tables) : setTargetTables(tables);
         } catch (e) {
             console.error('Failed to fetch tables:', e);
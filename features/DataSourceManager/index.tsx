@@ -14,9 +14,9 @@ import { useViewMode } from '../../store/globalStore';
 
 interface DataSourceManagerProps {
   connections: DbConnection[];
-  onAdd: (conn: Omit<DbConnection, 'id'>) => void;
-  onUpdate: (conn: DbConnection) => void;
-  onDelete: (id: string) => void;
+  onAdd: (conn: Omit<DbConnection, 'id'>) => Promise<void>;
+  onUpdate: (conn: DbConnection) => Promise<void>;
+  onDelete: (id: string) => Promise<void>;
 }
 
 // Alert Modal Component
@@ -72,12 +72,7 @@ const getDbConfig = (type: DatabaseType) => {
   }
 };
 
-export const DataSourceManager: React.FC<{
-  connections: DbConnection[];
-  onAdd: (conn: Omit<DbConnection, 'id'>) => void;
-  onUpdate: (conn: DbConnection) => void;
-  onDelete: (id: string) => void;
-}> = ({ connections, onAdd, onUpdate, onDelete }) => {
+export const DataSourceManager: React.FC<DataSourceManagerProps> = ({ connections, onAdd, onUpdate, onDelete }) => {
   const { t } = useTranslation();
   // Reliable check for Tauri v2
   const isTauri = !!(window as any).__TAURI_INTERNALS__ || !!(window as any).__TAURI__;
@@ -106,14 +101,24 @@ export const DataSourceManager: React.FC<{
     setShowModal(true);
   };
 
-  const handleSave = () => {
+  const showError = (error: unknown) => {
+    const errorMsg = typeof error === 'string' ? error : String(error);
+    setAlertState({ isOpen: true, title: t('common.failed'), message: errorMsg, type: 'error' });
+  };
+
+  // 保存到后端连接注册表；已保存的连接未输入密码时沿用原密码
+  const handleSave = async () => {
     if (isFormValid) {
-      if (editingConn.id) {
-        onUpdate(editingConn as DbConnection);
-      } else {
-        onAdd(editingConn as Omit<DbConnection, 'id'>);
+      try {
+        if (editingConn.id) {
+          await onUpdate(editingConn as DbConnection);
+        } else {
+          await onAdd(editingConn as Omit<DbConnection, 'id'>);
+        }
+        setShowModal(false);
+      } catch (error) {
+        showError(error);
       }
-      setShowModal(false);
     }
   };
 
@@ -123,7 +128,7 @@ export const DataSourceManager: React.FC<{
   };
 
   const handleConfirmDelete = () => {
-    onDelete(confirmDelete.id);
+    onDelete(confirmDelete.id).catch(showError);
     setConfirmDelete({ isOpen: false, id: '' });
   };
 
@@ -3,7 +3,7 @@ import { Loader2, X, Plus, Database, Code, Sparkles, Play, ChevronLeft, ChevronR
 import { SqlTab } from '../hooks/useSqlTabs';
 import { SqlEditor } from './SqlEditor';
 import { useTranslation } from 'react-i18next';
-import { DbConnection, TableInfo } from '../../../types';
+import { DbConnection, TableInfo, QueryResult } from '../../../types';
 
 interface SqlQueryTabProps {
     sqlTabs: SqlTab[];
@@ -156,9 +156,8 @@ export const SqlQueryTab: React.FC<SqlQueryTabProps> = ({
                                     }
                                     updateSqlTab(activeTab_sql.id, { loading: true, error: '', currentPage: 1 });
                                     const { invoke } = require('@tauri-apps/api/core');
-                                    const connectionId = `mysql://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}/${selectedDatabase || ''}`;
-                                    invoke('db_query', { id: connectionId, sql: text.trim() })
-                                        .then((results: any) => {
+                                    invoke('db_query', { id: selectedConnection.id, sql: text.trim(), options: { db: selectedDatabase ?? undefined } })
+                                        .then(({ rows: results }: QueryResult) => {
                                             updateSqlTab(activeTab_sql.id, { results, loading: false });
                                             showToast(t('dbViewer.querySuccessfulResultsLen'), 'success');
                                         })
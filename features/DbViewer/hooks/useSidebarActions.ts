@@ -6,6 +6,7 @@ import { useDbViewerStore } from '../store';
 import { useDatabase } from '../../../hooks/useDatabase';
 import { useTables } from '../../../hooks/useTables';
 import { DatabaseService } from '../../../services/database.service';
+import { QueryResult } from '../../../types';
 import { generateSelectSql, generateInsertSql, generateUpdateSql, generateDeleteSql } from '../utils/sqlGenerator';
 import { useToast } from '../../common/Toast';
 import { detectDbTypeFromDdl, convertMysqlToDoris, convertDorisToMysql } from '../utils/ddlConverter';
@@ -147,13 +148,12 @@ export const useSidebarActions = () => {
         } else if (type === 'db-data') {
             onProgress(t('dbViewer.DatabaseSelectedDatabase'));
             onProgress(t('dbViewer.TotalTablesLengthTables'));
-            const connStr = `mysql://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}`;
             let allData = `-- Database: ${selectedDatabase}\n-- Data Export Time: ${new Date().toLocaleString()}\n\n`;
             for (let i = 0; i < tables.length; i++) {
                 const table = tables[i];
                 onProgress(t('dbViewer.I1TablesLengthExportingDa'));
-                const rows: any[] = await invoke('db_query', {
-                    id: connStr,
+                const { rows } = await invoke<QueryResult>('db_query', {
+                    id: selectedConnection.id,
                     sql: `SELECT * FROM \`${selectedDatabase}\`.\`${table.name}\` LIMIT 10000`
                 });
                 if (rows.length > 0) {
@@ -179,9 +179,8 @@ export const useSidebarActions = () => {
         } else if (type === 'table-data' && tableName) {
             onProgress(t('dbViewer.TableTableName'));
             onProgress(t('dbViewer.QueryingData'));
-            const connStr = `mysql://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}`;
-            const rows: any[] = await invoke('db_query', {
-                id: connStr,
+            const { rows } = await invoke<QueryResult>('db_query', {
+                id: selectedConnection.id,
                 sql: `SELECT * FROM \`${selectedDatabase}\`.\`${tableName}\` LIMIT 10000`
             });
             onProgress(t('dbViewer.TotalRowsLengthRows'));
@@ -204,7 +203,7 @@ export const useSidebarActions = () => {
         if (!selectedConnection || !selectedDatabase) return;
         setConvertModal(prev => ({ ...prev, isConverting: true, progress: t('dbViewer.initializing') }));
         try {
-            const dbId = `${selectedConnection.type}://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}`;
+            const dbId = selectedConnection.id;
             let targetTables = tables;
             if (targetTables.length === 0) throw new Error(t('dbViewer.noTablesFoundInCurrentDat'));
 
@@ -222,7 +221,7 @@ export const useSidebarActions = () => {
                 const columns = schema.columns || [];
 
                 const ddlQuery = `SHOW CREATE TABLE \`${selectedDatabase}\`.\`${tableName}\``;
-                const ddlResult = await invoke<any[]>('db_query', { id: dbId, sql: ddlQuery });
+                const { rows: ddlResult } = await invoke<QueryResult>('db_query', { id: dbId, sql: ddlQuery });
                 let ddl = '';
                 if (ddlResult && ddlResult.length > 0) ddl = ddlResult[0]['Create Table'] || ddlResult[0]['Create View'] || '';
 
@@ -1,6 +1,7 @@
 import { useState, useMemo } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { useTranslation } from 'react-i18next';
+import { QueryResult } from '../../../types';
 import { useDbViewerStore } from '../store';
 import { useToast } from '../../common/Toast';
 
@@ -114,8 +115,7 @@ export const useSqlTabs = () => {
         updateSqlTab(activeTab_sql.id, { loading: true, error: '', currentPage: 1 });
 
         try {
-            const connectionId = `mysql://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}/${selectedDatabase || ''}`;
-            const results = await invoke<any[]>('db_query', { id: connectionId, sql: sql.trim() });
+            const { rows: results } = await invoke<QueryResult>('db_query', { id: selectedConnection.id, sql: sql.trim(), options: { db: selectedDatabase ?? undefined } });
             updateSqlTab(activeTab_sql.id, { results, loading: false });
             showToast(t('db_viewer.querySuccessfulResultsLen', { count: results.length }), 'success');
         } catch (error: any) {
@@ -142,8 +142,7 @@ export const useSqlTabs = () => {
         updateSqlTab(activeTab_sql.id, { loading: true, error: '', currentPage: 1 });
 
         try {
-            const connectionId = `mysql://${selectedConnection.user}:${selectedConnection.password || ''}@${selectedConnection.host}:${selectedConnection.port}/${selectedDatabase || ''}`;
-            const results = await invoke<any[]>('db_query', { id: connectionId, sql: selectedText.trim() });
+            const { rows: results } = await invoke<QueryResult>('db_query', { id: selectedConnection.id, sql: selectedText.trim(), options: { db: selectedDatabase ?? undefined } });
             updateSqlTab(activeTab_sql.id, { results, loading: false });
             showToast(t('db_viewer.querySuccessfulResultsLen', { count: results.length }), 'success');
         } catch (error: any) {
@@ -169,9 +169,8 @@ export const Canvas: React.FC<CanvasProps> = ({ connections, onNodeClick }) => {
                 const conn = connections.find(c => c.id === connId);
 
                 if (conn) {
-                    const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
                     const detail = await invoke<TableDetail>('db_get_table_schema', {
-                        id: connStr,
+                        id: conn.id,
                         db,
                         table: table.name
                     });
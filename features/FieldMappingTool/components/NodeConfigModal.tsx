@@ -72,8 +72,7 @@ export const NodeConfigModal: React.FC<NodeConfigModalProps> = ({
         if (!conn) return;
 
         try {
-            const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
-            const dbs = await invoke<string[]>('db_get_databases', { id: connStr });
+            const dbs = await invoke<string[]>('db_get_databases', { id: conn.id });
             setDatabases(dbs);
         } catch (e) {
             console.error(e);
@@ -87,8 +86,7 @@ export const NodeConfigModal: React.FC<NodeConfigModalProps> = ({
 
         setIsLoading(true);
         try {
-            const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
-            const tableList = await invoke<TableInfo[]>('db_get_tables', { id: connStr, db });
+            const tableList = await invoke<TableInfo[]>('db_get_tables', { id: conn.id, db });
             setTables(tableList);
         } catch (e) {
             console.error(e);
@@ -109,9 +107,8 @@ export const NodeConfigModal: React.FC<NodeConfigModalProps> = ({
         if (!conn) return;
 
         try {
-            const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
             const detail = await invoke<TableDetail>('db_get_table_schema', {
-                id: connStr,
+                id: conn.id,
                 db: selectedDb,
                 table: selectedTable
             });
@@ -202,6 +202,7 @@ export function generateConfigForPath(
         sinkQuery = `INSERT INTO \`${sinkDb}\`.\`${sinkTable}\``;
     }
     
+    // 密码不保存在前端，未填写时输出 SeaTunnel 变量占位符，提交作业时以 -i source_password=... 传入
     const config = `env {
   execution.parallelism = 1
   job.mode = "BATCH"
@@ -212,7 +213,7 @@ source {
     url = "jdbc:mysql://${sourceConn.host}:${sourceConn.port}/${sourceDb}"
     driver = "com.mysql.cj.jdbc.Driver"
     user = "${sourceConn.user}"
-    password = "${sourceConn.password || ''}"
+    password = "${sourceConn.password || '${source_password}'}"
     query = "${sourceQuery}"
   }
 }
@@ -222,7 +223,7 @@ sink {
     url = "jdbc:mysql://${sinkConn.host}:${sinkConn.port}/${sinkDb}"
     driver = "com.mysql.cj.jdbc.Driver"
     user = "${sinkConn.user}"
-    password = "${sinkConn.password || ''}"
+    password = "${sinkConn.password || '${sink_password}'}"
     query = "${sinkQuery}"
   }
 }`;
@@ -21,6 +21,10 @@ export function useSeatunnel(connections: DbConnection[]) {
   };
 
   const [activeJob, setActiveJob] = useState<ScriptJob | null>(null);
+
+  // 作业引用的连接 id；早期保存的作业没有 connId，按主机、端口与用户匹配
+  const connIdOf = (job: JobConfig) =>
+    job.connId || connections.find(c => c.host === job.host && c.port === job.port && c.user === job.user)?.id;
   const [showSelector, setShowSelector] = useState(false);
   const [selectingFor, setSelectingFor] = useState<'source' | 'sink' | null>(null);
   const [generatedConfig, setGeneratedConfig] = useState<string>('');
@@ -122,8 +126,7 @@ export function useSeatunnel(connections: DbConnection[]) {
     try {
       let dbs: string[] = [];
       if (isTauri) {
-        const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
-        dbs = await invoke('db_get_databases', { id: connStr });
+        dbs = await invoke('db_get_databases', { id: conn.id });
       } else {
         dbs = ['demo_db_1', 'demo_db_2', 'test_schema'];
       }
@@ -146,8 +149,7 @@ export function useSeatunnel(connections: DbConnection[]) {
     try {
       let tables: TableInfo[] = [];
       if (isTauri) {
-        const connStr = `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
-        tables = await invoke('db_get_tables', { id: connStr, db: dbName });
+        tables = await invoke('db_get_tables', { id: connIdOf(conn), db: dbName });
       } else {
         tables = [{ name: 'table_1', rows: 100, size: '1KB' }, { name: 'table_2', rows: 200, size: '2KB' }];
       }
@@ -185,11 +187,11 @@ export function useSeatunnel(connections: DbConnection[]) {
 
     if (activeJob && targetSide) {
       const jobConfig: JobConfig = {
+        connId: conn.id,
         type: conn.type,
         host: conn.host,
         port: conn.port,
         user: conn.user,
-        password: conn.password,
         database: conn.defaultDatabase || '',
         table: '',
         name: conn.name
@@ -262,9 +264,8 @@ export function useSeatunnel(connections: DbConnection[]) {
 
       if (isTauri) {
         try {
-          const sourceConnStr = `mysql://${activeJob.source.user}:${activeJob.source.password || ''}@${activeJob.source.host}:${activeJob.source.port}`;
           const sourceTableDetail = await invoke<TableDetail>('db_get_table_schema', {
-            id: sourceConnStr,
+            id: connIdOf(activeJob.source),
             db: activeJob.source.database,
             table: activeJob.source.table
           });
@@ -275,9 +276,8 @@ export function useSeatunnel(connections: DbConnection[]) {
         }
 
         try {
-          const sinkConnStr = `mysql://${activeJob.sink.user}:${activeJob.sink.password || ''}@${activeJob.sink.host}:${activeJob.sink.port}`;
           const sinkTableDetail = await invoke<TableDetail>('db_get_table_schema', {
-            id: sinkConnStr,
+            id: connIdOf(activeJob.sink),
             db: activeJob.sink.database,
             table: activeJob.sink.table
           });
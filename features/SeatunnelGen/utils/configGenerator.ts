@@ -26,6 +26,7 @@ export const generateConfig = (
     const sinkQuery = `INSERT INTO \`${sink.database}\`.\`${sink.table}\` (${sinkColsNames}) VALUES (${sinkPlaceholders})`;
 
     // 3. Build Configuration String
+    // 密码不保存在前端，未填写时输出 SeaTunnel 变量占位符，提交作业时以 -i source_password=... 传入
     return `env {
   execution.parallelism = 1
   job.mode = "BATCH"
@@ -36,7 +37,7 @@ source {
     url = "jdbc:mysql://${source.host}:${source.port}/${source.database}"
     driver = "com.mysql.cj.jdbc.Driver"
     user = "${source.user}"
-    password = "${source.password || ''}"
+    password = "${source.password || '${source_password}'}"
     query = "${sourceQuery}"
   }
 }
@@ -46,14 +47,14 @@ sink {
     ${sink.type === 'doris'
             ? `fenodes = "${sink.host}:${sink.port}"
     username = "${sink.user}"
-    password = "${sink.password || ''}"
+    password = "${sink.password || '${sink_password}'}"
     table.identifier = "${sink.database}.${sink.table}"
     sink.enable-2pc = "true"
     sink.label-prefix = "label_seatunnel"`
             : `url = "jdbc:mysql://${sink.host}:${sink.port}/${sink.database}"
     driver = "com.mysql.cj.jdbc.Driver"
     user = "${sink.user}"
-    password = "${sink.password || ''}"
+    password = "${sink.password || '${sink_password}'}"
     query = "${sinkQuery}"`
         }
   }
@@ -6,20 +6,12 @@ import { DbConnection, TableInfo, TableDetail, ColumnInfo } from '../types';
  * 统一处理数据库连接、查询等操作
  */
 export class DatabaseService {
-    /**
-     * 构建连接字符串
-     */
-    private static getConnectionString(conn: DbConnection): string {
-        return `mysql://${conn.user}:${conn.password || ''}@${conn.host}:${conn.port}`;
-    }
-
     /**
      * 获取数据库列表
      */
     static async getDatabases(conn: DbConnection): Promise<string[]> {
-        const connStr = this.getConnectionString(conn);
         try {
-            return await invoke<string[]>('db_get_databases', { id: connStr });
+            return await invoke<string[]>('db_get_databases', { id: conn.id });
         } catch (error) {
             console.error('Failed to fetch databases:', error);
             throw error;
@@ -30,9 +22,8 @@ export class DatabaseService {
      * 获取表列表
      */
     static async getTables(conn: DbConnection, database: string): Promise<TableInfo[]> {
-        const connStr = this.getConnectionString(conn);
         try {
-            return await invoke<TableInfo[]>('db_get_tables', { id: connStr, db: database });
+            return await invoke<TableInfo[]>('db_get_tables', { id: conn.id, db: database });
         } catch (error) {
             console.error('Failed to fetch tables:', error);
             throw error;
@@ -47,10 +38,9 @@ export class DatabaseService {
         database: string,
         table: string
     ): Promise<TableDetail> {
-        const connStr = this.getConnectionString(conn);
         try {
             return await invoke<TableDetail>('db_get_table_schema', {
-                id: connStr,
+                id: conn.id,
                 db: database,
                 table
             });
@@ -64,9 +54,8 @@ export class DatabaseService {
      * 测试连接
      */
     static async testConnection(conn: DbConnection): Promise<boolean> {
-        const connStr = this.getConnectionString(conn);
         try {
-            await invoke('db_test_connection', { id: connStr });
+            await invoke('db_test_connection', { payload: conn });
             return true;
         } catch (error) {
             console.error('Connection test failed:', error);
@@ -82,10 +71,9 @@ export class DatabaseService {
         database: string,
         query: string
     ): Promise<any> {
-        const connStr = this.getConnectionString(conn);
         try {
             return await invoke('db_execute_query', {
-                id: connStr,
+                id: conn.id,
                 db: database,
                 query
             });
@@ -0,0 +1,1233 @@
+// 表数据对比：两侧按主键排序流式读取并归并，在后端统计一致、差异与单侧独有的行，差异样本分页返回
+
+use super::cursor::{self, Cursor, CursorOptions};
+use super::driver::{self, Backend, Canceller, DbDriver, StreamEvent};
+use super::model::{is_binary_type, QueryStatus};
+use super::registry::DbConfig;
+use crate::sql::Dialect;
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+// 前端监听的事件名
+pub const PROGRESS_EVENT: &str = "db-compare-progress";
+// 进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+// 对比结果 (差异样本) 的保留时间与个数
+const RESULT_TTL_SECS: u64 = 1800;
+const MAX_RESULTS: usize = 20;
+// 单页样本数上限
+const MAX_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SideConfig {
+    pub conn_id: String,
+    pub db: String,
+    pub table: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompareKey {
+    pub field: String,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompareMode {
+    // 两侧按键排序逐行归并
+    #[default]
+    Rows,
+    // 按键范围分段比较行数与校验和，只逐行对比不一致的分段
+    Checksum,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareRequest {
+    pub source: SideConfig,
+    pub target: SideConfig,
+    // 归并所用的键列 (按源表列名)，两侧需能唯一确定一行
+    pub primary_keys: Vec<CompareKey>,
+    // 两侧共用的 WHERE 条件
+    pub filter_condition: Option<String>,
+    // 只对比这些列，默认对比两侧同名 (不区分大小写) 的全部列
+    pub columns: Option<Vec<String>>,
+    #[serde(default)]
+    pub ignore_columns: Vec<String>,
+    // 保留的样本行数，默认 10000
+    pub max_samples: Option<usize>,
+    // 样本中包含一致的行
+    #[serde(default)]
+    pub include_matches: bool,
+    // 随结果返回的首页样本数，默认 100
+    pub page_size: Option<usize>,
+    // 逐行模式下等待下一行的最长时间，默认 0 (不限)
+    pub timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub mode: CompareMode,
+    // 校验和模式每段的行数 (按源表计)，默认 10000
+    pub chunk_size: Option<usize>,
+    // 同时作为查询 id，可用 db_cancel_query 取消
+    pub compare_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareStats {
+    #[serde(rename = "match")]
+    pub matched: u64,
+    pub diff: u64,
+    pub source_only: u64,
+    pub target_only: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkStats {
+    pub total: u64,
+    // 校验和不一致、已逐行对比的分段数
+    pub differing: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareRowStatus {
+    Match,
+    Diff,
+    OnlySource,
+    OnlyTarget,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareRow {
+    // 键值，多列以逗号连接
+    pub key_display: String,
+    pub status: CompareRowStatus,
+    pub source_data: Option<Value>,
+    pub target_data: Option<Value>,
+    // 值不一致的列 (源表列名)
+    pub diff_fields: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareProgress {
+    pub compare_id: String,
+    pub source_rows: u64,
+    pub target_rows: u64,
+    pub stats: CompareStats,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunks: Option<ChunkStats>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareSummary {
+    pub compare_id: String,
+    pub stats: CompareStats,
+    // 对比的列 (源表列名，含键列)
+    pub columns: Vec<String>,
+    pub source_rows: u64,
+    pub target_rows: u64,
+    // 仅校验和模式
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunks: Option<ChunkStats>,
+    // 首页样本，其余通过 db_compare_samples 分页读取
+    pub samples: Vec<CompareRow>,
+    pub sample_total: usize,
+    // 样本数达到 maxSamples 后不再保留
+    pub samples_truncated: bool,
+    pub elapsed_ms: u64,
+    // 未完成时统计只包含已读取的部分
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparePage {
+    pub compare_id: String,
+    pub offset: usize,
+    pub samples: Vec<CompareRow>,
+    // 符合状态筛选的样本总数
+    pub total: usize,
+}
+
+// 目标表及列对应关系，供生成修复 SQL
+#[derive(Debug, Clone)]
+pub struct CompareTarget {
+    pub side: SideConfig,
+    pub keys: Vec<ColumnPair>,
+    pub columns: Vec<ColumnPair>,
+    // 对比已完成且样本未截断，样本包含全部差异
+    pub complete: bool,
+}
+
+struct StoredResult {
+    samples: Vec<CompareRow>,
+    target: CompareTarget,
+    created: Instant,
+}
+
+// 已完成对比的样本，供分页读取
+#[derive(Default)]
+pub struct CompareRegistry {
+    results: Mutex<HashMap<String, StoredResult>>,
+}
+
+impl CompareRegistry {
+    fn insert(&self, compare_id: &str, samples: Vec<CompareRow>, target: CompareTarget) {
+        let mut results = self.results.lock().unwrap();
+        results.retain(|_, r| r.created.elapsed() < Duration::from_secs(RESULT_TTL_SECS));
+        // 超出个数时丢弃最早的结果
+        while results.len() >= MAX_RESULTS {
+            let Some(oldest) = results.iter().min_by_key(|(_, r)| r.created).map(|(id, _)| id.clone()) else {
+                break;
+            };
+            results.remove(&oldest);
+        }
+        results.insert(compare_id.to_string(), StoredResult { samples, target, created: Instant::now() });
+    }
+
+    fn page(&self, compare_id: &str, offset: usize, limit: usize, status: Option<CompareRowStatus>) -> Result<ComparePage, String> {
+        let results = self.results.lock().unwrap();
+        let result = results.get(compare_id).ok_or_else(|| format!("Compare result not found: {}", compare_id))?;
+        let matching = || result.samples.iter().filter(|row| status.is_none_or(|s| row.status == s));
+        Ok(ComparePage {
+            compare_id: compare_id.to_string(),
+            offset,
+            samples: matching().skip(offset).take(limit.clamp(1, MAX_PAGE_SIZE)).cloned().collect(),
+            total: matching().count(),
+        })
+    }
+
+    // 取出指定状态的样本及目标表信息
+    pub fn rows(&self, compare_id: &str, statuses: &[CompareRowStatus]) -> Result<(CompareTarget, Vec<CompareRow>), String> {
+        let results = self.results.lock().unwrap();
+        let result = results.get(compare_id).ok_or_else(|| format!("Compare result not found: {}", compare_id))?;
+        let rows = result.samples.iter().filter(|row| statuses.contains(&row.status)).cloned().collect();
+        Ok((result.target.clone(), rows))
+    }
+
+    fn remove(&self, compare_id: &str) -> bool {
+        self.results.lock().unwrap().remove(compare_id).is_some()
+    }
+
+    // 清理过期的结果 (在后台线程中调用)
+    pub fn sweep(&self) {
+        self.results.lock().unwrap().retain(|_, r| r.created.elapsed() < Duration::from_secs(RESULT_TTL_SECS));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyKind {
+    Numeric,
+    Text,
+    Other,
+}
+
+// 按列类型名粗分键的比较方式
+fn key_kind(col_type: &str) -> KeyKind {
+    let lower = col_type.to_lowercase();
+    let base = lower.split('(').next().unwrap_or_default().trim();
+    const NUMERIC: [&str; 8] = ["int", "decimal", "numeric", "float", "double", "real", "serial", "number"];
+    const TEXT: [&str; 6] = ["char", "text", "enum", "set", "string", "name"];
+    // interval、point 含 int
+    if NUMERIC.iter().any(|n| base.contains(n)) && !base.contains("interval") && !base.contains("point") {
+        KeyKind::Numeric
+    } else if TEXT.iter().any(|t| base.contains(t)) {
+        KeyKind::Text
+    } else {
+        KeyKind::Other
+    }
+}
+
+// 对比用到的列信息
+struct TableColumn {
+    name: String,
+    col_type: String,
+    nullable: bool,
+    // 文本列的排序规则 (MySQL 的 COLLATION_NAME、PostgreSQL 的 collname)
+    collation: Option<String>,
+}
+
+// 两侧对应的列名
+#[derive(Debug, Clone)]
+pub struct ColumnPair {
+    pub source: String,
+    pub target: String,
+    // 两侧列是否为二进制类型 (值为 base64)，生成修复 SQL 时还原为字节
+    pub source_binary: bool,
+    pub target_binary: bool,
+    // 任一侧为数值列时按数值对比 (DECIMAL 以文本返回，1.50 与 1.5000 相等)
+    pub numeric: bool,
+}
+
+impl ColumnPair {
+    fn new(source: &TableColumn, target: &TableColumn) -> ColumnPair {
+        ColumnPair {
+            source: source.name.clone(),
+            target: target.name.clone(),
+            source_binary: is_binary_type(&source.col_type),
+            target_binary: is_binary_type(&target.col_type),
+            numeric: key_kind(&source.col_type) == KeyKind::Numeric || key_kind(&target.col_type) == KeyKind::Numeric,
+        }
+    }
+}
+
+struct KeySpec {
+    source: String,
+    target: String,
+    source_kind: KeyKind,
+    target_kind: KeyKind,
+    source_collation: Option<String>,
+    target_collation: Option<String>,
+    order: SortOrder,
+}
+
+impl KeySpec {
+    // 两侧类型不同时只要一侧是数值就按数值比较
+    fn kind(&self) -> KeyKind {
+        match (self.source_kind, self.target_kind) {
+            (KeyKind::Numeric, _) | (_, KeyKind::Numeric) => KeyKind::Numeric,
+            (KeyKind::Text, _) | (_, KeyKind::Text) => KeyKind::Text,
+            _ => KeyKind::Other,
+        }
+    }
+}
+
+// 两侧同名列 (先精确匹配再不区分大小写)，按源表列顺序
+fn match_columns(source: &[TableColumn], target: &[TableColumn]) -> Vec<(usize, usize)> {
+    source
+        .iter()
+        .enumerate()
+        .filter_map(|(i, col)| {
+            target
+                .iter()
+                .position(|t| t.name == col.name)
+                .or_else(|| target.iter().position(|t| t.name.eq_ignore_ascii_case(&col.name)))
+                .map(|j| (i, j))
+        })
+        .collect()
+}
+
+// 列本身的排序规则是否已按字节 (码点) 序，此时直接按列排序以使用索引
+fn byte_ordered(backend: Backend, collation: Option<&str>) -> bool {
+    let Some(collation) = collation.map(str::to_lowercase) else { return false };
+    match backend {
+        Backend::MySql => collation == "binary" || collation.ends_with("_bin"),
+        Backend::Postgres => collation == "c" || collation == "posix",
+        Backend::Sqlite => false,
+    }
+}
+
+// 两侧排序规则是否相同 (名称不区分大小写，未知时视为默认规则)
+fn same_collation(a: Option<&str>, b: Option<&str>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (a, b) => a == b,
+    }
+}
+
+// 文本键按字节序排序，与后端的字符串比较一致；NULL 统一排在升序的最前
+// 列的排序规则不是字节序时才改写排序表达式，此时无法使用键上的索引，需要额外排序
+fn order_expr(config: &DbConfig, column: String, kind: KeyKind, order: SortOrder, collation: Option<&str>) -> Result<String, String> {
+    let backend = driver::backend_of(config)?;
+    let text = kind == KeyKind::Text && !byte_ordered(backend, collation);
+    let expr = match backend {
+        // 先转为 utf8mb4 再按二进制比较，与列字符集及 PAD SPACE 无关；Doris 本身按字节比较
+        Backend::MySql if text && Dialect::parse(&config.db_type) != Some(Dialect::Doris) => {
+            format!("CAST(CONVERT({} USING utf8mb4) AS BINARY)", column)
+        }
+        Backend::Postgres if text => format!("{} COLLATE \"C\"", column),
+        Backend::Sqlite if text => format!("{} COLLATE BINARY", column),
+        _ => column,
+    };
+    Ok(match (backend, order) {
+        (Backend::Postgres, SortOrder::Asc) => format!("{} ASC NULLS FIRST", expr),
+        (Backend::Postgres, SortOrder::Desc) => format!("{} DESC NULLS LAST", expr),
+        (_, SortOrder::Asc) => format!("{} ASC", expr),
+        (_, SortOrder::Desc) => format!("{} DESC", expr),
+    })
+}
+
+// 校验和的计算方式，两侧需相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    MySql,
+    Doris,
+    Postgres,
+}
+
+fn flavor_of(config: &DbConfig) -> Result<Flavor, String> {
+    match driver::backend_of(config)? {
+        Backend::MySql if Dialect::parse(&config.db_type) == Some(Dialect::Doris) => Ok(Flavor::Doris),
+        Backend::MySql => Ok(Flavor::MySql),
+        Backend::Postgres => Ok(Flavor::Postgres),
+        Backend::Sqlite => Err("Checksum compare is not supported for SQLite; use the rows mode".to_string()),
+    }
+}
+
+// 一行的校验值：各列转为文本以 # 连接 (附带 NULL 标记) 后取 CRC32 / MD5 前 8 位，分段内求和
+fn checksum_expr(flavor: Flavor, columns: &[String]) -> String {
+    let join = |f: &dyn Fn(&String) -> String| columns.iter().map(f).collect::<Vec<_>>().join(", ");
+    match flavor {
+        Flavor::MySql => format!(
+            "COALESCE(SUM(CRC32(CONCAT_WS('#', {}, CONCAT({})))), 0)",
+            join(&|c| format!("CAST({} AS BINARY)", c)),
+            join(&|c| format!("ISNULL({})", c))
+        ),
+        Flavor::Doris => format!(
+            "COALESCE(SUM(CAST(CONV(SUBSTR(MD5(CONCAT_WS('#', {}, CONCAT({}))), 1, 8), 16, 10) AS BIGINT)), 0)",
+            join(&|c| format!("CAST({} AS STRING)", c)),
+            join(&|c| format!("IF({} IS NULL, '1', '0')", c))
+        ),
+        Flavor::Postgres => format!(
+            "COALESCE(SUM(('x' || SUBSTR(MD5(CONCAT_WS('#', {}, CONCAT({}))), 1, 8))::bit(32)::bigint), 0)",
+            join(&|c| format!("{}::text", c)),
+            join(&|c| format!("({} IS NULL)::int", c))
+        ),
+    }
+}
+
+// 一侧的查询：先键列后对比列，列顺序两侧一致
+struct SideQuery {
+    backend: Backend,
+    reference: String,
+    columns: Vec<String>,
+    keys: Vec<String>,
+    key_names: Vec<String>,
+    // 与 compare_values 一致的排序
+    order: String,
+    // 分段沿键的方向推进；各键方向不一致时按升序分段，结果仍正确但样本顺序按段
+    walk: SortOrder,
+    filter: Option<String>,
+}
+
+impl SideQuery {
+    fn new(config: &DbConfig, side: &SideConfig, columns: &[&str], keys: &[(&str, KeyKind, SortOrder, Option<&str>)], filter: Option<&str>) -> Result<SideQuery, String> {
+        let (dialect, reference) = driver::table_ref(config, &side.db, &side.table)?;
+        let order = keys
+            .iter()
+            .map(|(name, kind, order, collation)| order_expr(config, dialect.quote_ident(name), *kind, *order, *collation))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", ");
+        Ok(SideQuery {
+            backend: driver::backend_of(config)?,
+            reference,
+            columns: columns.iter().map(|c| dialect.quote_ident(c)).collect(),
+            keys: keys.iter().map(|(name, ..)| dialect.quote_ident(name)).collect(),
+            key_names: keys.iter().map(|(name, ..)| name.to_string()).collect(),
+            order,
+            walk: match keys {
+                [(_, _, first, _), rest @ ..] if rest.iter().all(|(_, _, o, _)| o == first) => *first,
+                _ => SortOrder::Asc,
+            },
+            filter: filter.map(str::trim).filter(|f| !f.is_empty()).map(str::to_string),
+        })
+    }
+
+    fn placeholder(&self, n: usize) -> String {
+        match self.backend {
+            Backend::Postgres => format!("${}", n),
+            _ => "?".to_string(),
+        }
+    }
+
+    // 过滤条件与键区间 (lower, upper]，多列键按行值比较；键为 NULL 的行不在任何区间内
+    // 区间按列本身比较以便使用索引，文本键两侧的排序规则需一致
+    fn condition(&self, lower: Option<&[Value]>, upper: Option<&[Value]>) -> (String, Vec<Value>) {
+        let tuple = match self.keys.as_slice() {
+            [key] => key.clone(),
+            keys => format!("({})", keys.join(", ")),
+        };
+        let mut conditions: Vec<String> = self.filter.iter().map(|f| format!("({})", f)).collect();
+        let mut params = Vec::new();
+        let ops = match self.walk {
+            SortOrder::Asc => [">", "<="],
+            SortOrder::Desc => ["<", ">="],
+        };
+        for (bound, op) in [lower, upper].into_iter().zip(ops) {
+            let Some(values) = bound else {
+                continue;
+            };
+            let marks: Vec<String> = values
+                .iter()
+                .map(|v| {
+                    params.push(v.clone());
+                    self.placeholder(params.len())
+                })
+                .collect();
+            let marks = match marks.as_slice() {
+                [mark] => mark.clone(),
+                marks => format!("({})", marks.join(", ")),
+            };
+            conditions.push(format!("{} {} {}", tuple, op, marks));
+        }
+        let clause = if conditions.is_empty() { String::new() } else { format!(" WHERE {}", conditions.join(" AND ")) };
+        (clause, params)
+    }
+
+    fn rows_sql(&self, clause: &str) -> String {
+        format!("SELECT {} FROM {}{} ORDER BY {}", self.columns.join(", "), self.reference, clause, self.order)
+    }
+
+    // 从 lower 之后数 chunk_size 行的键，作为分段上界
+    fn boundary_sql(&self, clause: &str, chunk_size: usize) -> String {
+        let direction = match self.walk {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        let order: Vec<String> = self.keys.iter().map(|k| format!("{} {}", k, direction)).collect();
+        format!("SELECT {} FROM {}{} ORDER BY {} LIMIT 1 OFFSET {}", self.keys.join(", "), self.reference, clause, order.join(", "), chunk_size - 1)
+    }
+
+    fn checksum_sql(&self, clause: &str, flavor: Flavor) -> String {
+        format!(
+            "SELECT COUNT(*) AS row_count, {} AS checksum FROM {}{}",
+            checksum_expr(flavor, &self.columns),
+            self.reference,
+            clause
+        )
+    }
+}
+
+fn text(value: &Value) -> Cow<'_, str> {
+    match value {
+        Value::String(s) => Cow::Borrowed(s),
+        other => Cow::Owned(other.to_string()),
+    }
+}
+
+fn integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(n) => n.as_i64().map(i128::from).or_else(|| n.as_u64().map(i128::from)),
+        Value::Bool(b) => Some(*b as i128),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// 十进制数字串的符号、去掉前导零的整数位与去掉末尾零的小数位，不含指数
+struct Decimal {
+    negative: bool,
+    int: String,
+    frac: String,
+}
+
+fn decimal(value: &Value) -> Option<Decimal> {
+    let text = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(&text)),
+    };
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, ""));
+    if (int.is_empty() && frac.is_empty()) || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (int, frac) = (int.trim_start_matches('0'), frac.trim_end_matches('0'));
+    // -0.00 与 0 相等
+    Some(Decimal { negative: negative && !(int.is_empty() && frac.is_empty()), int: int.to_string(), frac: frac.to_string() })
+}
+
+// 先比符号，再比整数位数与各位数字，最后按小数位逐位比较
+fn compare_decimals(a: &Decimal, b: &Decimal) -> Ordering {
+    let magnitude = a.int.len().cmp(&b.int.len()).then_with(|| a.int.cmp(&b.int)).then_with(|| a.frac.cmp(&b.frac));
+    match (a.negative, b.negative) {
+        (false, true) => Ordering::Greater,
+        (true, false) => Ordering::Less,
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+    }
+}
+
+fn float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::Bool(b) => Some(*b as u8 as f64),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+// 整数按 i128、DECIMAL 字符串按十进制位精确比较，带指数等其余情况按 f64
+fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    if let (Some(x), Some(y)) = (integer(a), integer(b)) {
+        return Some(x.cmp(&y));
+    }
+    if let (Some(x), Some(y)) = (decimal(a), decimal(b)) {
+        return Some(compare_decimals(&x, &y));
+    }
+    float(a)?.partial_cmp(&float(b)?)
+}
+
+// 键值的排序，与两侧 ORDER BY 的结果一致 (NULL 最小、文本按字节序)
+fn compare_values(a: &Value, b: &Value, kind: KeyKind) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        _ => {
+            let numeric = kind == KeyKind::Numeric || matches!((a, b), (Value::Number(_), Value::Number(_)));
+            numeric.then(|| compare_numbers(a, b)).flatten().unwrap_or_else(|| text(a).cmp(&text(b)))
+        }
+    }
+}
+
+// 两侧的值是否一致：数值按数值比较 (1 与 1.0、true 与 1)，JSON 文本与对象按解析后比较
+// 两个字符串只在 numeric (数值列) 时按数值比较
+fn values_equal(a: &Value, b: &Value, numeric: bool) -> bool {
+    if a == b {
+        return true;
+    }
+    match (a, b) {
+        (Value::Null, _) | (_, Value::Null) => false,
+        (Value::String(_), Value::String(_)) => numeric && compare_numbers(a, b) == Some(Ordering::Equal),
+        (Value::Object(_) | Value::Array(_), Value::String(s)) | (Value::String(s), Value::Object(_) | Value::Array(_)) => {
+            serde_json::from_str::<Value>(s).is_ok_and(|parsed| parsed == *a || parsed == *b)
+        }
+        _ => compare_numbers(a, b).map_or_else(|| text(a) == text(b), |o| o == Ordering::Equal),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Source,
+    Target,
+}
+
+// 提前结束对比的原因
+struct Stop(QueryStatus, String);
+
+// 归并的一侧：逐行对比时为游标，校验和模式下为一个分段内已读取的行
+trait RowSource {
+    // 读完返回 Ok(None)
+    fn next_row(&mut self) -> Result<Option<Value>, Stop>;
+}
+
+impl RowSource for Cursor {
+    fn next_row(&mut self) -> Result<Option<Value>, Stop> {
+        match Cursor::next_row(self) {
+            Some(row) => Ok(Some(row)),
+            None => match self.status() {
+                QueryStatus::Completed => Ok(None),
+                status => Err(Stop(status, self.error().unwrap_or("Query did not complete").to_string())),
+            },
+        }
+    }
+}
+
+impl RowSource for std::vec::IntoIter<Value> {
+    fn next_row(&mut self) -> Result<Option<Value>, Stop> {
+        Ok(self.next())
+    }
+}
+
+struct Merge {
+    keys: Vec<KeySpec>,
+    // 参与对比的非键列
+    columns: Vec<ColumnPair>,
+    include_matches: bool,
+    max_samples: usize,
+    stats: CompareStats,
+    samples: Vec<CompareRow>,
+    samples_truncated: bool,
+    source_rows: u64,
+    target_rows: u64,
+    chunks: Option<ChunkStats>,
+}
+
+impl Merge {
+    fn key(&self, row: &Value, side: Side) -> Vec<Value> {
+        self.keys
+            .iter()
+            .map(|k| {
+                let name = match side {
+                    Side::Source => &k.source,
+                    Side::Target => &k.target,
+                };
+                row.get(name).cloned().unwrap_or(Value::Null)
+            })
+            .collect()
+    }
+
+    fn compare_keys(&self, a: &[Value], b: &[Value]) -> Ordering {
+        for ((spec, x), y) in self.keys.iter().zip(a).zip(b) {
+            let ordering = compare_values(x, y, spec.kind());
+            let ordering = if spec.order == SortOrder::Desc { ordering.reverse() } else { ordering };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+
+    // 读取一侧的下一行并检查顺序；排序规则与这里的比较不一致时无法归并
+    fn advance(&mut self, rows: &mut dyn RowSource, side: Side, prev: &mut Option<Vec<Value>>) -> Result<Option<(Value, Vec<Value>)>, Stop> {
+        let Some(row) = rows.next_row()? else {
+            return Ok(None);
+        };
+        let key = self.key(&row, side);
+        let label = match side {
+            Side::Source => {
+                self.source_rows += 1;
+                "Source"
+            }
+            Side::Target => {
+                self.target_rows += 1;
+                "Target"
+            }
+        };
+        if prev.as_ref().is_some_and(|p| self.compare_keys(p, &key) == Ordering::Greater) {
+            return Err(Stop(
+                QueryStatus::Failed,
+                format!("{} rows are not in key order near key ({}); check the key columns and their collation", label, key_display(&key)),
+            ));
+        }
+        *prev = Some(key.clone());
+        Ok(Some((row, key)))
+    }
+
+    fn record(&mut self, status: CompareRowStatus, key: &[Value], source: Option<Value>, target: Option<Value>, diff_fields: Vec<String>) {
+        match status {
+            CompareRowStatus::Match => self.stats.matched += 1,
+            CompareRowStatus::Diff => self.stats.diff += 1,
+            CompareRowStatus::OnlySource => self.stats.source_only += 1,
+            CompareRowStatus::OnlyTarget => self.stats.target_only += 1,
+        }
+        if status == CompareRowStatus::Match && !self.include_matches {
+            return;
+        }
+        if self.samples.len() >= self.max_samples {
+            self.samples_truncated = true;
+            return;
+        }
+        self.samples.push(CompareRow { key_display: key_display(key), status, source_data: source, target_data: target, diff_fields });
+    }
+
+    fn diff_fields(&self, source: &Value, target: &Value) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter(|c| {
+                let a = source.get(&c.source).unwrap_or(&Value::Null);
+                let b = target.get(&c.target).unwrap_or(&Value::Null);
+                !values_equal(a, b, c.numeric)
+            })
+            .map(|c| c.source.clone())
+            .collect()
+    }
+
+    fn run(&mut self, source: &mut dyn RowSource, target: &mut dyn RowSource, report: &mut dyn FnMut(&Merge)) -> Result<(), Stop> {
+        let (mut source_prev, mut target_prev) = (None, None);
+        let mut s = self.advance(source, Side::Source, &mut source_prev)?;
+        let mut t = self.advance(target, Side::Target, &mut target_prev)?;
+        let mut reported = Instant::now();
+        loop {
+            let ordering = match (&s, &t) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some((_, sk)), Some((_, tk))) => self.compare_keys(sk, tk),
+            };
+            match ordering {
+                Ordering::Less => {
+                    let (row, key) = s.take().unwrap();
+                    self.record(CompareRowStatus::OnlySource, &key, Some(row), None, Vec::new());
+                    s = self.advance(source, Side::Source, &mut source_prev)?;
+                }
+                Ordering::Greater => {
+                    let (row, key) = t.take().unwrap();
+                    self.record(CompareRowStatus::OnlyTarget, &key, None, Some(row), Vec::new());
+                    t = self.advance(target, Side::Target, &mut target_prev)?;
+                }
+                Ordering::Equal => {
+                    let ((source_row, key), (target_row, _)) = (s.take().unwrap(), t.take().unwrap());
+                    let diff = self.diff_fields(&source_row, &target_row);
+                    let status = if diff.is_empty() { CompareRowStatus::Match } else { CompareRowStatus::Diff };
+                    self.record(status, &key, Some(source_row), Some(target_row), diff);
+                    s = self.advance(source, Side::Source, &mut source_prev)?;
+                    t = self.advance(target, Side::Target, &mut target_prev)?;
+                }
+            }
+            if reported.elapsed() >= PROGRESS_INTERVAL {
+                report(self);
+                reported = Instant::now();
+            }
+        }
+        report(self);
+        Ok(())
+    }
+}
+
+fn key_display(key: &[Value]) -> String {
+    key.iter().map(|v| text(v).into_owned()).collect::<Vec<_>>().join(", ")
+}
+
+// 只查列名、类型与可空，不读取完整表结构 (Doris 的分区、Rollup 等)
+fn columns_query(backend: Backend, db: &str, table: &str) -> Statement {
+    let params = |values: &[&str]| values.iter().map(|v| Value::String(v.to_string())).collect();
+    match backend {
+        Backend::MySql => (
+            "SELECT COLUMN_NAME AS name, COLUMN_TYPE AS type, IS_NULLABLE AS nullable, COLLATION_NAME AS collation FROM information_schema.COLUMNS \
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND EXTRA NOT LIKE '%VIRTUAL GENERATED%' AND EXTRA NOT LIKE '%STORED GENERATED%' \
+            ORDER BY ORDINAL_POSITION"
+                .to_string(),
+            params(&[db, table]),
+        ),
+        // 与驱动一致：public 下的同名表优先于 schema.table 形式的匹配
+        Backend::Postgres => (
+            "SELECT a.attname::text AS name, format_type(a.atttypid, a.atttypmod) AS type, \
+                CASE WHEN a.attnotnull THEN 'NO' ELSE 'YES' END AS nullable, \
+                (SELECT collname::text FROM pg_collation WHERE oid = a.attcollation) AS collation \
+            FROM pg_attribute a \
+            WHERE a.attrelid = (SELECT c.oid FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+                WHERE (n.nspname = 'public' AND c.relname = $1) OR n.nspname || '.' || c.relname = $1 \
+                ORDER BY (n.nspname = 'public' AND c.relname = $1) DESC LIMIT 1) \
+            AND a.attnum > 0 AND NOT a.attisdropped AND a.attgenerated = '' ORDER BY a.attnum"
+                .to_string(),
+            params(&[table]),
+        ),
+        Backend::Sqlite => (
+            "SELECT name, type, CASE WHEN \"notnull\" = 1 OR pk > 0 THEN 'NO' ELSE 'YES' END AS nullable FROM pragma_table_info(?, ?) ORDER BY cid"
+                .to_string(),
+            params(&[table, db]),
+        ),
+    }
+}
+
+async fn describe(state: &AppState, side: &SideConfig) -> Result<(DbConfig, Vec<TableColumn>), String> {
+    let config = state.connections.get(&side.conn_id)?;
+    let (sql, params) = columns_query(driver::backend_of(&config)?, &side.db, &side.table);
+    let mut driver = driver::open(state, &side.conn_id, Some(&side.db))?;
+    let rows = driver::blocking(move || query(driver.as_mut(), &sql, params)).await?;
+    if rows.is_empty() {
+        return Err(format!("Table not found: {}", side.table));
+    }
+    let field = |row: &Value, name: &str| row.get(name).map(|v| text(v).into_owned()).unwrap_or_default();
+    let columns = rows
+        .iter()
+        .map(|row| TableColumn {
+            name: field(row, "name"),
+            col_type: field(row, "type"),
+            nullable: field(row, "nullable").eq_ignore_ascii_case("YES"),
+            collation: row.get("collation").and_then(Value::as_str).map(str::to_string),
+        })
+        .collect();
+    Ok((config, columns))
+}
+
+// 游标与驱动析构时可能需要连接服务端中止查询或关闭连接
+async fn release<T: Send + 'static>(items: T) {
+    let _ = driver::blocking(move || {
+        drop(items);
+        Ok(())
+    })
+    .await;
+}
+
+// 两侧的查询在 compareId 下一起取消
+fn register(state: &AppState, compare_id: &str, request: &CompareRequest, source: Arc<Canceller>, target: Arc<Canceller>) -> Result<Arc<Canceller>, String> {
+    let cancel = Arc::new(Canceller::new(None, move || {
+        source.cancel();
+        target.cancel();
+    }));
+    let label = format!("compare {} / {}", request.source.table, request.target.table);
+    state.queries.register(compare_id, &request.source.conn_id, &label, cancel.clone())?;
+    Ok(cancel)
+}
+
+fn progress_reporter(app: AppHandle, compare_id: String) -> impl FnMut(&Merge) + Send {
+    move |merge: &Merge| {
+        let progress = CompareProgress {
+            compare_id: compare_id.clone(),
+            source_rows: merge.source_rows,
+            target_rows: merge.target_rows,
+            stats: merge.stats,
+            chunks: merge.chunks,
+        };
+        let _ = app.emit(PROGRESS_EVENT, progress);
+    }
+}
+
+// 逐行模式：两侧整表按键排序流式读取后归并
+async fn compare_rows(
+    app: AppHandle,
+    state: &AppState,
+    request: &CompareRequest,
+    compare_id: &str,
+    queries: (SideQuery, SideQuery),
+    mut merge: Merge,
+) -> Result<(Merge, Result<(), Stop>), String> {
+    let options = |side: &SideConfig, suffix: &str| CursorOptions {
+        page_size: 1000,
+        max_rows: u64::MAX,
+        timeout_secs: request.timeout_secs.unwrap_or(0),
+        db: Some(side.db.clone()),
+        query_id: Some(format!("{}:{}", compare_id, suffix)),
+    };
+    let (source_query, target_query) = queries;
+    let source_sql = source_query.rows_sql(&source_query.condition(None, None).0);
+    let target_sql = target_query.rows_sql(&target_query.condition(None, None).0);
+    let mut source = cursor::open(state, &request.source.conn_id, source_sql, options(&request.source, "source")).await?;
+    let mut target = match cursor::open(state, &request.target.conn_id, target_sql, options(&request.target, "target")).await {
+        Ok(target) => target,
+        Err(e) => {
+            release(source).await;
+            return Err(e);
+        }
+    };
+    if let Err(e) = register(state, compare_id, request, source.canceller(), target.canceller()) {
+        release((source, target)).await;
+        return Err(e);
+    }
+    let mut report = progress_reporter(app, compare_id.to_string());
+    driver::blocking(move || {
+        let result = merge.run(&mut source, &mut target, &mut report);
+        // 提前结束时中止另一侧仍在进行的查询
+        drop(source);
+        drop(target);
+        Ok((merge, result))
+    })
+    .await
+}
+
+// 执行查询并读取全部行
+fn query(driver: &mut dyn DbDriver, sql: &str, params: Vec<Value>) -> Result<Vec<Value>, String> {
+    let params = (!params.is_empty()).then_some(Value::Array(params));
+    let mut rows = Vec::new();
+    driver.stream(sql, params, &mut |event| {
+        if let StreamEvent::Row(row) = event {
+            rows.push(row);
+        }
+        true
+    })?;
+    Ok(rows)
+}
+
+type Statement = (String, Vec<Value>);
+
+// 两侧的查询并行执行
+fn query_both(source: &mut dyn DbDriver, target: &mut dyn DbDriver, statements: (Statement, Statement)) -> (Result<Vec<Value>, String>, Result<Vec<Value>, String>) {
+    let ((source_sql, source_params), (target_sql, target_params)) = statements;
+    std::thread::scope(|scope| {
+        let handle = scope.spawn(|| query(source, &source_sql, source_params));
+        let target_rows = query(target, &target_sql, target_params);
+        (handle.join().unwrap_or_else(|_| Err("Query thread panicked".to_string())), target_rows)
+    })
+}
+
+struct Checksum {
+    source: SideQuery,
+    target: SideQuery,
+    flavor: Flavor,
+    chunk_size: usize,
+}
+
+impl Checksum {
+    // 分段读取与校验：上界取自源表，同一区间在两侧计算行数与校验和，不一致的分段再逐行归并
+    fn run(&self, merge: &mut Merge, source: &mut dyn DbDriver, target: &mut dyn DbDriver, cancel: &Canceller, report: &mut dyn FnMut(&Merge)) -> Result<(), Stop> {
+        let failed = |e: String| {
+            let status = if cancel.is_cancelled() { QueryStatus::Cancelled } else { QueryStatus::Failed };
+            Stop(status, e)
+        };
+        let mut chunks = ChunkStats::default();
+        let mut lower: Option<Vec<Value>> = None;
+        loop {
+            if cancel.is_cancelled() {
+                return Err(Stop(QueryStatus::Cancelled, "Query was cancelled".to_string()));
+            }
+            let (clause, params) = self.source.condition(lower.as_deref(), None);
+            let boundary = query(source, &self.source.boundary_sql(&clause, self.chunk_size), params).map_err(failed)?;
+            // 最后一段不设上界，包含目标表中超出源表键范围的行
+            let upper: Option<Vec<Value>> =
+                boundary.first().map(|row| self.source.key_names.iter().map(|k| row.get(k).cloned().unwrap_or(Value::Null)).collect());
+
+            let source_range = self.source.condition(lower.as_deref(), upper.as_deref());
+            let target_range = self.target.condition(lower.as_deref(), upper.as_deref());
+            let statements = (
+                (self.source.checksum_sql(&source_range.0, self.flavor), source_range.1.clone()),
+                (self.target.checksum_sql(&target_range.0, self.flavor), target_range.1.clone()),
+            );
+            let (source_sum, target_sum) = query_both(source, target, statements);
+            let (source_sum, target_sum) = (source_sum.map_err(failed)?, target_sum.map_err(failed)?);
+            let field = |rows: &[Value], name: &str| rows.first().and_then(|r| r.get(name)).cloned().unwrap_or(Value::Null);
+            let count = |rows: &[Value]| integer(&field(rows, "row_count")).unwrap_or(0) as u64;
+            chunks.total += 1;
+            let same = count(&source_sum) == count(&target_sum) && values_equal(&field(&source_sum, "checksum"), &field(&target_sum, "checksum"), true);
+            if same {
+                let rows = count(&source_sum);
+                merge.stats.matched += rows;
+                merge.source_rows += rows;
+                merge.target_rows += rows;
+            } else {
+                chunks.differing += 1;
+                let statements = (
+                    (self.source.rows_sql(&source_range.0), source_range.1),
+                    (self.target.rows_sql(&target_range.0), target_range.1),
+                );
+                let (source_rows, target_rows) = query_both(source, target, statements);
+                let (source_rows, target_rows) = (source_rows.map_err(failed)?, target_rows.map_err(failed)?);
+                merge.chunks = Some(chunks);
+                merge.run(&mut source_rows.into_iter(), &mut target_rows.into_iter(), report)?;
+            }
+            merge.chunks = Some(chunks);
+            report(merge);
+            match upper {
+                Some(upper) => lower = Some(upper),
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+// 校验和模式：两侧数据库只返回每段的行数与校验和，适合跨网络对比超大表
+async fn compare_checksum(
+    app: AppHandle,
+    state: &AppState,
+    request: &CompareRequest,
+    compare_id: &str,
+    checksum: Checksum,
+    mut merge: Merge,
+) -> Result<(Merge, Result<(), Stop>), String> {
+    let open = |side: &SideConfig| -> Result<Box<dyn DbDriver>, String> { driver::open(state, &side.conn_id, Some(&side.db)) };
+    let (source, target) = (open(&request.source)?, open(&request.target)?);
+    let opened = driver::blocking(move || {
+        let (mut source, mut target) = (source, target);
+        let cancellers = source.canceller().and_then(|s| Ok((Arc::new(s), Arc::new(target.canceller()?))));
+        Ok((source, target, cancellers))
+    })
+    .await?;
+    let (mut source, mut target, cancellers) = opened;
+    let registered = cancellers.and_then(|(s, t)| register(state, compare_id, request, s, t));
+    let cancel = match registered {
+        Ok(cancel) => cancel,
+        Err(e) => {
+            release((source, target)).await;
+            return Err(e);
+        }
+    };
+    let mut report = progress_reporter(app, compare_id.to_string());
+    driver::blocking(move || {
+        let result = checksum.run(&mut merge, source.as_mut(), target.as_mut(), &cancel, &mut report);
+        drop(source);
+        drop(target);
+        Ok((merge, result))
+    })
+    .await
+}
+
+// 对比两张表的数据：两侧按键排序流式读取后归并，内存占用与表大小无关
+// mode 为 checksum 时按键范围分段比较校验和，只逐行对比不一致的分段 (两侧需为同类数据库，键列不可为 NULL，文本键两侧排序规则相同)
+// 进度通过 db-compare-progress 事件上报；样本保留在后端，用 db_compare_samples 分页读取
+#[tauri::command]
+pub async fn db_compare_tables(app: AppHandle, state: State<'_, AppState>, request: CompareRequest) -> Result<CompareSummary, String> {
+    if request.primary_keys.iter().all(|k| k.field.trim().is_empty()) {
+        return Err("At least one key column is required".to_string());
+    }
+    let (source_config, source_columns) = describe(&state, &request.source).await?;
+    let (target_config, target_columns) = describe(&state, &request.target).await?;
+    let flavor = match request.mode {
+        CompareMode::Rows => None,
+        CompareMode::Checksum => {
+            let flavor = flavor_of(&source_config)?;
+            if flavor_of(&target_config)? != flavor {
+                return Err("Checksum compare requires both tables on the same kind of database".to_string());
+            }
+            Some(flavor)
+        }
+    };
+
+    let ignored = |name: &str| request.ignore_columns.iter().any(|c| c.eq_ignore_ascii_case(name));
+    let wanted = |name: &str| request.columns.as_ref().is_none_or(|cols| cols.iter().any(|c| c.eq_ignore_ascii_case(name)));
+    let pairs = match_columns(&source_columns, &target_columns);
+    let mut keys = Vec::new();
+    let mut key_pairs = Vec::new();
+    for key in request.primary_keys.iter().filter(|k| !k.field.trim().is_empty()) {
+        let &(i, j) = pairs
+            .iter()
+            .find(|(i, _)| source_columns[*i].name.eq_ignore_ascii_case(key.field.trim()))
+            .ok_or_else(|| format!("Key column {} was not found in both tables", key.field))?;
+        // 键为 NULL 的行不在任何分段区间内，校验和模式会漏掉它们
+        if flavor.is_some() && (source_columns[i].nullable || target_columns[j].nullable) {
+            return Err(format!("Checksum compare requires NOT NULL key columns; {} is nullable, use the rows mode", key.field.trim()));
+        }
+        // 分段条件按各自列的排序规则比较，文本键的排序规则不同时两侧同一区间包含的行不同
+        let (source_collation, target_collation) = (source_columns[i].collation.as_deref(), target_columns[j].collation.as_deref());
+        let text = key_kind(&source_columns[i].col_type) == KeyKind::Text || key_kind(&target_columns[j].col_type) == KeyKind::Text;
+        if flavor.is_some() && text && !same_collation(source_collation, target_collation) {
+            return Err(format!(
+                "Checksum compare requires the same collation on both sides for text key {} ({} vs {}), use the rows mode",
+                key.field.trim(),
+                source_collation.unwrap_or("default"),
+                target_collation.unwrap_or("default")
+            ));
+        }
+        keys.push(KeySpec {
+            source: source_columns[i].name.clone(),
+            target: target_columns[j].name.clone(),
+            source_kind: key_kind(&source_columns[i].col_type),
+            target_kind: key_kind(&target_columns[j].col_type),
+            source_collation: source_columns[i].collation.clone(),
+            target_collation: target_columns[j].collation.clone(),
+            order: key.order,
+        });
+        key_pairs.push(ColumnPair::new(&source_columns[i], &target_columns[j]));
+    }
+    let columns: Vec<ColumnPair> = pairs
+        .iter()
+        .map(|&(i, j)| ColumnPair::new(&source_columns[i], &target_columns[j]))
+        .filter(|c| !keys.iter().any(|k| k.source == c.source) && wanted(&c.source) && !ignored(&c.source))
+        .collect();
+
+    let select = |side: Side| -> Vec<&str> {
+        let names = keys.iter().map(|k| (&k.source, &k.target)).chain(columns.iter().map(|c| (&c.source, &c.target)));
+        names.map(|(s, t)| if matches!(side, Side::Source) { s.as_str() } else { t.as_str() }).collect()
+    };
+    let filter = request.filter_condition.as_deref();
+    let source_keys: Vec<_> = keys.iter().map(|k| (k.source.as_str(), k.source_kind, k.order, k.source_collation.as_deref())).collect();
+    let target_keys: Vec<_> = keys.iter().map(|k| (k.target.as_str(), k.target_kind, k.order, k.target_collation.as_deref())).collect();
+    let source_query = SideQuery::new(&source_config, &request.source, &select(Side::Source), &source_keys, filter)?;
+    let target_query = SideQuery::new(&target_config, &request.target, &select(Side::Target), &target_keys, filter)?;
+    let compared: Vec<String> = select(Side::Source).into_iter().map(str::to_string).collect();
+
+    let compare_id = request.compare_id.clone().filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.cursors.next_id("compare"));
+    let started = Instant::now();
+    let merge = Merge {
+        keys,
+        columns,
+        include_matches: request.include_matches,
+        max_samples: request.max_samples.unwrap_or(10_000),
+        stats: CompareStats::default(),
+        samples: Vec::new(),
+        samples_truncated: false,
+        source_rows: 0,
+        target_rows: 0,
+        chunks: None,
+    };
+    let result = match flavor {
+        None => compare_rows(app, &state, &request, &compare_id, (source_query, target_query), merge).await,
+        Some(flavor) => {
+            let chunk_size = request.chunk_size.unwrap_or(10_000).max(1);
+            let checksum = Checksum { source: source_query, target: target_query, flavor, chunk_size };
+            compare_checksum(app, &state, &request, &compare_id, checksum, merge).await
+        }
+    };
+    state.queries.remove(&compare_id);
+    let (merge, result) = result?;
+
+    let (status, error) = match result {
+        Ok(()) => (QueryStatus::Completed, None),
+        Err(Stop(status, error)) => (status, Some(error)),
+    };
+    let page_size = request.page_size.unwrap_or(100).clamp(1, MAX_PAGE_SIZE);
+    let summary = CompareSummary {
+        compare_id: compare_id.clone(),
+        stats: merge.stats,
+        columns: compared,
+        source_rows: merge.source_rows,
+        target_rows: merge.target_rows,
+        chunks: merge.chunks,
+        samples: merge.samples.iter().take(page_size).cloned().collect(),
+        sample_total: merge.samples.len(),
+        samples_truncated: merge.samples_truncated,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        status,
+        error,
+    };
+    let target = CompareTarget {
+        side: request.target.clone(),
+        keys: key_pairs,
+        columns: merge.columns,
+        complete: summary.status == QueryStatus::Completed && !summary.samples_truncated,
+    };
+    state.compares.insert(&compare_id, merge.samples, target);
+    Ok(summary)
+}
+
+// 分页读取对比样本，可按状态筛选
+#[tauri::command]
+pub fn db_compare_samples(
+    state: State<'_, AppState>,
+    compare_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    status: Option<CompareRowStatus>,
+) -> Result<ComparePage, String> {
+    state.compares.page(&compare_id, offset.unwrap_or(0), limit.unwrap_or(100), status)
+}
+
+// 释放对比结果，返回结果是否存在
+#[tauri::command]
+pub fn db_compare_close(state: State<'_, AppState>, compare_id: String) -> bool {
+    state.compares.remove(&compare_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cmp(a: Value, b: Value) -> Ordering {
+        compare_numbers(&a, &b).unwrap()
+    }
+
+    #[test]
+    fn compares_decimal_strings_exactly() {
+        assert_eq!(cmp(json!("12345678901234567890.123456789012345678"), json!("12345678901234567890.123456789012345679")), Ordering::Less);
+        assert_eq!(cmp(json!("1.50"), json!("1.5")), Ordering::Equal);
+        assert_eq!(cmp(json!("-0.00"), json!("0")), Ordering::Equal);
+        assert_eq!(cmp(json!("-2.5"), json!("-10")), Ordering::Greater);
+        assert_eq!(cmp(json!("0.45"), json!("0.5")), Ordering::Less);
+        assert_eq!(cmp(json!("007.1"), json!(7)), Ordering::Greater);
+        assert_eq!(cmp(json!(1.5), json!("1.50")), Ordering::Equal);
+        assert!(values_equal(&json!("100.000"), &json!(100), false));
+    }
+
+    #[test]
+    fn compares_strings_numerically_only_for_numeric_columns() {
+        assert!(values_equal(&json!("1.50"), &json!("1.5000"), true));
+        assert!(!values_equal(&json!("1.50"), &json!("1.5000"), false));
+        assert!(!values_equal(&json!("1.5"), &json!("1.51"), true));
+        assert!(!values_equal(&json!("abc"), &json!("ABC"), true));
+        assert!(!values_equal(&json!(null), &json!("0"), true));
+    }
+
+    #[test]
+    fn orders_by_column_when_collation_is_bytewise() {
+        assert!(byte_ordered(Backend::MySql, Some("utf8mb4_bin")));
+        assert!(byte_ordered(Backend::Postgres, Some("C")));
+        assert!(!byte_ordered(Backend::MySql, Some("utf8mb4_0900_ai_ci")));
+        assert!(!byte_ordered(Backend::MySql, None));
+    }
+
+    #[test]
+    fn checksum_keys_need_matching_collations() {
+        assert!(same_collation(Some("utf8mb4_bin"), Some("UTF8MB4_BIN")));
+        assert!(same_collation(None, None));
+        assert!(!same_collation(Some("utf8mb4_general_ci"), Some("utf8mb4_bin")));
+        assert!(!same_collation(Some("C"), None));
+    }
+}
@@ -0,0 +1,426 @@
+// 查询游标：结果在独立线程中流式读取，前端按页拉取；行数上限与等待超时在这里控制
+
+use super::driver::{self, Canceller, DbDriver, StreamEvent};
+use super::model::{QueryColumn, QueryResult, QueryStatus};
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+// 单页行数上限，避免一次传给前端过多数据
+const MAX_PAGE_SIZE: usize = 10_000;
+// 游标超过该时间未被访问时关闭
+const IDLE_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CursorOptions {
+    // 打开游标时返回的首页行数，也是 fetch 未指定行数时的默认值
+    pub page_size: usize,
+    // 最多读取的行数，超出部分不再读取并标记 truncated；0 表示不限
+    pub max_rows: u64,
+    // 等待服务端返回下一行的最长时间，超时后中止查询；0 表示不限
+    pub timeout_secs: u64,
+    // 查询的数据库 (MySQL 系切换默认库，PostgreSQL 为连接的库，SQLite 忽略)
+    pub db: Option<String>,
+    // 由前端指定，用于 db_cancel_query；游标省略时自动生成，并作为游标 id
+    pub query_id: Option<String>,
+}
+
+impl Default for CursorOptions {
+    fn default() -> Self {
+        CursorOptions { page_size: 500, max_rows: 100_000, timeout_secs: 30, db: None, query_id: None }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPage {
+    pub cursor_id: String,
+    // 仅打开游标时返回
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<QueryColumn>>,
+    pub rows: Vec<serde_json::Value>,
+    // 已读取的总行数 (含本页)
+    pub fetched: u64,
+    // 结果已读完或已中止，游标随之关闭
+    pub done: bool,
+    // 因 maxRows 未读完全部结果
+    pub truncated: bool,
+    // 结果读完时的总行数；流式读取无法提前得知
+    pub total: Option<u64>,
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+enum Message {
+    Event(StreamEvent),
+    End,
+    Failed(String),
+}
+
+pub struct Cursor {
+    id: String,
+    rx: Receiver<Message>,
+    cancel: Arc<Canceller>,
+    options: CursorOptions,
+    columns: Vec<QueryColumn>,
+    fetched: u64,
+    truncated: bool,
+    status: QueryStatus,
+    error: Option<String>,
+    last_used: Instant,
+}
+
+impl Cursor {
+    // 在阻塞线程中调用：把驱动交给读取线程并等待列信息；执行失败时返回已结束的游标
+    fn start(id: String, mut driver: Box<dyn DbDriver>, cancel: Arc<Canceller>, sql: String, params: Option<serde_json::Value>, options: CursorOptions) -> Cursor {
+        // 有界通道：前端未拉取时读取线程阻塞，不会把整个结果读入内存
+        let (tx, rx) = mpsc::sync_channel::<Message>(options.page_size.clamp(1, MAX_PAGE_SIZE));
+        let worker_cancel = cancel.clone();
+        std::thread::spawn(move || {
+            // PostgreSQL 游标在两次读取之间收到的 cancel request 不生效，取消后由这里停止读取
+            let result = driver.stream(&sql, params, &mut |event| !worker_cancel.is_cancelled() && tx.send(Message::Event(event)).is_ok());
+            let _ = tx.send(match result {
+                Ok(()) => Message::End,
+                Err(e) => Message::Failed(e),
+            });
+            // 驱动 (及 PostgreSQL 客户端) 在该线程中释放
+            drop(driver);
+        });
+
+        let mut cursor = Cursor {
+            id,
+            rx,
+            cancel,
+            options,
+            columns: Vec::new(),
+            fetched: 0,
+            truncated: false,
+            status: QueryStatus::Running,
+            error: None,
+            last_used: Instant::now(),
+        };
+        match cursor.recv() {
+            Some(Message::Event(StreamEvent::Columns(columns))) => cursor.columns = columns,
+            Some(Message::Failed(e)) => cursor.finish(QueryStatus::Failed, Some(e)),
+            Some(_) => cursor.finish(QueryStatus::Failed, Some("Query returned no result set".to_string())),
+            None => {}
+        }
+        cursor
+    }
+
+    fn done(&self) -> bool {
+        self.status != QueryStatus::Running
+    }
+
+    // 用户已取消时，之后的出错或结束都记为 cancelled
+    fn finish(&mut self, status: QueryStatus, error: Option<String>) {
+        if self.done() {
+            return;
+        }
+        if self.cancel.is_cancelled() {
+            self.status = QueryStatus::Cancelled;
+            self.error = Some("Query was cancelled".to_string());
+        } else {
+            self.status = status;
+            self.error = error;
+        }
+    }
+
+    // 等待下一条消息，超时后中止查询；None 表示游标已结束
+    fn recv(&mut self) -> Option<Message> {
+        let timeout = self.options.timeout_secs;
+        let received = match timeout {
+            0 => self.rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            _ => self.rx.recv_timeout(Duration::from_secs(timeout)),
+        };
+        match received {
+            Ok(message) => Some(message),
+            Err(RecvTimeoutError::Disconnected) => {
+                self.finish(QueryStatus::Completed, None);
+                None
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                self.abort();
+                self.finish(QueryStatus::TimedOut, Some(format!("Query timed out after {}s", timeout)));
+                None
+            }
+        }
+    }
+
+    // 未读完时中止服务端查询
+    fn abort(&mut self) {
+        if !self.done() {
+            self.cancel.interrupt();
+        }
+    }
+
+    // 读取下一行；结果读完、失败、取消或超时后返回 None，结果见 status/error
+    pub fn next_row(&mut self) -> Option<serde_json::Value> {
+        while !self.done() {
+            if self.cancel.is_cancelled() {
+                self.finish(QueryStatus::Cancelled, None);
+                break;
+            }
+            match self.recv() {
+                Some(Message::Event(StreamEvent::Row(row))) => {
+                    // 达到上限后再读到一行说明结果被截断
+                    if self.options.max_rows != 0 && self.fetched >= self.options.max_rows {
+                        self.truncated = true;
+                        self.abort();
+                        self.finish(QueryStatus::Completed, None);
+                        break;
+                    }
+                    self.fetched += 1;
+                    return Some(row);
+                }
+                Some(Message::Event(StreamEvent::Columns(columns))) => self.columns = columns,
+                Some(Message::Failed(e)) => self.finish(QueryStatus::Failed, Some(e)),
+                Some(Message::End) => self.finish(QueryStatus::Completed, None),
+                None => {}
+            }
+        }
+        None
+    }
+
+    pub fn columns(&self) -> &[QueryColumn] {
+        &self.columns
+    }
+
+    pub fn status(&self) -> QueryStatus {
+        self.status
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn canceller(&self) -> Arc<Canceller> {
+        self.cancel.clone()
+    }
+
+    fn next_page(&mut self, size: usize) -> CursorPage {
+        self.last_used = Instant::now();
+        let size = size.clamp(1, MAX_PAGE_SIZE);
+        let mut rows = Vec::new();
+        while rows.len() < size {
+            match self.next_row() {
+                Some(row) => rows.push(row),
+                None => break,
+            }
+        }
+        CursorPage {
+            cursor_id: self.id.clone(),
+            columns: None,
+            rows,
+            fetched: self.fetched,
+            done: self.done(),
+            truncated: self.truncated,
+            total: (self.status == QueryStatus::Completed && !self.truncated).then_some(self.fetched),
+            status: self.status,
+            error: self.error.clone(),
+        }
+    }
+}
+
+impl Drop for Cursor {
+    // 未读完就关闭时先中止查询，避免读取线程继续接收剩余结果
+    fn drop(&mut self) {
+        self.abort();
+    }
+}
+
+// 已打开的游标
+#[derive(Default)]
+pub struct CursorRegistry {
+    cursors: Mutex<HashMap<String, Arc<Mutex<Cursor>>>>,
+    counter: AtomicU64,
+}
+
+impl CursorRegistry {
+    // 游标、查询与导出共用的 id 序号
+    pub fn next_id(&self, prefix: &str) -> String {
+        let millis = chrono::Local::now().timestamp_millis();
+        format!("{}-{}-{}", prefix, millis, self.counter.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn get(&self, id: &str) -> Result<Arc<Mutex<Cursor>>, String> {
+        self.cursors.lock().unwrap().get(id).cloned().ok_or_else(|| format!("Cursor not found: {}", id))
+    }
+
+    fn insert(&self, cursor: Cursor) {
+        self.cursors.lock().unwrap().insert(cursor.id.clone(), Arc::new(Mutex::new(cursor)));
+    }
+
+    // 移出的游标析构时可能需要连接服务端中止查询，应在阻塞线程中释放
+    fn remove(&self, id: &str) -> Option<Arc<Mutex<Cursor>>> {
+        self.cursors.lock().unwrap().remove(id)
+    }
+
+    // 关闭空闲超时的游标；正在拉取的游标跳过 (在后台线程中调用)
+    // 析构时会连接服务端中止查询，先在锁内移出，释放锁后再析构
+    pub fn sweep(&self) {
+        let expired: Vec<Arc<Mutex<Cursor>>> = {
+            let mut cursors = self.cursors.lock().unwrap();
+            let ids: Vec<String> = cursors
+                .iter()
+                .filter(|(_, cursor)| cursor.try_lock().is_ok_and(|c| c.last_used.elapsed() >= Duration::from_secs(IDLE_TIMEOUT_SECS)))
+                .map(|(id, _)| id.clone())
+                .collect();
+            ids.iter().filter_map(|id| cursors.remove(id)).collect()
+        };
+        drop(expired);
+    }
+}
+
+// 在阻塞线程中取得中止句柄 (PostgreSQL 需先建立连接)，登记后才开始执行
+async fn prepare(state: &AppState, id: &str, query_id: &str, sql: &str, db: Option<&str>) -> Result<(Box<dyn DbDriver>, Arc<Canceller>), String> {
+    let mut driver = driver::open(state, id, db)?;
+    let (driver, cancel) = driver::blocking(move || {
+        let cancel = Arc::new(driver.canceller()?);
+        Ok((driver, cancel))
+    })
+    .await?;
+    if let Err(e) = state.queries.register(query_id, id, sql, cancel.clone()) {
+        // PostgreSQL 客户端关闭时会阻塞
+        driver::blocking(move || {
+            drop(driver);
+            Ok(())
+        })
+        .await?;
+        return Err(e);
+    }
+    Ok((driver, cancel))
+}
+
+// 完整读取的结果状态
+pub struct Drained {
+    pub columns: Vec<QueryColumn>,
+    pub rows: u64,
+    pub truncated: bool,
+    pub status: QueryStatus,
+    pub error: Option<String>,
+}
+
+// 接收完整结果的每一页 (含空结果的首页)
+pub trait PageSink: Send + 'static {
+    fn page(&mut self, columns: &[QueryColumn], rows: Vec<serde_json::Value>) -> Result<(), String>;
+}
+
+impl PageSink for Vec<serde_json::Value> {
+    fn page(&mut self, _columns: &[QueryColumn], rows: Vec<serde_json::Value>) -> Result<(), String> {
+        self.extend(rows);
+        Ok(())
+    }
+}
+
+// 逐页读取完整结果 (db_query、导出)，sink 出错时中止查询；sink 处理完后随结果返回
+// 同样受 maxRows 与超时限制，登记在 queryId 下可被取消
+pub async fn drain<S: PageSink>(state: &AppState, id: &str, sql: String, params: Option<serde_json::Value>, options: CursorOptions, mut sink: S) -> Result<(Drained, S), String> {
+    let query_id = options.query_id.clone().unwrap_or_else(|| state.cursors.next_id("query"));
+    let (driver, cancel) = prepare(state, id, &query_id, &sql, options.db.as_deref()).await?;
+    let result = driver::blocking(move || {
+        let mut cursor = Cursor::start(String::new(), driver, cancel, sql, params, options);
+        let mut first = true;
+        while first || !cursor.done() {
+            first = false;
+            let page = cursor.next_page(MAX_PAGE_SIZE);
+            if let Err(e) = sink.page(&cursor.columns, page.rows) {
+                cursor.abort();
+                cursor.finish(QueryStatus::Failed, Some(e));
+            }
+        }
+        let drained = Drained {
+            columns: std::mem::take(&mut cursor.columns),
+            rows: cursor.fetched,
+            truncated: cursor.truncated,
+            status: cursor.status,
+            error: cursor.error.take(),
+        };
+        Ok((drained, sink))
+    })
+    .await;
+    state.queries.remove(&query_id);
+    result
+}
+
+// 由后端逐行读取结果 (数据对比)；游标在阻塞线程中读取与释放，释放后登记自动失效
+pub async fn open(state: &AppState, id: &str, sql: String, options: CursorOptions) -> Result<Cursor, String> {
+    let query_id = options.query_id.clone().unwrap_or_else(|| state.cursors.next_id("query"));
+    let (driver, cancel) = prepare(state, id, &query_id, &sql, options.db.as_deref()).await?;
+    driver::blocking(move || Ok(Cursor::start(query_id, driver, cancel, sql, None, options))).await
+}
+
+pub async fn run(state: &AppState, id: &str, sql: String, params: Option<serde_json::Value>, options: CursorOptions) -> Result<QueryResult, String> {
+    let (drained, rows) = drain(state, id, sql, params, options, Vec::new()).await?;
+    Ok(QueryResult { columns: drained.columns, rows, truncated: drained.truncated, status: drained.status, error: drained.error })
+}
+
+// 打开游标并返回首页；结果一页即可读完时游标直接关闭
+#[tauri::command]
+pub async fn db_cursor_open(
+    state: State<'_, AppState>,
+    id: String,
+    sql: String,
+    params: Option<serde_json::Value>,
+    options: Option<CursorOptions>,
+) -> Result<CursorPage, String> {
+    let options = options.unwrap_or_default();
+    let cursor_id = options.query_id.clone().unwrap_or_else(|| state.cursors.next_id("cursor"));
+    if state.cursors.get(&cursor_id).is_ok() {
+        return Err(format!("Cursor {} is already open", cursor_id));
+    }
+    // 游标关闭后中止句柄随之释放，登记自动失效
+    let (driver, cancel) = prepare(&state, &id, &cursor_id, &sql, options.db.as_deref()).await?;
+    let page_size = options.page_size;
+    let (cursor, page) = driver::blocking(move || {
+        let mut cursor = Cursor::start(cursor_id, driver, cancel, sql, params, options);
+        let mut page = cursor.next_page(page_size);
+        page.columns = Some(cursor.columns.clone());
+        Ok((cursor, page))
+    })
+    .await?;
+    if !page.done {
+        state.cursors.insert(cursor);
+    }
+    Ok(page)
+}
+
+// 拉取下一页，size 省略时使用打开时的 pageSize
+#[tauri::command]
+pub async fn db_cursor_fetch(state: State<'_, AppState>, cursor_id: String, size: Option<usize>) -> Result<CursorPage, String> {
+    let cursor = state.cursors.get(&cursor_id)?;
+    let result = driver::blocking({
+        let cursor = cursor.clone();
+        move || {
+            let mut cursor = cursor.lock().unwrap();
+            let size = size.unwrap_or(cursor.options.page_size);
+            Ok(cursor.next_page(size))
+        }
+    })
+    .await;
+    // 读完、失败、取消或超时后游标不可再用
+    if result.as_ref().map_or(true, |page| page.done) {
+        state.cursors.remove(&cursor_id);
+    }
+    result
+}
+
+// 返回游标是否存在；未读完的查询会被中止
+#[tauri::command]
+pub async fn db_cursor_close(state: State<'_, AppState>, cursor_id: String) -> Result<bool, String> {
+    let Some(cursor) = state.cursors.remove(&cursor_id) else {
+        return Ok(false);
+    };
+    driver::blocking(move || {
+        drop(cursor);
+        Ok(true)
+    })
+    .await
+}
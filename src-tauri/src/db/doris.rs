@@ -0,0 +1,462 @@
+// Doris 表元数据：解析 SHOW CREATE TABLE / SHOW PARTITIONS，并读取 Rollup 与物化视图
+
+use crate::sql::Dialect;
+use mysql::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DorisInfo {
+    // DUPLICATE / UNIQUE / AGGREGATE
+    pub key_model: Option<String>,
+    pub key_columns: Vec<String>,
+    // HASH / RANDOM
+    pub distribution: Option<String>,
+    pub distribution_columns: Vec<String>,
+    // 数字或 AUTO
+    pub buckets: Option<String>,
+    // RANGE / LIST，未分区为 None
+    pub partition_type: Option<String>,
+    pub partition_columns: Vec<String>,
+    pub auto_partition: bool,
+    pub partitions: Vec<DorisPartition>,
+    pub rollups: Vec<DorisRollup>,
+    pub materialized_views: Vec<DorisMaterializedView>,
+    pub properties: BTreeMap<String, String>,
+    // 分区、Rollup 或物化视图查询失败的原因，对应字段留空
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DorisPartition {
+    pub id: Option<String>,
+    pub name: String,
+    pub state: Option<String>,
+    // SHOW PARTITIONS 的原始 Range 文本
+    pub range: Option<String>,
+    // RANGE 分区的上下界 (左闭右开)
+    pub lower: Option<String>,
+    pub upper: Option<String>,
+    // LIST 分区的取值
+    pub values: Vec<String>,
+    pub buckets: Option<u32>,
+    pub replication_num: Option<u32>,
+    pub storage_medium: Option<String>,
+    pub data_size: Option<String>,
+    pub row_count: Option<i64>,
+    pub visible_version_time: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DorisRollup {
+    pub name: String,
+    pub keys_type: Option<String>,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DorisMaterializedView {
+    pub name: String,
+    // 同步物化视图 (单表，随导入更新) 或异步物化视图
+    pub sync: bool,
+    pub columns: Vec<String>,
+    // 同步视图为各列的定义表达式，异步视图为查询语句
+    pub definition: Option<String>,
+    pub state: Option<String>,
+    pub refresh: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    // 反引号标识符
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+impl Token {
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self, Token::Word(w) if w.eq_ignore_ascii_case(word))
+    }
+
+    fn text(&self) -> String {
+        match self {
+            Token::Word(s) | Token::Ident(s) | Token::Str(s) => s.clone(),
+            Token::Punct(c) => c.to_string(),
+        }
+    }
+}
+
+fn tokenize(sql: &str) -> Vec<Token> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '`' || c == '\'' || c == '"' {
+            // 引号内：反斜杠转义 (反引号除外) 与重复引号
+            let mut text = String::new();
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == '\\' && c != '`' && i + 1 < chars.len() {
+                    text.push(chars[i + 1]);
+                    i += 2;
+                } else if chars[i] == c {
+                    if chars.get(i + 1) == Some(&c) {
+                        text.push(c);
+                        i += 2;
+                    } else {
+                        i += 1;
+                        break;
+                    }
+                } else {
+                    text.push(chars[i]);
+                    i += 1;
+                }
+            }
+            tokens.push(if c == '`' { Token::Ident(text) } else { Token::Str(text) });
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.') {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+// tokens[start] 为 '(' 时返回括号内的记号与右括号之后的位置
+// 分区区间 [('a'), ('b')) 为左闭右开写法，'[' 与 ')' 配对
+fn group(tokens: &[Token], start: usize) -> Option<(&[Token], usize)> {
+    if tokens.get(start) != Some(&Token::Punct('(')) {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, t) in tokens.iter().enumerate().skip(start) {
+        match t {
+            Token::Punct('(' | '[') => depth += 1,
+            Token::Punct(')' | ']') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&tokens[start + 1..i], i + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// 括号内的列名；表达式分区 (如 date_trunc(`dt`, 'day')) 只取反引号标识符
+fn column_names(tokens: &[Token]) -> Vec<String> {
+    let quoted: Vec<String> = tokens.iter().filter_map(|t| if let Token::Ident(s) = t { Some(s.clone()) } else { None }).collect();
+    if !quoted.is_empty() {
+        return quoted;
+    }
+    tokens
+        .iter()
+        .enumerate()
+        .filter_map(|(i, t)| match t {
+            Token::Word(w) if tokens.get(i + 1) != Some(&Token::Punct('(')) => Some(w.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+// 解析 SHOW CREATE TABLE 中列定义之后的表属性
+pub fn parse_create_table(ddl: &str) -> DorisInfo {
+    let tokens = tokenize(ddl);
+    let mut info = DorisInfo::default();
+    // 跳过列定义
+    let Some(tail_start) = tokens.iter().position(|t| *t == Token::Punct('(')).and_then(|p| group(&tokens, p)).map(|(_, end)| end) else {
+        return info;
+    };
+
+    let mut i = tail_start;
+    while i < tokens.len() {
+        let t = &tokens[i];
+        let next = tokens.get(i + 1);
+        if (t.is_word("DUPLICATE") || t.is_word("UNIQUE") || t.is_word("AGGREGATE")) && next.is_some_and(|n| n.is_word("KEY")) {
+            if let Some((inner, end)) = group(&tokens, i + 2) {
+                info.key_model = Some(t.text().to_uppercase());
+                info.key_columns = column_names(inner);
+                i = end;
+                continue;
+            }
+        } else if t.is_word("PARTITION") && next.is_some_and(|n| n.is_word("BY")) {
+            info.auto_partition = i > 0 && tokens[i - 1].is_word("AUTO");
+            let mut j = i + 2;
+            if let Some(kind) = tokens.get(j).filter(|k| k.is_word("RANGE") || k.is_word("LIST")) {
+                info.partition_type = Some(kind.text().to_uppercase());
+                j += 1;
+            }
+            if let Some((inner, end)) = group(&tokens, j) {
+                info.partition_columns = column_names(inner);
+                // 分区定义以 SHOW PARTITIONS 为准，这里跳过
+                i = group(&tokens, end).map(|(_, e)| e).unwrap_or(end);
+                continue;
+            }
+        } else if t.is_word("DISTRIBUTED") && next.is_some_and(|n| n.is_word("BY")) {
+            let mut j = i + 2;
+            if let Some(kind) = tokens.get(j) {
+                info.distribution = Some(kind.text().to_uppercase());
+                j += 1;
+                if let Some((inner, end)) = group(&tokens, j) {
+                    info.distribution_columns = column_names(inner);
+                    j = end;
+                }
+            }
+            if tokens.get(j).is_some_and(|b| b.is_word("BUCKETS")) {
+                info.buckets = tokens.get(j + 1).map(|b| b.text().to_uppercase());
+                j += 2;
+            }
+            i = j;
+            continue;
+        } else if t.is_word("PROPERTIES") {
+            if let Some((inner, end)) = group(&tokens, i + 1) {
+                for pair in inner.split(|t| *t == Token::Punct(',')) {
+                    if let [key, Token::Punct('='), value] = pair {
+                        info.properties.insert(key.text(), value.text());
+                    }
+                }
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    info
+}
+
+// Range 文本形如 [types: [DATEV2]; keys: [2024-01-01]; ..types: [DATEV2]; keys: [2024-02-01]; )，取出各 keys
+fn range_keys(range: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = range;
+    while let Some(start) = rest.find("keys: [") {
+        let after = &rest[start + "keys: [".len()..];
+        let Some(end) = after.find(']') else { break };
+        keys.push(after[..end].to_string());
+        rest = &after[end + 1..];
+    }
+    keys
+}
+
+// 按列名取值 (不同 Doris 版本的 SHOW 结果列不同)
+fn column(row: &mysql::Row, name: &str) -> Option<String> {
+    let idx = row.columns_ref().iter().position(|c| c.name_str().eq_ignore_ascii_case(name))?;
+    row.get_opt::<Option<String>, _>(idx).and_then(|v| v.ok()).flatten().filter(|v| !v.is_empty())
+}
+
+fn partitions(conn: &mut mysql::Conn, db: &str, table: &str, partition_type: Option<&str>) -> Result<Vec<DorisPartition>, String> {
+    let d = Dialect::Doris;
+    let query = format!("SHOW PARTITIONS FROM {}.{}", d.quote_ident(db), d.quote_ident(table));
+    let rows: Vec<mysql::Row> = conn.query(&query).map_err(|e| format!("Failed to fetch partitions: {}", e))?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let range = column(row, "Range");
+            let keys = range.as_deref().map(range_keys).unwrap_or_default();
+            let (lower, upper, values) = match (partition_type, keys.as_slice()) {
+                (Some("RANGE"), [lower, upper]) => (Some(lower.clone()), Some(upper.clone()), Vec::new()),
+                (Some("LIST"), _) => (None, None, keys),
+                _ => (None, None, Vec::new()),
+            };
+            DorisPartition {
+                id: column(row, "PartitionId"),
+                name: column(row, "PartitionName").unwrap_or_default(),
+                state: column(row, "State"),
+                range,
+                lower,
+                upper,
+                values,
+                buckets: column(row, "Buckets").and_then(|v| v.parse().ok()),
+                replication_num: column(row, "ReplicationNum").and_then(|v| v.parse().ok()),
+                storage_medium: column(row, "StorageMedium"),
+                data_size: column(row, "DataSize").map(|v| v.trim().to_string()),
+                row_count: column(row, "RowCount").and_then(|v| v.parse().ok()),
+                visible_version_time: column(row, "VisibleVersionTime"),
+            }
+        })
+        .collect())
+}
+
+struct IndexGroup {
+    name: String,
+    keys_type: Option<String>,
+    columns: Vec<String>,
+    // 同步物化视图各列的定义表达式
+    defines: Vec<String>,
+}
+
+// DESC table ALL 按索引列出列：首行带 IndexName，其后同一索引的行 IndexName 为空
+fn indexes(conn: &mut mysql::Conn, db: &str, table: &str) -> Result<(Vec<DorisRollup>, Vec<DorisMaterializedView>), String> {
+    let d = Dialect::Doris;
+    let query = format!("DESC {}.{} ALL", d.quote_ident(db), d.quote_ident(table));
+    let rows: Vec<mysql::Row> = conn.query(&query).map_err(|e| format!("Failed to fetch rollups: {}", e))?;
+
+    let mut groups: Vec<IndexGroup> = Vec::new();
+    for row in &rows {
+        if let Some(name) = column(row, "IndexName") {
+            groups.push(IndexGroup { name, keys_type: column(row, "IndexKeysType"), columns: Vec::new(), defines: Vec::new() });
+        }
+        let (Some(group), Some(field)) = (groups.last_mut(), column(row, "Field")) else { continue };
+        if let Some(expr) = column(row, "DefineExpr") {
+            group.defines.push(format!("{} = {}", field, expr));
+        }
+        group.columns.push(field);
+    }
+
+    let mut rollups = Vec::new();
+    let mut views = Vec::new();
+    for IndexGroup { name, keys_type, columns, defines } in groups {
+        // 基础索引与表同名
+        if name == table {
+            continue;
+        }
+        // 同步物化视图的列带定义表达式或 mv_ 前缀
+        if !defines.is_empty() || columns.iter().any(|c| c.starts_with("mv_") || c.starts_with("mva_")) {
+            views.push(DorisMaterializedView {
+                name,
+                sync: true,
+                columns,
+                definition: (!defines.is_empty()).then(|| defines.join("\n")),
+                state: None,
+                refresh: None,
+            });
+        } else {
+            rollups.push(DorisRollup { name, keys_type, columns });
+        }
+    }
+    Ok((rollups, views))
+}
+
+// sql 中是否以独立标识符出现 table
+fn mentions(sql: &str, table: &str) -> bool {
+    let lower = sql.to_lowercase();
+    let table = table.to_lowercase();
+    let is_ident = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    lower.match_indices(&table).any(|(pos, _)| !is_ident(lower[..pos].chars().next_back()) && !is_ident(lower[pos + table.len()..].chars().next()))
+}
+
+// 异步物化视图 (Doris 2.1+)，引用了该表的才返回
+fn async_views(conn: &mut mysql::Conn, db: &str, table: &str) -> Result<Vec<DorisMaterializedView>, String> {
+    let query = format!("SELECT * FROM mv_infos('database' = {})", Dialect::Doris.quote_str(db));
+    let rows: Vec<mysql::Row> = conn.query(&query).map_err(|e| format!("Failed to fetch materialized views: {}", e))?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let sql = column(row, "QuerySql")?;
+            mentions(&sql, table).then(|| DorisMaterializedView {
+                name: column(row, "Name").unwrap_or_default(),
+                sync: false,
+                columns: Vec::new(),
+                definition: Some(sql),
+                state: column(row, "State"),
+                refresh: column(row, "RefreshInfo").or_else(|| column(row, "RefreshState")),
+            })
+        })
+        .collect())
+}
+
+// 建表语句解析失败不影响结果；分区、Rollup、物化视图查询失败时记入 warnings 并留空
+pub fn inspect(conn: &mut mysql::Conn, db: &str, table: &str, ddl: &str) -> DorisInfo {
+    let mut info = parse_create_table(ddl);
+    match partitions(conn, db, table, info.partition_type.as_deref()) {
+        Ok(parts) => info.partitions = parts,
+        Err(e) => info.warnings.push(e),
+    }
+    match indexes(conn, db, table) {
+        Ok((rollups, views)) => {
+            info.rollups = rollups;
+            info.materialized_views = views;
+        }
+        Err(e) => info.warnings.push(e),
+    }
+    match async_views(conn, db, table) {
+        Ok(views) => info.materialized_views.extend(views),
+        Err(e) => info.warnings.push(e),
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RANGE_DDL: &str = "CREATE TABLE `orders` (
+  `order_id` bigint NOT NULL COMMENT '订单 (id)',
+  `dt` date NOT NULL,
+  `amount` decimal(10,2) SUM NULL
+) ENGINE=OLAP
+AGGREGATE KEY(`order_id`, `dt`)
+COMMENT 'OLAP'
+PARTITION BY RANGE(`dt`)
+(PARTITION p202401 VALUES [('2024-01-01'), ('2024-02-01')),
+PARTITION p202402 VALUES [('2024-02-01'), ('2024-03-01')))
+DISTRIBUTED BY HASH(`order_id`) BUCKETS 16
+PROPERTIES (
+\"replication_allocation\" = \"tag.location.default: 3\",
+\"storage_medium\" = \"hdd\"
+);";
+
+    #[test]
+    fn parses_range_partitioned_aggregate_table() {
+        let info = parse_create_table(RANGE_DDL);
+        assert_eq!(info.key_model.as_deref(), Some("AGGREGATE"));
+        assert_eq!(info.key_columns, ["order_id", "dt"]);
+        assert_eq!(info.partition_type.as_deref(), Some("RANGE"));
+        assert_eq!(info.partition_columns, ["dt"]);
+        assert!(!info.auto_partition);
+        assert_eq!(info.distribution.as_deref(), Some("HASH"));
+        assert_eq!(info.distribution_columns, ["order_id"]);
+        assert_eq!(info.buckets.as_deref(), Some("16"));
+        assert_eq!(info.properties.get("replication_allocation").map(String::as_str), Some("tag.location.default: 3"));
+        assert_eq!(info.properties.get("storage_medium").map(String::as_str), Some("hdd"));
+    }
+
+    #[test]
+    fn parses_list_partitioned_random_table() {
+        let ddl = "CREATE TABLE `events` (`city` varchar(32) NULL, `ts` datetime NULL) ENGINE=OLAP
+DUPLICATE KEY(`city`)
+AUTO PARTITION BY LIST (`city`) (PARTITION pbj VALUES IN ('beijing'))
+DISTRIBUTED BY RANDOM BUCKETS AUTO
+PROPERTIES (\"replication_num\" = \"1\");";
+        let info = parse_create_table(ddl);
+        assert_eq!(info.key_model.as_deref(), Some("DUPLICATE"));
+        assert_eq!(info.partition_type.as_deref(), Some("LIST"));
+        assert_eq!(info.partition_columns, ["city"]);
+        assert!(info.auto_partition);
+        assert_eq!(info.distribution.as_deref(), Some("RANDOM"));
+        assert!(info.distribution_columns.is_empty());
+        assert_eq!(info.buckets.as_deref(), Some("AUTO"));
+        assert_eq!(info.properties.get("replication_num").map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn unpartitioned_unique_table_with_backquoted_names() {
+        let info = parse_create_table("CREATE TABLE `t` (`a b` int, `c``d` int) UNIQUE KEY(`a b`, `c``d`) DISTRIBUTED BY HASH(`a b`) BUCKETS 1");
+        assert_eq!(info.key_model.as_deref(), Some("UNIQUE"));
+        assert_eq!(info.key_columns, ["a b", "c`d"]);
+        assert!(info.partition_type.is_none());
+        assert_eq!(info.distribution_columns, ["a b"]);
+    }
+
+    #[test]
+    fn extracts_range_keys() {
+        let range = "[types: [DATEV2]; keys: [2024-01-01]; ..types: [DATEV2]; keys: [2024-02-01]; )";
+        assert_eq!(range_keys(range), ["2024-01-01", "2024-02-01"]);
+        assert!(range_keys("").is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+// 数据库驱动抽象：db_* 命令按 DbConfig.db_type 分发到 MySQL (含 Doris)、PostgreSQL 或 SQLite
+
+use super::model::{QueryColumn, TableDetail, TableInfo};
+use super::mysql_driver::MySqlDriver;
+use super::postgres_driver::PostgresDriver;
+use super::registry::DbConfig;
+use super::sqlite_driver::SqliteDriver;
+use crate::sql::Dialect;
+use crate::AppState;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub trait DbDriver: Send {
+    // 执行一次简单查询确认连接可用
+    fn ping(&mut self) -> Result<(), String>;
+    fn databases(&mut self) -> Result<Vec<String>, String>;
+    fn tables(&mut self, db: &str) -> Result<Vec<TableInfo>, String>;
+    fn table_schema(&mut self, db: &str, table: &str) -> Result<TableDetail, String>;
+    // 流式读取第一个结果集：先回调列信息，再逐行回调，sink 返回 false 时停止读取
+    // params 为数组时按位置绑定，为对象时按名称绑定 (驱动支持时)
+    fn stream(&mut self, sql: &str, params: Option<serde_json::Value>, sink: &mut dyn FnMut(StreamEvent) -> bool) -> Result<(), String>;
+    // 执行不返回结果集的单条语句 (含 BEGIN/COMMIT)，返回影响行数
+    fn execute(&mut self, sql: &str) -> Result<u64, String>;
+    // 执行单条语句并返回自增 id、警告等细节，驱动不支持时只有影响行数
+    fn execute_detailed(&mut self, sql: &str) -> Result<ExecOutcome, String> {
+        Ok(ExecOutcome { affected_rows: self.execute(sql)?, ..ExecOutcome::default() })
+    }
+    // 返回可在其他线程中止该连接上正在执行语句的句柄
+    fn canceller(&mut self) -> Result<Canceller, String>;
+}
+
+#[derive(Debug, Default)]
+pub struct ExecOutcome {
+    pub affected_rows: u64,
+    pub last_insert_id: Option<u64>,
+    pub warnings: u16,
+    pub warning_messages: Vec<String>,
+    pub info: String,
+}
+
+pub enum StreamEvent {
+    Columns(Vec<QueryColumn>),
+    Row(serde_json::Value),
+}
+
+// 中止句柄：MySQL 为旁路连接 KILL QUERY，PostgreSQL 为 cancel request，SQLite 为 interrupt
+pub struct Canceller {
+    // 服务端连接 (线程) id，SQLite 没有
+    pub session_id: Option<u64>,
+    kill: Box<dyn Fn() + Send + Sync>,
+    cancelled: AtomicBool,
+}
+
+impl Canceller {
+    pub fn new(session_id: Option<u64>, kill: impl Fn() + Send + Sync + 'static) -> Canceller {
+        Canceller { session_id, kill: Box::new(kill), cancelled: AtomicBool::new(false) }
+    }
+
+    // 用户主动取消，结果状态记为 cancelled
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        (self.kill)();
+    }
+
+    // 超时、达到行数上限等内部中止
+    pub fn interrupt(&self) {
+        (self.kill)();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    // MySQL 协议 (MySQL/Doris)
+    MySql,
+    Postgres,
+    // 本地文件，host 为文件路径
+    Sqlite,
+}
+
+pub fn backend_of(config: &DbConfig) -> Result<Backend, String> {
+    Ok(match dialect_of(&config.db_type)? {
+        Dialect::MySql | Dialect::Doris => Backend::MySql,
+        Dialect::Postgres => Backend::Postgres,
+        Dialect::Sqlite => Backend::Sqlite,
+    })
+}
+
+// 连接类型对应的 SQL 方言，含 Dialect::parse 不接受的 SQLite
+pub fn dialect_of(db_type: &str) -> Result<Dialect, String> {
+    if matches!(db_type.trim().to_lowercase().as_str(), "sqlite" | "sqlite3") {
+        return Ok(Dialect::Sqlite);
+    }
+    Dialect::parse(db_type).ok_or_else(|| format!("Unsupported database type: {}", db_type))
+}
+
+// 表的引用及其标识符方言；表名按 tables 返回的形式 (PostgreSQL 非 public 表为 schema.table)
+pub fn table_ref(config: &DbConfig, db: &str, table: &str) -> Result<(Dialect, String), String> {
+    let (dialect, qualified) = match backend_of(config)? {
+        Backend::MySql => (Dialect::MySql, (!db.is_empty()).then_some((db, table))),
+        Backend::Postgres => (Dialect::Postgres, table.split_once('.')),
+        // db 为 main 或 ATTACH 的 schema
+        Backend::Sqlite => (Dialect::Sqlite, (!db.is_empty()).then_some((db, table))),
+    };
+    let reference = match qualified {
+        Some((schema, name)) => format!("{}.{}", dialect.quote_ident(schema), dialect.quote_ident(name)),
+        None => dialect.quote_ident(table),
+    };
+    Ok((dialect, reference))
+}
+
+// 读取整表的语句
+pub fn table_query(config: &DbConfig, db: &str, table: &str) -> Result<String, String> {
+    Ok(format!("SELECT * FROM {}", table_ref(config, db, table)?.1))
+}
+
+// 开启事务的语句
+pub fn begin(backend: Backend) -> &'static str {
+    match backend {
+        Backend::MySql => "START TRANSACTION",
+        Backend::Postgres | Backend::Sqlite => "BEGIN",
+    }
+}
+
+// 按连接 id 打开驱动；MySQL 系复用连接池，PostgreSQL 在首次使用时建立连接
+pub fn open(state: &AppState, id: &str, db: Option<&str>) -> Result<Box<dyn DbDriver>, String> {
+    open_with(state, id, db, false)
+}
+
+// 执行 DDL/DML 时使用：SQLite 文件以可写方式打开，其余驱动与 open 相同
+pub fn open_writable(state: &AppState, id: &str, db: Option<&str>) -> Result<Box<dyn DbDriver>, String> {
+    open_with(state, id, db, true)
+}
+
+fn open_with(state: &AppState, id: &str, db: Option<&str>, writable: bool) -> Result<Box<dyn DbDriver>, String> {
+    let config = state.connections.get(id)?;
+    match backend_of(&config)? {
+        Backend::MySql => Ok(Box::new(MySqlDriver::pooled(state.pools.get(&state.connections, id, db)?, &config)?)),
+        Backend::Postgres => Ok(Box::new(PostgresDriver::new(config, db))),
+        Backend::Sqlite => Ok(Box::new(SqliteDriver::open(&config, writable)?)),
+    }
+}
+
+// 使用未保存的配置建立独立连接 (测试连接)
+pub fn connect(config: DbConfig) -> Result<Box<dyn DbDriver>, String> {
+    match backend_of(&config)? {
+        Backend::MySql => Ok(Box::new(MySqlDriver::direct(&config)?)),
+        Backend::Postgres => Ok(Box::new(PostgresDriver::new(config, None))),
+        Backend::Sqlite => Ok(Box::new(SqliteDriver::open(&config, false)?)),
+    }
+}
+
+// 驱动调用均为阻塞 IO (postgres 同步客户端不能在异步运行时中直接使用)，放到阻塞线程池执行
+pub async fn blocking<T, F>(f: F) -> Result<T, String>
+where
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f).await.map_err(|e| format!("Database task failed: {}", e))?
+}
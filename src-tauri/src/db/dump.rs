@@ -0,0 +1,953 @@
+// 逻辑备份与恢复 (MySQL/Doris)：生成 mysqldump 风格的 .sql 文件 (表结构、数据、视图、触发器、存储过程)
+// 并逐条回放；全部通过 MySQL 协议完成，不依赖 mysql/mysqldump 客户端程序
+
+use super::driver::{Backend, Canceller};
+use super::export::{Counting, Output};
+use super::model::QueryStatus;
+use super::mysql_driver::{is_binary, kill_handle};
+use super::registry::DbConfig;
+use crate::sql::{Dialect, StatementReader};
+use crate::AppState;
+use mysql::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+// 前端监听的事件名
+pub const DUMP_PROGRESS_EVENT: &str = "db-dump-progress";
+pub const RESTORE_PROGRESS_EVENT: &str = "db-restore-progress";
+// 单条 INSERT 的大小上限，低于 max_allowed_packet 的默认值
+const MAX_INSERT_BYTES: usize = 1024 * 1024;
+// 进度事件的最小间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+// 恢复时最多记录的失败语句数，避免结果过大
+const MAX_ERRORS: usize = 1000;
+// 失败语句回显的字符数
+const ECHO_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DumpOptions {
+    pub schema: bool,
+    pub data: bool,
+    pub views: bool,
+    pub triggers: bool,
+    // 存储过程与函数，仅在导出整个库时写出
+    pub routines: bool,
+    // CREATE 前写出 DROP ... IF EXISTS
+    pub drop_objects: bool,
+    // 保留 DEFINER 子句 (默认去掉，恢复到其他实例时不要求定义者账号存在)
+    pub keep_definer: bool,
+    // 在一致性快照中读取 (InnoDB，Doris 忽略)
+    pub single_transaction: bool,
+    // 每条 INSERT 的行数
+    pub batch_size: usize,
+    // 每次读取的行数 (按主键分段)
+    pub chunk_size: usize,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        DumpOptions {
+            schema: true,
+            data: true,
+            views: true,
+            triggers: true,
+            routines: true,
+            drop_objects: true,
+            keep_definer: false,
+            single_transaction: true,
+            batch_size: 500,
+            chunk_size: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpRequest {
+    // 连接 id
+    pub id: String,
+    pub db: String,
+    // 要导出的表与视图，省略时导出整个库
+    pub tables: Option<Vec<String>>,
+    pub output_path: String,
+    #[serde(default)]
+    pub options: DumpOptions,
+    // 同时作为查询 id，可用 db_cancel_query 取消
+    pub dump_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DumpStage {
+    Tables,
+    Routines,
+    Views,
+    Triggers,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpProgress {
+    pub dump_id: String,
+    pub stage: DumpStage,
+    // 正在导出的对象名
+    pub object: String,
+    // 当前阶段已完成 / 总对象数
+    pub done: usize,
+    pub total: usize,
+    pub rows: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpCounts {
+    pub tables: usize,
+    pub views: usize,
+    pub triggers: usize,
+    pub routines: usize,
+    pub rows: u64,
+    // 因权限不足等原因跳过的对象
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpSummary {
+    pub dump_id: String,
+    pub path: String,
+    #[serde(flatten)]
+    pub counts: DumpCounts,
+    pub bytes: u64,
+    pub elapsed_ms: u64,
+    // 未完成时已删除不完整的文件
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OnError {
+    // 遇到失败的语句即停止
+    #[default]
+    Stop,
+    // 记录错误后继续执行后续语句
+    Continue,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreRequest {
+    // 连接 id
+    pub id: String,
+    // 默认库，文件中可以有 USE 语句
+    pub db: Option<String>,
+    pub input_path: String,
+    #[serde(default)]
+    pub on_error: OnError,
+    // 同时作为查询 id，可用 db_cancel_query 取消
+    pub restore_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgress {
+    pub restore_id: String,
+    pub statements: usize,
+    pub failed: usize,
+    pub bytes_read: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreError {
+    // 语句序号 (从 1 开始) 与起始行号
+    pub index: usize,
+    pub line: usize,
+    // 语句开头部分
+    pub statement: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreSummary {
+    pub restore_id: String,
+    pub path: String,
+    // 已执行的语句数 (含失败的)
+    pub statements: usize,
+    pub failed: usize,
+    pub errors: Vec<RestoreError>,
+    pub bytes_read: u64,
+    pub elapsed_ms: u64,
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn io_error(e: std::io::Error) -> String {
+    format!("Failed to write dump file: {}", e)
+}
+
+fn mysql_error(context: &str, e: mysql::Error) -> String {
+    format!("{}: {}", context, e)
+}
+
+// 逻辑备份只支持 MySQL 协议的连接，返回方言
+fn dialect_of(config: &DbConfig) -> Result<Dialect, String> {
+    if super::driver::backend_of(config)? != Backend::MySql {
+        return Err(format!("Dump and restore are not supported for {} connections", config.db_type));
+    }
+    Ok(Dialect::parse(&config.db_type).filter(|d| *d == Dialect::Doris).unwrap_or(Dialect::MySql))
+}
+
+// 长时间任务使用独立连接，不占用连接池，会话变量也不会带回池中
+fn connect(config: &DbConfig, db: Option<&str>) -> Result<(mysql::Conn, Canceller), String> {
+    let opts = config.mysql_opts(db)?;
+    let conn = mysql::Conn::new(opts.clone())
+        .map_err(|e| format!("Failed to connect to {}@{}:{} - {}", config.user, config.host, config.port, e))?;
+    let cancel = kill_handle(&conn, config.mysql_opts(None)?);
+    Ok((conn, cancel))
+}
+
+// 文本协议下的值按列类型写成 SQL 字面量
+fn literal(col: &mysql::Column, value: &mysql::Value) -> String {
+    use mysql::consts::ColumnType::*;
+    let mysql::Value::Bytes(bytes) = value else {
+        return value.as_sql(false);
+    };
+    match col.column_type() {
+        MYSQL_TYPE_TINY | MYSQL_TYPE_SHORT | MYSQL_TYPE_INT24 | MYSQL_TYPE_LONG | MYSQL_TYPE_LONGLONG | MYSQL_TYPE_YEAR
+        | MYSQL_TYPE_FLOAT | MYSQL_TYPE_DOUBLE | MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => String::from_utf8_lossy(bytes).into_owned(),
+        // JSON 列的 charset 为 binary，但只接受文本
+        MYSQL_TYPE_JSON => Dialect::MySql.quote_str(&String::from_utf8_lossy(bytes)),
+        MYSQL_TYPE_BIT => hex(bytes),
+        _ if is_binary(col) => hex(bytes),
+        _ => Dialect::MySql.quote_str(&String::from_utf8_lossy(bytes)),
+    }
+}
+
+// 二进制值写成十六进制字面量，空串写成 ''
+fn hex(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        return "''".to_string();
+    }
+    let mut out = String::with_capacity(bytes.len() * 2 + 2);
+    out.push_str("0x");
+    for b in bytes {
+        let _ = write!(out, "{:02X}", b);
+    }
+    out
+}
+
+// 去掉 CREATE 语句中的 DEFINER=`user`@`host` 子句
+fn strip_definer(sql: &str) -> String {
+    let Some(start) = sql.find(" DEFINER=") else {
+        return sql.to_string();
+    };
+    let bytes = sql.as_bytes();
+    let mut i = start + " DEFINER=".len();
+    // user@host，两部分都可能带反引号
+    for part in 0..2 {
+        if bytes.get(i) == Some(&b'`') {
+            i += 1;
+            while i < bytes.len() {
+                if bytes[i] == b'`' {
+                    if bytes.get(i + 1) == Some(&b'`') {
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    break;
+                }
+                i += 1;
+            }
+        } else {
+            while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'@' {
+                i += 1;
+            }
+        }
+        if part == 0 && bytes.get(i) == Some(&b'@') {
+            i += 1;
+        }
+    }
+    format!("{}{}", &sql[..start], &sql[i..])
+}
+
+fn section(out: &mut Output, title: &str) -> Result<(), String> {
+    write!(out, "\n--\n-- {}\n--\n\n", title).map_err(io_error)
+}
+
+// 写出以 DELIMITER ;; 包裹的语句 (触发器、存储过程体内含分号)
+fn write_compound(out: &mut Output, drop: Option<String>, create: &str) -> Result<(), String> {
+    if let Some(drop) = drop {
+        writeln!(out, "{};", drop).map_err(io_error)?;
+    }
+    write!(out, "DELIMITER ;;\n{};;\nDELIMITER ;\n", create).map_err(io_error)
+}
+
+// 批量 INSERT 的缓冲，按行数与语句大小分批
+struct InsertBatch {
+    head: String,
+    values: String,
+    rows: usize,
+    batch_size: usize,
+}
+
+impl InsertBatch {
+    fn push(&mut self, out: &mut impl Write, row: &str) -> Result<(), String> {
+        if self.rows > 0 {
+            self.values.push_str(",\n");
+        }
+        self.values.push_str(row);
+        self.rows += 1;
+        if self.rows >= self.batch_size || self.values.len() >= MAX_INSERT_BYTES {
+            self.flush(out)?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, out: &mut impl Write) -> Result<(), String> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+        writeln!(out, "{}{};", self.head, self.values).map_err(io_error)?;
+        self.values.clear();
+        self.rows = 0;
+        Ok(())
+    }
+}
+
+// 按间隔上报进度
+struct Reporter {
+    emit: Box<dyn Fn(DumpProgress) + Send>,
+    progress: DumpProgress,
+    reported: Instant,
+}
+
+impl Reporter {
+    fn report(&mut self, rows: u64, bytes: u64, force: bool) {
+        if !force && self.reported.elapsed() < PROGRESS_INTERVAL {
+            return;
+        }
+        self.progress.rows = rows;
+        self.progress.bytes = bytes;
+        (self.emit)(self.progress.clone());
+        self.reported = Instant::now();
+    }
+}
+
+struct Dumper {
+    conn: mysql::Conn,
+    out: Output,
+    dialect: Dialect,
+    db: String,
+    options: DumpOptions,
+    cancel: Arc<Canceller>,
+    counts: DumpCounts,
+    reporter: Reporter,
+}
+
+impl Dumper {
+    fn check_cancelled(&self) -> Result<(), String> {
+        if self.cancel.is_cancelled() {
+            return Err("Dump cancelled".to_string());
+        }
+        Ok(())
+    }
+
+    fn start_object(&mut self, stage: DumpStage, object: &str, done: usize, total: usize) -> Result<(), String> {
+        self.check_cancelled()?;
+        let progress = &mut self.reporter.progress;
+        progress.stage = stage;
+        progress.object = object.to_string();
+        progress.done = done;
+        progress.total = total;
+        self.reporter.report(self.counts.rows, self.out.bytes, true);
+        Ok(())
+    }
+
+    fn quote(&self, name: &str) -> String {
+        self.dialect.quote_ident(name)
+    }
+
+    fn create_statement(&self, sql: &str) -> String {
+        if self.options.keep_definer {
+            sql.to_string()
+        } else {
+            strip_definer(sql)
+        }
+    }
+
+    // SHOW CREATE ... 结果中的指定列，无权限时为 NULL
+    fn show_create(&mut self, sql: &str, column: &str) -> Result<Option<String>, String> {
+        let row: Option<mysql::Row> = self.conn.query_first(sql).map_err(|e| mysql_error(sql, e))?;
+        Ok(row.and_then(|row| row.get::<Option<String>, _>(column)).flatten())
+    }
+
+    fn header(&mut self, config: &DbConfig) -> Result<(), String> {
+        let (major, minor, patch) = self.conn.server_version();
+        let mut header = format!(
+            "-- DevToolbox Pro SQL dump\n-- Host: {}:{}    Database: {}\n-- Server version: {}.{}.{}\n-- Generated at: {}\n\n",
+            config.host,
+            config.port,
+            self.db,
+            major,
+            minor,
+            patch,
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        );
+        header.push_str("SET NAMES utf8mb4;\n");
+        if self.dialect == Dialect::MySql {
+            // TIMESTAMP 以 UTC 写出；0 值不触发自增；导入期间不检查外键
+            header.push_str("SET @OLD_TIME_ZONE=@@TIME_ZONE, TIME_ZONE='+00:00';\n");
+            header.push_str("SET @OLD_SQL_MODE=@@SQL_MODE, SQL_MODE='NO_AUTO_VALUE_ON_ZERO';\n");
+            header.push_str("SET @OLD_FOREIGN_KEY_CHECKS=@@FOREIGN_KEY_CHECKS, FOREIGN_KEY_CHECKS=0;\n");
+        }
+        self.out.write_all(header.as_bytes()).map_err(io_error)
+    }
+
+    fn footer(&mut self) -> Result<(), String> {
+        let mut footer = String::from("\n");
+        if self.dialect == Dialect::MySql {
+            footer.push_str("SET FOREIGN_KEY_CHECKS=@OLD_FOREIGN_KEY_CHECKS;\n");
+            footer.push_str("SET SQL_MODE=@OLD_SQL_MODE;\n");
+            footer.push_str("SET TIME_ZONE=@OLD_TIME_ZONE;\n");
+        }
+        footer.push_str("\n-- Dump completed\n");
+        self.out.write_all(footer.as_bytes()).map_err(io_error)?;
+        self.out.flush().map_err(io_error)
+    }
+
+    // 库中的表与视图 (名称, 是否视图)，按名称排序
+    fn objects(&mut self, tables: Option<&[String]>) -> Result<Vec<(String, bool)>, String> {
+        // Doris 的结果有额外的列，按位置读取
+        let rows: Vec<mysql::Row> = self.conn.query("SHOW FULL TABLES").map_err(|e| mysql_error("Failed to list tables", e))?;
+        let mut objects: Vec<(String, bool)> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let name: String = row.get(0)?;
+                let kind: String = row.get(1)?;
+                Some((name, kind.eq_ignore_ascii_case("VIEW")))
+            })
+            .collect();
+        if let Some(tables) = tables {
+            if let Some(missing) = tables.iter().find(|t| !objects.iter().any(|(name, _)| name == *t)) {
+                return Err(format!("Table not found: {}", missing));
+            }
+            objects.retain(|(name, _)| tables.contains(name));
+        }
+        objects.sort();
+        Ok(objects)
+    }
+
+    fn table(&mut self, table: &str) -> Result<(), String> {
+        let quoted = self.quote(table);
+        if self.options.schema {
+            let create = self
+                .show_create(&format!("SHOW CREATE TABLE {}", quoted), "Create Table")?
+                .ok_or_else(|| format!("Failed to read definition of table {}", table))?;
+            section(&mut self.out, &format!("Table structure for {}", quoted))?;
+            if self.options.drop_objects {
+                writeln!(self.out, "DROP TABLE IF EXISTS {};", quoted).map_err(io_error)?;
+            }
+            writeln!(self.out, "{};", create).map_err(io_error)?;
+        }
+        if self.options.data {
+            self.table_data(table)?;
+        }
+        self.counts.tables += 1;
+        Ok(())
+    }
+
+    // 可插入的列 (排除生成列) 与主键列
+    fn table_columns(&mut self, table: &str) -> Result<(Vec<String>, Vec<String>), String> {
+        let columns: Vec<(String, Option<String>)> = self
+            .conn
+            .exec(
+                "SELECT COLUMN_NAME, EXTRA FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+                (&self.db, table),
+            )
+            .map_err(|e| mysql_error("Failed to fetch columns", e))?;
+        let columns = columns
+            .into_iter()
+            .filter(|(_, extra)| !extra.as_deref().unwrap_or_default().to_uppercase().contains("GENERATED"))
+            .map(|(name, _)| name)
+            .collect();
+        // Doris 没有主键索引，整表流式读取
+        let primary_key = if self.dialect == Dialect::MySql {
+            self.conn
+                .exec(
+                    "SELECT COLUMN_NAME FROM information_schema.STATISTICS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND INDEX_NAME = 'PRIMARY' ORDER BY SEQ_IN_INDEX",
+                    (&self.db, table),
+                )
+                .map_err(|e| mysql_error("Failed to fetch primary key", e))?
+        } else {
+            Vec::new()
+        };
+        Ok((columns, primary_key))
+    }
+
+    // 有主键时按主键分段读取 (WHERE pk > 上一段末行 ORDER BY pk LIMIT n)，避免长时间的大查询
+    fn table_data(&mut self, table: &str) -> Result<(), String> {
+        let (columns, primary_key) = self.table_columns(table)?;
+        if columns.is_empty() {
+            return Ok(());
+        }
+        let quoted = self.quote(table);
+        let column_list = columns.iter().map(|c| self.quote(c)).collect::<Vec<_>>().join(", ");
+        let key_positions: Option<Vec<usize>> = primary_key.iter().map(|k| columns.iter().position(|c| c == k)).collect();
+        let key_positions = key_positions.filter(|k| !k.is_empty());
+
+        section(&mut self.out, &format!("Data for {}", quoted))?;
+        let mut batch = InsertBatch {
+            head: format!("INSERT INTO {} ({}) VALUES\n", quoted, column_list),
+            values: String::new(),
+            rows: 0,
+            batch_size: self.options.batch_size.max(1),
+        };
+        let select = format!("SELECT {} FROM {}", column_list, quoted);
+        match key_positions {
+            Some(positions) => {
+                let key_list = positions.iter().map(|i| self.quote(&columns[*i])).collect::<Vec<_>>().join(", ");
+                let chunk_size = self.options.chunk_size.max(1);
+                let mut last_key: Option<String> = None;
+                loop {
+                    self.check_cancelled()?;
+                    let sql = match &last_key {
+                        Some(key) => format!("{} WHERE ({}) > ({}) ORDER BY {} LIMIT {}", select, key_list, key, key_list, chunk_size),
+                        None => format!("{} ORDER BY {} LIMIT {}", select, key_list, chunk_size),
+                    };
+                    let (rows, last) = self.read_rows(&sql, &mut batch, Some(&positions))?;
+                    if last.is_some() {
+                        last_key = last;
+                    }
+                    if rows < chunk_size {
+                        break;
+                    }
+                }
+            }
+            None => {
+                self.read_rows(&select, &mut batch, None)?;
+            }
+        }
+        batch.flush(&mut self.out)
+    }
+
+    // 流式读取查询结果写入 batch，返回行数与末行的主键字面量
+    fn read_rows(&mut self, sql: &str, batch: &mut InsertBatch, key_positions: Option<&[usize]>) -> Result<(usize, Option<String>), String> {
+        let Dumper { conn, out, counts, reporter, .. } = self;
+        let mut result = conn.query_iter(sql).map_err(|e| mysql_error("Failed to read table data", e))?;
+        let columns: Vec<mysql::Column> = result.columns().as_ref().to_vec();
+        let mut count = 0;
+        let mut last_key = None;
+        let mut values: Vec<String> = Vec::with_capacity(columns.len());
+        for row in result.by_ref() {
+            let row = row.map_err(|e| mysql_error("Failed to read table data", e))?;
+            values.clear();
+            values.extend(columns.iter().zip(row.unwrap_raw()).map(|(col, v)| match v {
+                Some(v) => literal(col, &v),
+                None => "NULL".to_string(),
+            }));
+            batch.push(out, &format!("({})", values.join(",")))?;
+            if let Some(positions) = key_positions {
+                last_key = Some(positions.iter().map(|i| values[*i].as_str()).collect::<Vec<_>>().join(", "));
+            }
+            count += 1;
+            counts.rows += 1;
+            reporter.report(counts.rows, out.bytes, false);
+        }
+        Ok((count, last_key))
+    }
+
+    // 视图按依赖顺序写出：定义中引用了其他未写出视图的延后
+    fn views(&mut self, views: Vec<String>) -> Result<(), String> {
+        let mut definitions = Vec::new();
+        let total = views.len();
+        for (i, view) in views.into_iter().enumerate() {
+            self.start_object(DumpStage::Views, &view, i, total)?;
+            match self.show_create(&format!("SHOW CREATE VIEW {}", self.quote(&view)), "Create View")? {
+                Some(create) => definitions.push((view, create)),
+                None => self.counts.skipped.push(format!("View {}: definition not readable", view)),
+            }
+        }
+        while !definitions.is_empty() {
+            let ready = definitions
+                .iter()
+                .position(|(name, create)| {
+                    !definitions.iter().any(|(other, _)| other != name && create.contains(&self.quote(other)))
+                })
+                // 循环引用时按原顺序写出
+                .unwrap_or(0);
+            let (view, create) = definitions.remove(ready);
+            let quoted = self.quote(&view);
+            section(&mut self.out, &format!("View structure for {}", quoted))?;
+            if self.options.drop_objects {
+                writeln!(self.out, "DROP VIEW IF EXISTS {};", quoted).map_err(io_error)?;
+            }
+            writeln!(self.out, "{};", self.create_statement(&create)).map_err(io_error)?;
+            self.counts.views += 1;
+        }
+        Ok(())
+    }
+
+    // 触发器在数据之后写出，导入数据时不会触发
+    fn triggers(&mut self, tables: &[String]) -> Result<(), String> {
+        let rows: Vec<mysql::Row> = self.conn.query("SHOW TRIGGERS").map_err(|e| mysql_error("Failed to list triggers", e))?;
+        let triggers: Vec<String> = rows
+            .into_iter()
+            .filter(|row| row.get::<String, _>("Table").is_some_and(|t| tables.contains(&t)))
+            .filter_map(|row| row.get::<String, _>("Trigger"))
+            .collect();
+        let total = triggers.len();
+        for (i, trigger) in triggers.into_iter().enumerate() {
+            self.start_object(DumpStage::Triggers, &trigger, i, total)?;
+            let quoted = self.quote(&trigger);
+            let Some(create) = self.show_create(&format!("SHOW CREATE TRIGGER {}", quoted), "SQL Original Statement")? else {
+                self.counts.skipped.push(format!("Trigger {}: definition not readable", trigger));
+                continue;
+            };
+            section(&mut self.out, &format!("Trigger {}", quoted))?;
+            let drop = self.options.drop_objects.then(|| format!("DROP TRIGGER IF EXISTS {}", quoted));
+            let create = self.create_statement(&create);
+            write_compound(&mut self.out, drop, &create)?;
+            self.counts.triggers += 1;
+        }
+        Ok(())
+    }
+
+    fn routines(&mut self) -> Result<(), String> {
+        let routines: Vec<(String, String)> = self
+            .conn
+            .exec(
+                "SELECT ROUTINE_NAME, ROUTINE_TYPE FROM information_schema.ROUTINES WHERE ROUTINE_SCHEMA = ? ORDER BY ROUTINE_TYPE, ROUTINE_NAME",
+                (&self.db,),
+            )
+            .map_err(|e| mysql_error("Failed to list routines", e))?;
+        let total = routines.len();
+        for (i, (name, kind)) in routines.into_iter().enumerate() {
+            self.start_object(DumpStage::Routines, &name, i, total)?;
+            let kind = kind.to_uppercase();
+            let quoted = self.quote(&name);
+            let column = if kind == "FUNCTION" { "Create Function" } else { "Create Procedure" };
+            // 没有权限时 SHOW CREATE 返回 NULL
+            let Some(create) = self.show_create(&format!("SHOW CREATE {} {}", kind, quoted), column)? else {
+                self.counts.skipped.push(format!("{} {}: definition not readable", kind.to_lowercase(), name));
+                continue;
+            };
+            section(&mut self.out, &format!("{} {}", kind.to_lowercase(), quoted))?;
+            let drop = self.options.drop_objects.then(|| format!("DROP {} IF EXISTS {}", kind, quoted));
+            let create = self.create_statement(&create);
+            write_compound(&mut self.out, drop, &create)?;
+            self.counts.routines += 1;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, config: &DbConfig, tables_filter: Option<&[String]>) -> Result<(), String> {
+        if self.dialect == Dialect::MySql {
+            // 与文件头一致，TIMESTAMP 按 UTC 读取
+            self.conn.query_drop("SET TIME_ZONE = '+00:00'").map_err(|e| mysql_error("Failed to prepare session", e))?;
+            if self.options.single_transaction {
+                self.conn
+                    .query_drop("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+                    .and_then(|_| self.conn.query_drop("START TRANSACTION WITH CONSISTENT SNAPSHOT"))
+                    .map_err(|e| mysql_error("Failed to start transaction", e))?;
+            }
+        }
+        self.header(config)?;
+
+        let objects = self.objects(tables_filter)?;
+        let (views, tables): (Vec<_>, Vec<_>) = objects.into_iter().partition(|(_, is_view)| *is_view);
+        let tables: Vec<String> = tables.into_iter().map(|(name, _)| name).collect();
+        let views: Vec<String> = views.into_iter().map(|(name, _)| name).collect();
+
+        if self.options.schema || self.options.data {
+            for (i, table) in tables.iter().enumerate() {
+                self.start_object(DumpStage::Tables, table, i, tables.len())?;
+                self.table(table)?;
+            }
+        }
+        // 存储过程、触发器是 MySQL 特有的对象；存储过程不属于某张表，只在导出整个库时写出
+        let whole_db = tables_filter.is_none();
+        if self.dialect == Dialect::MySql && self.options.schema && self.options.routines && whole_db {
+            self.routines()?;
+        }
+        if self.options.schema && self.options.views {
+            self.views(views)?;
+        }
+        if self.dialect == Dialect::MySql && self.options.schema && self.options.triggers {
+            self.triggers(&tables)?;
+        }
+        if self.options.single_transaction && self.dialect == Dialect::MySql {
+            let _ = self.conn.query_drop("COMMIT");
+        }
+        self.footer()?;
+        self.reporter.report(self.counts.rows, self.out.bytes, true);
+        Ok(())
+    }
+}
+
+// 导出整个库或指定的表到 .sql 文件，进度通过 db-dump-progress 事件上报
+// 取消 (db_cancel_query) 或出错时删除不完整的文件，并在 status/error 中说明
+#[tauri::command]
+pub async fn db_dump(app: AppHandle, state: State<'_, AppState>, request: DumpRequest) -> Result<DumpSummary, String> {
+    let config = state.connections.get(&request.id)?;
+    let dialect = dialect_of(&config)?;
+    if request.db.trim().is_empty() {
+        return Err("Database is required".to_string());
+    }
+    let dump_id = request.dump_id.clone().filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.cursors.next_id("dump"));
+    let out = super::export::create(&request.output_path)?;
+
+    let db = request.db.clone();
+    let connect_config = config.clone();
+    let connected = super::driver::blocking(move || connect(&connect_config, Some(&db))).await;
+    let (conn, cancel) = match connected {
+        Ok(connected) => connected,
+        Err(e) => {
+            drop(out);
+            let _ = std::fs::remove_file(&request.output_path);
+            return Err(e);
+        }
+    };
+    let cancel = Arc::new(cancel);
+    let label = format!("dump {}", request.db);
+    if let Err(e) = state.queries.register(&dump_id, &request.id, &label, cancel.clone()) {
+        drop(out);
+        let _ = std::fs::remove_file(&request.output_path);
+        return Err(e);
+    }
+
+    let started = Instant::now();
+    let progress = DumpProgress { dump_id: dump_id.clone(), stage: DumpStage::Tables, object: String::new(), done: 0, total: 0, rows: 0, bytes: 0 };
+    let mut dumper = Dumper {
+        conn,
+        out,
+        dialect,
+        db: request.db.clone(),
+        options: request.options.clone(),
+        cancel: cancel.clone(),
+        counts: DumpCounts::default(),
+        reporter: Reporter {
+            emit: Box::new(move |progress| {
+                let _ = app.emit(DUMP_PROGRESS_EVENT, progress);
+            }),
+            progress,
+            reported: Instant::now(),
+        },
+    };
+    let tables = request.tables.clone();
+    let result = super::driver::blocking(move || {
+        let result = dumper.run(&config, tables.as_deref());
+        let bytes = dumper.out.bytes;
+        Ok((result, dumper.counts, bytes))
+    })
+    .await;
+    state.queries.remove(&dump_id);
+
+    let (result, counts, bytes) = result?;
+    let mut summary = DumpSummary {
+        dump_id,
+        path: request.output_path.clone(),
+        counts,
+        bytes,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        status: QueryStatus::Completed,
+        error: None,
+    };
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&request.output_path);
+        summary.status = if cancel.is_cancelled() { QueryStatus::Cancelled } else { QueryStatus::Failed };
+        summary.error = Some(e);
+    }
+    Ok(summary)
+}
+
+// 语句开头部分，用于错误回显
+fn echo(statement: &str) -> String {
+    let mut chars = statement.chars();
+    let head: String = chars.by_ref().take(ECHO_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}...", head)
+    } else {
+        head
+    }
+}
+
+// 逐条回放 .sql 文件 (支持 DELIMITER)，进度通过 db-restore-progress 事件上报
+// onError 为 continue 时记录失败的语句并继续执行
+#[tauri::command]
+pub async fn db_restore(app: AppHandle, state: State<'_, AppState>, request: RestoreRequest) -> Result<RestoreSummary, String> {
+    let config = state.connections.get(&request.id)?;
+    dialect_of(&config)?;
+    let file = File::open(&request.input_path).map_err(|e| format!("Failed to open {}: {}", request.input_path, e))?;
+    let total_bytes = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let restore_id = request.restore_id.clone().filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.cursors.next_id("restore"));
+
+    let db = request.db.clone();
+    let (mut conn, cancel) = super::driver::blocking(move || connect(&config, db.as_deref())).await?;
+    let cancel = Arc::new(cancel);
+    let label = format!("restore {}", request.input_path);
+    state.queries.register(&restore_id, &request.id, &label, cancel.clone())?;
+
+    let started = Instant::now();
+    let mut summary = RestoreSummary {
+        restore_id: restore_id.clone(),
+        path: request.input_path.clone(),
+        statements: 0,
+        failed: 0,
+        errors: Vec::new(),
+        bytes_read: 0,
+        elapsed_ms: 0,
+        status: QueryStatus::Completed,
+        error: None,
+    };
+    let on_error = request.on_error;
+    let worker_cancel = cancel.clone();
+    let result = super::driver::blocking(move || {
+        let mut reader = StatementReader::new(BufReader::new(Counting::new(file)));
+        let mut reported = Instant::now();
+        let report = |summary: &RestoreSummary, bytes_read: u64| {
+            let progress = RestoreProgress {
+                restore_id: summary.restore_id.clone(),
+                statements: summary.statements,
+                failed: summary.failed,
+                bytes_read,
+                total_bytes,
+            };
+            let _ = app.emit(RESTORE_PROGRESS_EVENT, progress);
+        };
+        while let Some(statement) = reader.next() {
+            let statement = statement.map_err(|e| format!("Failed to read {}: {}", summary.path, e));
+            summary.bytes_read = reader.get_ref().get_ref().bytes;
+            let statement = match statement {
+                Ok(statement) => statement,
+                Err(e) => {
+                    summary.status = QueryStatus::Failed;
+                    summary.error = Some(e);
+                    break;
+                }
+            };
+            if worker_cancel.is_cancelled() {
+                summary.status = QueryStatus::Cancelled;
+                break;
+            }
+            summary.statements += 1;
+            if let Err(e) = conn.query_drop(&statement) {
+                if worker_cancel.is_cancelled() {
+                    summary.status = QueryStatus::Cancelled;
+                    break;
+                }
+                summary.failed += 1;
+                let error = RestoreError { index: summary.statements, line: reader.line(), statement: echo(&statement), error: e.to_string() };
+                if on_error == OnError::Stop {
+                    summary.status = QueryStatus::Failed;
+                    summary.error = Some(format!("Statement {} (line {}) failed: {}", error.index, error.line, error.error));
+                    summary.errors.push(error);
+                    break;
+                }
+                if summary.errors.len() < MAX_ERRORS {
+                    summary.errors.push(error);
+                }
+            }
+            if reported.elapsed() >= PROGRESS_INTERVAL {
+                report(&summary, summary.bytes_read);
+                reported = Instant::now();
+            }
+        }
+        report(&summary, summary.bytes_read);
+        Ok(summary)
+    })
+    .await;
+    state.queries.remove(&restore_id);
+
+    let mut summary = result?;
+    summary.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mysql::consts::ColumnType;
+
+    fn column(column_type: ColumnType, charset: u16) -> mysql::Column {
+        mysql::Column::new(column_type).with_character_set(charset)
+    }
+
+    fn unhex(literal: &str) -> Vec<u8> {
+        let digits = literal.strip_prefix("0x").unwrap();
+        (0..digits.len()).step_by(2).map(|i| u8::from_str_radix(&digits[i..i + 2], 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn strip_definer_removes_quoted_and_bare_users() {
+        assert_eq!(strip_definer("CREATE DEFINER=`root`@`%` TRIGGER tr BEFORE INSERT ON t"), "CREATE TRIGGER tr BEFORE INSERT ON t");
+        assert_eq!(
+            strip_definer("CREATE ALGORITHM=UNDEFINED DEFINER=`a``b`@`localhost` SQL SECURITY DEFINER VIEW v AS SELECT 1"),
+            "CREATE ALGORITHM=UNDEFINED SQL SECURITY DEFINER VIEW v AS SELECT 1"
+        );
+        assert_eq!(strip_definer("CREATE DEFINER=root@localhost PROCEDURE p() BEGIN END"), "CREATE PROCEDURE p() BEGIN END");
+        assert_eq!(strip_definer("CREATE TABLE t (id INT)"), "CREATE TABLE t (id INT)");
+    }
+
+    #[test]
+    fn hex_round_trips_bytes() {
+        let bytes = [0x00, 0xab, 0x10, 0xff, b'\'', b';'];
+        assert_eq!(hex(&bytes), "0x00AB10FF273B");
+        assert_eq!(unhex(&hex(&bytes)), bytes);
+        assert_eq!(hex(&[]), "''");
+    }
+
+    #[test]
+    fn literal_follows_column_type() {
+        let blob = column(ColumnType::MYSQL_TYPE_BLOB, 63);
+        let bytes = vec![0xff, 0x00, b'\\', b'\''];
+        assert_eq!(unhex(&literal(&blob, &mysql::Value::Bytes(bytes.clone()))), bytes);
+
+        let text = column(ColumnType::MYSQL_TYPE_VAR_STRING, 45);
+        assert_eq!(literal(&text, &mysql::Value::Bytes(b"it's\n".to_vec())), "'it''s\\n'");
+        let int = column(ColumnType::MYSQL_TYPE_LONG, 63);
+        assert_eq!(literal(&int, &mysql::Value::Bytes(b"-42".to_vec())), "-42");
+        let decimal = column(ColumnType::MYSQL_TYPE_NEWDECIMAL, 63);
+        assert_eq!(literal(&decimal, &mysql::Value::Bytes(b"12345678901234567890.123".to_vec())), "12345678901234567890.123");
+        let json = column(ColumnType::MYSQL_TYPE_JSON, 63);
+        assert_eq!(literal(&json, &mysql::Value::Bytes(br#"{"a":"b"}"#.to_vec())), r#"'{"a":"b"}'"#);
+        let bit = column(ColumnType::MYSQL_TYPE_BIT, 63);
+        assert_eq!(literal(&bit, &mysql::Value::Bytes(vec![1])), "0x01");
+        assert_eq!(literal(&text, &mysql::Value::NULL), "NULL");
+    }
+
+    #[test]
+    fn insert_batch_splits_by_rows_and_size() {
+        let mut batch = InsertBatch { head: "INSERT INTO `t` (`v`) VALUES\n".to_string(), values: String::new(), rows: 0, batch_size: 2 };
+        let mut out = Vec::new();
+        for row in ["('a;b')", "('c')", "('d')"] {
+            batch.push(&mut out, row).unwrap();
+        }
+        batch.flush(&mut out).unwrap();
+        // 空批次不输出语句
+        batch.flush(&mut out).unwrap();
+        let statements: Vec<String> = StatementReader::new(out.as_slice()).collect::<std::io::Result<_>>().unwrap();
+        assert_eq!(statements, vec!["INSERT INTO `t` (`v`) VALUES\n('a;b'),\n('c')", "INSERT INTO `t` (`v`) VALUES\n('d')"]);
+
+        let mut batch = InsertBatch { head: "INSERT INTO `t` (`v`) VALUES\n".to_string(), values: String::new(), rows: 0, batch_size: 1000 };
+        let mut out = Vec::new();
+        let large = format!("('{}')", "x".repeat(MAX_INSERT_BYTES));
+        batch.push(&mut out, &large).unwrap();
+        batch.push(&mut out, "('y')").unwrap();
+        batch.flush(&mut out).unwrap();
+        let statements: Vec<String> = StatementReader::new(out.as_slice()).collect::<std::io::Result<_>>().unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[1].ends_with("('y')"));
+    }
+}
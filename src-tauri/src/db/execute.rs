@@ -0,0 +1,181 @@
+// 执行 DDL/DML：逐条执行并返回影响行数、自增 id、警告与耗时
+
+use super::driver::{self, Backend, DbDriver};
+use crate::sql::{split_statements, Dialect};
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tauri::State;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExecuteOptions {
+    // 在同一事务中执行，任一语句失败则回滚；MySQL 中含 DDL 等隐式提交的语句时拒绝执行
+    pub transaction: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementResult {
+    // 1 起始
+    pub index: usize,
+    pub sql: String,
+    pub affected_rows: u64,
+    pub last_insert_id: Option<u64>,
+    pub warnings: u16,
+    pub warning_messages: Vec<String>,
+    pub info: String,
+    pub elapsed_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteResult {
+    pub statements: Vec<StatementResult>,
+    pub affected_rows: u64,
+    // 最后一条产生自增 id 的语句
+    pub last_insert_id: Option<u64>,
+    pub warnings: u32,
+    pub transaction: bool,
+    pub elapsed_ms: u128,
+}
+
+// 结果中回显的语句最多保留的字符数
+const SQL_ECHO_LIMIT: usize = 200;
+
+// MySQL 中会隐式提交当前事务的语句 (DDL、账号管理与锁表)
+const IMPLICIT_COMMIT: [&str; 9] = ["ALTER", "CREATE", "DROP", "RENAME", "TRUNCATE", "GRANT", "REVOKE", "LOCK", "UNLOCK"];
+
+// 语句的首个关键字 (跳过开头的注释)，大写
+fn leading_keyword(sql: &str) -> String {
+    let mut rest = sql.trim_start();
+    loop {
+        if rest.starts_with("--") || rest.starts_with('#') {
+            rest = rest.find('\n').map_or("", |i| &rest[i + 1..]).trim_start();
+        } else if rest.starts_with("/*") && !rest.starts_with("/*!") {
+            rest = rest.find("*/").map_or("", |i| &rest[i + 2..]).trim_start();
+        } else {
+            break;
+        }
+    }
+    rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect::<String>().to_uppercase()
+}
+
+// 第一条会隐式提交的语句 (1 起始序号, 关键字)
+fn implicit_commit(statements: &[String]) -> Option<(usize, String)> {
+    statements
+        .iter()
+        .map(|sql| leading_keyword(sql))
+        .enumerate()
+        .find(|(_, keyword)| IMPLICIT_COMMIT.contains(&keyword.as_str()))
+        .map(|(i, keyword)| (i + 1, keyword))
+}
+
+fn run_statement(conn: &mut dyn DbDriver, index: usize, sql: &str) -> Result<StatementResult, String> {
+    let started = Instant::now();
+    let outcome = conn.execute_detailed(sql)?;
+    Ok(StatementResult {
+        index,
+        sql: sql.chars().take(SQL_ECHO_LIMIT).collect(),
+        affected_rows: outcome.affected_rows,
+        last_insert_id: outcome.last_insert_id,
+        warnings: outcome.warnings,
+        warning_messages: outcome.warning_messages,
+        info: outcome.info,
+        elapsed_ms: started.elapsed().as_millis(),
+    })
+}
+
+fn run_all(conn: &mut dyn DbDriver, statements: &[String]) -> Result<Vec<StatementResult>, (usize, String)> {
+    statements
+        .iter()
+        .enumerate()
+        .map(|(i, sql)| run_statement(conn, i + 1, sql).map_err(|e| (i + 1, e)))
+        .collect()
+}
+
+pub fn execute(conn: &mut dyn DbDriver, backend: Backend, sql: &str, opts: &ExecuteOptions) -> Result<ExecuteResult, String> {
+    let started = Instant::now();
+    let dialect = match backend {
+        Backend::MySql => Dialect::MySql,
+        Backend::Postgres => Dialect::Postgres,
+        Backend::Sqlite => Dialect::Sqlite,
+    };
+    let statements = split_statements(sql, dialect);
+    if statements.is_empty() {
+        return Err("No SQL statement to execute".to_string());
+    }
+    if opts.transaction && backend == Backend::MySql {
+        if let Some((index, keyword)) = implicit_commit(&statements) {
+            return Err(format!(
+                "Statement #{} ({}) commits implicitly on MySQL and cannot be rolled back; run it without a transaction",
+                index, keyword
+            ));
+        }
+    }
+
+    let results = if opts.transaction {
+        conn.execute(driver::begin(backend)).map_err(|e| format!("Failed to start transaction: {}", e))?;
+        match run_all(conn, &statements) {
+            Ok(results) => {
+                conn.execute("COMMIT").map_err(|e| format!("Commit failed: {}", e))?;
+                results
+            }
+            Err((index, e)) => {
+                let rollback = match conn.execute("ROLLBACK") {
+                    Ok(_) => "transaction rolled back".to_string(),
+                    Err(re) => format!("rollback failed: {}", re),
+                };
+                return Err(format!("Statement #{} failed ({}): {}", index, rollback, e));
+            }
+        }
+    } else {
+        // 非事务模式下在第一条失败处停止，之前的语句已生效
+        run_all(conn, &statements).map_err(|(index, e)| {
+            format!("Statement #{} failed ({} earlier statement(s) already applied): {}", index, index - 1, e)
+        })?
+    };
+
+    Ok(ExecuteResult {
+        affected_rows: results.iter().map(|r| r.affected_rows).sum(),
+        last_insert_id: results.iter().rev().find_map(|r| r.last_insert_id),
+        warnings: results.iter().map(|r| r.warnings as u32).sum(),
+        transaction: opts.transaction,
+        elapsed_ms: started.elapsed().as_millis(),
+        statements: results,
+    })
+}
+
+// 执行 INSERT/UPDATE/DELETE/DDL，多条语句以分号分隔
+#[tauri::command]
+pub async fn db_execute_query(
+    state: State<'_, AppState>,
+    id: String,
+    query: String,
+    db: Option<String>,
+    options: Option<ExecuteOptions>,
+) -> Result<ExecuteResult, String> {
+    let backend = driver::backend_of(&state.connections.get(&id)?)?;
+    let mut conn = driver::open_writable(&state, &id, db.as_deref())?;
+    driver::blocking(move || execute(conn.as_mut(), backend, &query, &options.unwrap_or_default())).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_leading_keyword_after_comments() {
+        assert_eq!(leading_keyword("  -- note\n# more\n/* x */ create table t (a int)"), "CREATE");
+        assert_eq!(leading_keyword("/*!40101 SET NAMES utf8 */"), "");
+        assert_eq!(leading_keyword("update t set a = 1"), "UPDATE");
+    }
+
+    #[test]
+    fn detects_statements_that_commit_implicitly() {
+        let statements = ["INSERT INTO t VALUES (1)", "Alter TABLE t ADD b int", "DROP TABLE u"].map(String::from);
+        assert_eq!(implicit_commit(&statements), Some((2, "ALTER".to_string())));
+        let statements = ["INSERT INTO t VALUES (1)", "UPDATE t SET created = 1", "DELETE FROM t"].map(String::from);
+        assert_eq!(implicit_commit(&statements), None);
+    }
+}
@@ -0,0 +1,510 @@
+// 表/查询结果导出：流式写入 CSV、JSON Lines、xlsx 或 INSERT 语句文件，数据不经过前端
+
+use super::cursor::{self, CursorOptions, PageSink};
+use super::driver;
+use super::model::{QueryColumn, QueryStatus};
+use crate::excel::insert::{render_insert, InsertMode};
+use crate::excel::value::SqlValue;
+use crate::sql::Dialect;
+use crate::AppState;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use encoding_rs::Encoding;
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use tauri::{AppHandle, Emitter, State};
+
+// 前端监听的事件名
+pub const PROGRESS_EVENT: &str = "db-export-progress";
+// xlsx 单个工作表的行数上限 (含表头)
+const XLSX_MAX_ROWS: u32 = 1_048_576;
+// Excel 单元格文本长度上限
+const XLSX_MAX_TEXT: usize = 32_767;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Xlsx,
+    Sql,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuoteStyle {
+    // 含分隔符、引号或换行时加引号
+    #[default]
+    Necessary,
+    // NULL 以外的值都加引号
+    Always,
+    // 数字、布尔与 NULL 以外的值加引号
+    NonNumeric,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CsvOptions {
+    pub delimiter: String,
+    pub quote: String,
+    pub quote_style: QuoteStyle,
+    pub header: bool,
+    // utf-8、utf-8-bom (便于 Excel 打开) 或 gbk、gb18030、big5 等编码名
+    pub encoding: String,
+    pub crlf: bool,
+    // NULL 输出的文本
+    pub null_value: String,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ",".to_string(),
+            quote: "\"".to_string(),
+            quote_style: QuoteStyle::Necessary,
+            header: true,
+            encoding: "utf-8".to_string(),
+            crlf: false,
+            null_value: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SqlOptions {
+    // INSERT 的目标表名，默认为导出的表名
+    pub table_name: Option<String>,
+    // 目标库类型 (mysql、doris、postgres、sqlite)，决定引号、转义与二进制字面量；默认与源连接相同
+    pub db_type: Option<String>,
+    pub batch_size: usize,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        SqlOptions { table_name: None, db_type: None, batch_size: 500 }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportRequest {
+    // 连接 id
+    pub id: String,
+    pub db: Option<String>,
+    // 导出整表，或导出 sql 的结果 (二选一)
+    pub table: Option<String>,
+    pub sql: Option<String>,
+    pub params: Option<Value>,
+    pub format: ExportFormat,
+    pub output_path: String,
+    #[serde(default)]
+    pub csv: CsvOptions,
+    #[serde(default)]
+    pub insert: SqlOptions,
+    // 省略时不限制行数
+    pub max_rows: Option<u64>,
+    // 等待下一行的最长时间，省略或为 0 时不限
+    pub timeout_secs: Option<u64>,
+    // 同时作为查询 id，可用 db_cancel_query 取消
+    pub export_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProgress {
+    pub export_id: String,
+    pub rows: u64,
+    // xlsx 保存前无法得知文件大小，只上报行数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    pub export_id: String,
+    pub path: String,
+    pub format: ExportFormat,
+    pub rows: u64,
+    pub bytes: u64,
+    // 因 maxRows 未导出全部结果
+    pub truncated: bool,
+    // 未完成时已删除不完整的文件
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 记录读写的字节数
+pub struct Counting<T> {
+    inner: T,
+    pub bytes: u64,
+}
+
+impl<T> Counting<T> {
+    pub fn new(inner: T) -> Counting<T> {
+        Counting { inner, bytes: 0 }
+    }
+}
+
+impl<R: Read> Read for Counting<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes += n as u64;
+        Ok(n)
+    }
+}
+
+impl<W: Write> Write for Counting<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.bytes += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+pub type Output = Counting<BufWriter<File>>;
+
+pub fn create(path: &str) -> Result<Output, String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path, e))?;
+    Ok(Counting::new(BufWriter::new(file)))
+}
+
+fn io_error(e: std::io::Error) -> String {
+    format!("Failed to write export file: {}", e)
+}
+
+fn xlsx_error(e: XlsxError) -> String {
+    format!("Failed to write export file: {}", e)
+}
+
+// 行对象按列顺序取值
+fn cells<'a>(columns: &'a [QueryColumn], row: &'a Value) -> impl Iterator<Item = &'a Value> {
+    columns.iter().map(move |c| row.get(&c.name).unwrap_or(&Value::Null))
+}
+
+enum Writer {
+    Csv { out: Output, options: CsvOptions, encoding: &'static Encoding, header_done: bool },
+    Jsonl { out: Output },
+    Xlsx { workbook: Box<Workbook>, path: String, sheet: usize, row: u32, header: Format },
+    Sql { out: Output, dialect: Dialect, table: String, batch_size: usize, batch: Vec<Vec<SqlValue>>, columns: Vec<String> },
+}
+
+impl Writer {
+    // path 为导出过程中写入的临时文件
+    fn new(req: &ExportRequest, path: &str, source_type: &str, source: &str) -> Result<Writer, String> {
+        match req.format {
+            ExportFormat::Csv => {
+                let options = req.csv.clone();
+                if options.delimiter.is_empty() {
+                    return Err("CSV delimiter must not be empty".to_string());
+                }
+                let label = options.encoding.trim().to_lowercase();
+                let bom = label == "utf-8-bom" || label == "utf8-bom";
+                let encoding = if bom { Some(encoding_rs::UTF_8) } else { Encoding::for_label(label.as_bytes()) }
+                    .ok_or_else(|| format!("Unsupported encoding: {}", options.encoding))?;
+                // encoding_rs 不支持编码为 UTF-16
+                if encoding.output_encoding() != encoding {
+                    return Err(format!("Unsupported encoding: {}", options.encoding));
+                }
+                let mut out = create(path)?;
+                if bom {
+                    out.write_all(b"\xEF\xBB\xBF").map_err(io_error)?;
+                }
+                Ok(Writer::Csv { out, options, encoding, header_done: false })
+            }
+            ExportFormat::Jsonl => Ok(Writer::Jsonl { out: create(path)? }),
+            ExportFormat::Xlsx => {
+                let mut workbook = Box::new(Workbook::new());
+                workbook.add_worksheet_with_constant_memory();
+                Ok(Writer::Xlsx { workbook, path: path.to_string(), sheet: 0, row: 0, header: Format::new().set_bold() })
+            }
+            ExportFormat::Sql => {
+                let db_type = req.insert.db_type.as_deref().unwrap_or(source_type);
+                let dialect = driver::dialect_of(db_type).map_err(|_| format!("Unsupported target database type for SQL export: {}", db_type))?;
+                let table = req.insert.table_name.clone().or_else(|| req.table.clone()).unwrap_or_else(|| "export".to_string());
+                let mut out = create(path)?;
+                writeln!(
+                    out,
+                    "-- Export of {}\n-- Dialect: {}\n-- Generated at {}\n",
+                    source,
+                    dialect.name(),
+                    chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+                )
+                .map_err(io_error)?;
+                Ok(Writer::Sql { out, dialect, table, batch_size: req.insert.batch_size.max(1), batch: Vec::new(), columns: Vec::new() })
+            }
+        }
+    }
+
+    fn bytes(&self) -> Option<u64> {
+        match self {
+            Writer::Csv { out, .. } | Writer::Jsonl { out } | Writer::Sql { out, .. } => Some(out.bytes),
+            // 保存时才压缩写出
+            Writer::Xlsx { .. } => None,
+        }
+    }
+
+    fn write_page(&mut self, columns: &[QueryColumn], rows: Vec<Value>) -> Result<(), String> {
+        match self {
+            Writer::Csv { out, options, encoding, header_done } => {
+                let eol = if options.crlf { "\r\n" } else { "\n" };
+                if !*header_done && options.header {
+                    let names: Vec<Value> = columns.iter().map(|c| Value::String(c.name.clone())).collect();
+                    write_csv_record(out, options, encoding, names.iter(), eol)?;
+                }
+                *header_done = true;
+                for row in &rows {
+                    write_csv_record(out, options, encoding, cells(columns, row), eol)?;
+                }
+            }
+            Writer::Jsonl { out } => {
+                for row in &rows {
+                    serde_json::to_writer(&mut *out, row).map_err(|e| format!("Failed to write export file: {}", e))?;
+                    out.write_all(b"\n").map_err(io_error)?;
+                }
+            }
+            Writer::Xlsx { workbook, sheet, row, header, .. } => {
+                // 首页 (可能为空) 先写表头
+                if *row == 0 {
+                    write_xlsx_header(workbook, *sheet, columns, header)?;
+                    *row = 1;
+                }
+                for values in &rows {
+                    // 工作表写满后续写到新的工作表
+                    if *row >= XLSX_MAX_ROWS {
+                        workbook.add_worksheet_with_constant_memory();
+                        *sheet += 1;
+                        write_xlsx_header(workbook, *sheet, columns, header)?;
+                        *row = 1;
+                    }
+                    let ws = workbook.worksheet_from_index(*sheet).map_err(xlsx_error)?;
+                    for (col, value) in cells(columns, values).enumerate() {
+                        let col = col as u16;
+                        match value {
+                            Value::Null => {}
+                            Value::Bool(b) => {
+                                ws.write_boolean(*row, col, *b).map_err(xlsx_error)?;
+                            }
+                            // 超出双精度有效位的整数按文本写入，避免精度丢失
+                            Value::Number(n) if n.is_f64() || n.as_i64().is_some_and(|i| i.unsigned_abs() < (1 << 53)) => {
+                                ws.write_number(*row, col, n.as_f64().unwrap_or_default()).map_err(xlsx_error)?;
+                            }
+                            other => {
+                                let text = match other {
+                                    Value::String(s) => s.clone(),
+                                    _ => other.to_string(),
+                                };
+                                let text: String = if text.len() > XLSX_MAX_TEXT { text.chars().take(XLSX_MAX_TEXT).collect() } else { text };
+                                ws.write_string(*row, col, &text).map_err(xlsx_error)?;
+                            }
+                        }
+                    }
+                    *row += 1;
+                }
+            }
+            Writer::Sql { out, dialect, table, batch_size, batch, columns: names } => {
+                if names.is_empty() {
+                    *names = columns.iter().map(|c| c.name.clone()).collect();
+                }
+                for row in &rows {
+                    batch.push(columns.iter().zip(cells(columns, row)).map(|(c, v)| column_value(&c.name, c.is_binary(), v)).collect::<Result<_, _>>()?);
+                    if batch.len() >= *batch_size {
+                        let sql = render_insert(*dialect, table, names, &[], batch, InsertMode::Insert).map_err(|e| e.to_string())?;
+                        out.write_all(sql.as_bytes()).map_err(io_error)?;
+                        batch.clear();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 写出剩余内容，返回文件大小
+    fn finish(self) -> Result<u64, String> {
+        match self {
+            Writer::Csv { mut out, .. } | Writer::Jsonl { mut out } => {
+                out.flush().map_err(io_error)?;
+                Ok(out.bytes)
+            }
+            Writer::Sql { mut out, dialect, table, batch, columns, .. } => {
+                if !batch.is_empty() {
+                    let sql = render_insert(dialect, &table, &columns, &[], &batch, InsertMode::Insert).map_err(|e| e.to_string())?;
+                    out.write_all(sql.as_bytes()).map_err(io_error)?;
+                }
+                out.flush().map_err(io_error)?;
+                Ok(out.bytes)
+            }
+            Writer::Xlsx { mut workbook, path, .. } => {
+                workbook.save(&path).map_err(xlsx_error)?;
+                std::fs::metadata(&path).map(|m| m.len()).map_err(io_error)
+            }
+        }
+    }
+}
+
+fn write_xlsx_header(workbook: &mut Workbook, sheet: usize, columns: &[QueryColumn], format: &Format) -> Result<(), String> {
+    let ws = workbook.worksheet_from_index(sheet).map_err(xlsx_error)?;
+    for (col, c) in columns.iter().enumerate() {
+        ws.write_string_with_format(0, col as u16, &c.name, format).map_err(xlsx_error)?;
+    }
+    Ok(())
+}
+
+pub fn to_sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(b) => SqlValue::Bool(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => SqlValue::Int(i),
+            None => SqlValue::Number(n.to_string()),
+        },
+        Value::String(s) => SqlValue::Str(s.clone()),
+        other => SqlValue::Str(other.to_string()),
+    }
+}
+
+// 二进制列的 base64 值还原为字节，写成各方言的十六进制字面量
+pub fn column_value(column: &str, binary: bool, value: &Value) -> Result<SqlValue, String> {
+    match value {
+        Value::String(s) if binary => B64.decode(s).map(SqlValue::Bytes).map_err(|e| format!("Invalid binary value in column {}: {}", column, e)),
+        _ => Ok(to_sql_value(value)),
+    }
+}
+
+fn write_csv_record<'a>(
+    out: &mut Output,
+    options: &CsvOptions,
+    encoding: &'static Encoding,
+    values: impl Iterator<Item = &'a Value>,
+    eol: &str,
+) -> Result<(), String> {
+    let quote = options.quote.as_str();
+    let mut line = String::new();
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            line.push_str(&options.delimiter);
+        }
+        let (text, numeric) = match value {
+            Value::Null => {
+                line.push_str(&options.null_value);
+                continue;
+            }
+            Value::String(s) => (s.clone(), false),
+            Value::Number(_) | Value::Bool(_) => (value.to_string(), true),
+            other => (other.to_string(), false),
+        };
+        let needs_quote = match options.quote_style {
+            QuoteStyle::Always => true,
+            QuoteStyle::NonNumeric => !numeric,
+            QuoteStyle::Necessary => text.contains(options.delimiter.as_str()) || text.contains(['\r', '\n']) || (!quote.is_empty() && text.contains(quote)),
+        };
+        if needs_quote && !quote.is_empty() {
+            line.push_str(quote);
+            line.push_str(&text.replace(quote, &format!("{0}{0}", quote)));
+            line.push_str(quote);
+        } else {
+            line.push_str(&text);
+        }
+    }
+    line.push_str(eol);
+    // 无法编码的字符按 HTML 数字实体写出
+    let (bytes, _, _) = encoding.encode(&line);
+    out.write_all(&bytes).map_err(io_error)
+}
+
+struct ExportSink {
+    writer: Writer,
+    rows: u64,
+    reporter: Box<dyn Fn(u64, Option<u64>) + Send>,
+}
+
+impl PageSink for ExportSink {
+    fn page(&mut self, columns: &[QueryColumn], rows: Vec<Value>) -> Result<(), String> {
+        let count = rows.len() as u64;
+        self.writer.write_page(columns, rows)?;
+        self.rows += count;
+        (self.reporter)(self.rows, self.writer.bytes());
+        Ok(())
+    }
+}
+
+// 导出表或查询结果到文件，进度通过 db-export-progress 事件上报
+// 取消 (db_cancel_query)、超时或出错时删除不完整的文件，并在 status/error 中说明
+#[tauri::command]
+pub async fn db_export(app: AppHandle, state: State<'_, AppState>, request: ExportRequest) -> Result<ExportSummary, String> {
+    let config = state.connections.get(&request.id)?;
+    let db = request.db.clone().unwrap_or_default();
+    let (sql, source) = match (&request.table, &request.sql) {
+        (Some(table), None) => (driver::table_query(&config, &db, table)?, table.clone()),
+        (None, Some(sql)) => (sql.clone(), "query".to_string()),
+        _ => return Err("Specify either a table or a sql query to export".to_string()),
+    };
+    let export_id = request.export_id.clone().filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.cursors.next_id("export"));
+    // 先写入临时文件，完成后再替换 outputPath，失败时不影响已有的同名文件
+    let partial = format!("{}.{}.part", request.output_path, export_id);
+    let writer = Writer::new(&request, &partial, &config.db_type, &source)?;
+
+    let progress_id = export_id.clone();
+    let reporter = Box::new(move |rows: u64, bytes: Option<u64>| {
+        let _ = app.emit(PROGRESS_EVENT, ExportProgress { export_id: progress_id.clone(), rows, bytes });
+    });
+    let options = CursorOptions {
+        page_size: 1000,
+        max_rows: request.max_rows.unwrap_or(u64::MAX),
+        timeout_secs: request.timeout_secs.unwrap_or(0),
+        db: request.db.clone(),
+        query_id: Some(export_id.clone()),
+    };
+    let sink = ExportSink { writer, rows: 0, reporter };
+    let drained = cursor::drain(&state, &request.id, sql, request.params.clone(), options, sink).await;
+
+    let path = request.output_path.clone();
+    let remove_partial = || {
+        let _ = std::fs::remove_file(&partial);
+    };
+    let (drained, sink) = match drained {
+        Ok(result) => result,
+        Err(e) => {
+            remove_partial();
+            return Err(e);
+        }
+    };
+    let mut summary = ExportSummary {
+        export_id,
+        path: path.clone(),
+        format: request.format,
+        rows: sink.rows,
+        bytes: 0,
+        truncated: drained.truncated,
+        status: drained.status,
+        error: drained.error,
+    };
+    if summary.status != QueryStatus::Completed {
+        drop(sink);
+        remove_partial();
+        return Ok(summary);
+    }
+    // xlsx 在保存时才压缩写出
+    let finished = driver::blocking(move || sink.writer.finish()).await.and_then(|bytes| {
+        std::fs::rename(&partial, &path).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        Ok(bytes)
+    });
+    match finished {
+        Ok(bytes) => summary.bytes = bytes,
+        Err(e) => {
+            remove_partial();
+            summary.status = QueryStatus::Failed;
+            summary.error = Some(e);
+        }
+    }
+    Ok(summary)
+}
@@ -0,0 +1,18 @@
+// 数据库连接管理
+
+pub mod compare;
+pub mod cursor;
+pub mod doris;
+pub mod driver;
+pub mod dump;
+pub mod execute;
+pub mod export;
+pub mod model;
+pub mod mysql_driver;
+pub mod params;
+pub mod pool;
+pub mod postgres_driver;
+pub mod registry;
+pub mod repair;
+pub mod running;
+pub mod sqlite_driver;
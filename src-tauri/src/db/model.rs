@@ -0,0 +1,126 @@
+// 各数据库驱动共用的返回结构
+
+use super::doris::DorisInfo;
+use serde::Serialize;
+
+// 表信息结构
+#[derive(Debug, Serialize)]
+pub struct TableInfo {
+    pub name: String,
+    pub rows: i64,
+    pub size: String,
+    pub comment: Option<String>,
+    // TABLE / VIEW，驱动同时列出视图时返回
+    #[serde(rename = "tableType", skip_serializing_if = "Option::is_none")]
+    pub table_type: Option<String>,
+}
+
+// 表详情结构 (Schema)
+#[derive(Debug, Serialize)]
+pub struct ColumnInfo {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub col_type: String,
+    pub length: Option<u32>,
+    pub scale: Option<u32>,
+    pub nullable: bool,
+    #[serde(rename = "isPrimaryKey")]
+    pub is_primary_key: bool,
+    #[serde(rename = "defaultValue")]
+    pub default_value: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TableDetail {
+    pub name: String,
+    pub rows: i64,
+    pub size: String,
+    pub engine: Option<String>,
+    pub collation: Option<String>,
+    pub comment: Option<String>,
+    pub columns: Vec<ColumnInfo>,
+    pub ddl: String,
+    // 仅 Doris：键模型、分桶、分区、Rollup、物化视图与表属性
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doris: Option<DorisInfo>,
+}
+
+pub const BINARY_CHARSET: &str = "binary";
+
+// 查询结果列信息
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryColumn {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub sql_type: String,
+    // 如 NOT_NULL、PRIMARY_KEY、UNSIGNED、BINARY (仅 MySQL)
+    pub flags: Vec<String>,
+    // 二进制列 (BLOB/BINARY/bytea) 为 binary，其值在各驱动中统一以 base64 字符串返回
+    pub charset: Option<String>,
+    pub length: Option<u32>,
+    pub decimals: Option<u32>,
+}
+
+impl QueryColumn {
+    pub fn new(name: &str, sql_type: &str) -> QueryColumn {
+        QueryColumn { name: name.to_string(), sql_type: sql_type.to_string(), flags: Vec::new(), charset: None, length: None, decimals: None }
+    }
+
+    pub fn binary(name: &str, sql_type: &str) -> QueryColumn {
+        QueryColumn { charset: Some(BINARY_CHARSET.to_string()), ..QueryColumn::new(name, sql_type) }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.charset.as_deref() == Some(BINARY_CHARSET)
+    }
+}
+
+// 表结构中的二进制列类型 (BLOB/BINARY/bytea/空间类型)，查询结果中以 base64 返回
+pub fn is_binary_type(col_type: &str) -> bool {
+    const GEOMETRY: [&str; 8] = ["geometry", "point", "linestring", "polygon", "multipoint", "multilinestring", "multipolygon", "geometrycollection"];
+    let lower = col_type.trim().to_lowercase();
+    let base = lower.split(['(', ' ']).next().unwrap_or_default();
+    base.contains("blob") || base.contains("binary") || base == "bytea" || GEOMETRY.contains(&base)
+}
+
+// 查询的结束状态；running 表示游标还有未读取的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QueryStatus {
+    Running,
+    Completed,
+    Cancelled,
+    TimedOut,
+    Failed,
+}
+
+// db_query 的结果：列信息 + 按列名组成的行对象
+// 执行中被取消、超时或出错时 rows 为已读取的部分，error 为原因
+#[derive(Debug, Serialize)]
+pub struct QueryResult {
+    pub columns: Vec<QueryColumn>,
+    pub rows: Vec<serde_json::Value>,
+    // 行数超过 maxRows，未返回全部结果
+    pub truncated: bool,
+    pub status: QueryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 辅助函数：格式化字节大小
+pub fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+    
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.2} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.2} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
@@ -0,0 +1,398 @@
+// MySQL / Doris 驱动 (MySQL 协议)，Doris 表结构额外返回分区、分桶等元数据
+
+use super::doris;
+use super::driver::{Canceller, DbDriver, ExecOutcome, StreamEvent};
+use super::model::{format_size, ColumnInfo, QueryColumn, TableDetail, TableInfo};
+use super::pool::PoolConn;
+use super::registry::DbConfig;
+use crate::sql::Dialect;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use mysql::prelude::*;
+
+// information_schema.COLUMNS / TABLES 查询的行
+type ColumnRow = (String, String, Option<u64>, Option<u64>, Option<u32>, String, String, Option<String>, Option<String>);
+type TableRow = (Option<u64>, Option<u64>, Option<String>, Option<String>, Option<String>);
+
+enum Connection {
+    Pooled(PoolConn),
+    Direct(mysql::Conn),
+}
+
+pub struct MySqlDriver {
+    conn: Connection,
+    dialect: Dialect,
+    // 中止查询时建立旁路连接执行 KILL QUERY
+    opts: mysql::Opts,
+}
+
+// MySQL 协议连接区分 MySQL 与 Doris
+fn dialect_of(config: &DbConfig) -> Dialect {
+    Dialect::parse(&config.db_type).filter(|d| *d == Dialect::Doris).unwrap_or(Dialect::MySql)
+}
+
+impl MySqlDriver {
+    pub fn pooled(conn: PoolConn, config: &DbConfig) -> Result<MySqlDriver, String> {
+        Ok(MySqlDriver { conn: Connection::Pooled(conn), dialect: dialect_of(config), opts: config.mysql_opts(None)? })
+    }
+
+    pub fn direct(config: &DbConfig) -> Result<MySqlDriver, String> {
+        let opts = config.mysql_opts(None)?;
+        let conn = mysql::Conn::new(opts.clone())
+            .map_err(|e| format!("Failed to connect to {}@{}:{} - {}", config.user, config.host, config.port, e))?;
+        Ok(MySqlDriver { conn: Connection::Direct(conn), dialect: dialect_of(config), opts })
+    }
+
+    fn conn(&mut self) -> &mut mysql::Conn {
+        match &mut self.conn {
+            Connection::Pooled(conn) => conn.as_mut(),
+            Connection::Direct(conn) => conn,
+        }
+    }
+}
+
+impl DbDriver for MySqlDriver {
+    fn ping(&mut self) -> Result<(), String> {
+        self.conn().query_drop("SELECT 1").map_err(|e| format!("Connection established but query failed: {}", e))
+    }
+
+    fn databases(&mut self) -> Result<Vec<String>, String> {
+        self.conn().query_map("SHOW DATABASES", |db_name: String| db_name).map_err(|e| format!("Failed to fetch databases: {}", e))
+    }
+
+    fn tables(&mut self, _db: &str) -> Result<Vec<TableInfo>, String> {
+        // 连接已切换到目标库
+        let query = "SELECT TABLE_NAME, TABLE_ROWS, DATA_LENGTH, TABLE_COMMENT FROM information_schema.TABLES WHERE TABLE_SCHEMA = DATABASE() AND TABLE_TYPE = 'BASE TABLE'";
+
+        self.conn()
+            .query_map(query, |(name, rows, size_bytes, comment): (String, Option<u64>, Option<u64>, Option<String>)| TableInfo {
+                name,
+                rows: rows.unwrap_or(0) as i64,
+                size: format_size(size_bytes.unwrap_or(0)),
+                comment,
+                table_type: None,
+            })
+            .map_err(|e| format!("Failed to fetch tables: {}", e))
+    }
+
+    fn table_schema(&mut self, db: &str, table: &str) -> Result<TableDetail, String> {
+        let dialect = self.dialect;
+        let conn = self.conn();
+
+        // 获取列信息 - 两阶段查询确保类型准确
+        // 阶段1: 通过 DESC 获取准确的列类型（如 varchar(50)），避免 information_schema 的类型映射问题
+        let desc_query = format!("DESC {}", Dialect::MySql.quote_ident(table));
+        // DESC 失败时 type_map 为空，回退到 information_schema 的类型
+        let mut type_map: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+        if let Ok(desc_rows) = conn.query_map(&desc_query, |row: mysql::Row| {
+            let field: String = row.get(0).unwrap_or_default();
+            let col_type: String = row.get(1).unwrap_or_default();
+            (field, col_type)
+        }) {
+            for (f, t) in desc_rows {
+                if !f.is_empty() && !t.is_empty() {
+                    type_map.insert(f, t);
+                }
+            }
+        }
+
+        // 阶段2: 通过 information_schema 获取完整的列元数据（注释、默认值、主键等），库名/表名以参数绑定
+        let column_query = "SELECT COLUMN_NAME, DATA_TYPE, CHARACTER_MAXIMUM_LENGTH, NUMERIC_PRECISION, NUMERIC_SCALE, IS_NULLABLE, COLUMN_KEY, COLUMN_DEFAULT, COLUMN_COMMENT \
+            FROM information_schema.COLUMNS \
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? \
+            ORDER BY ORDINAL_POSITION";
+
+        let columns: Vec<ColumnInfo> = conn.exec_map(column_query, (db, table), |(name, data_type, char_max_len, num_precision, scale, nullable, key, default_val, comment): ColumnRow| {
+            // 优先使用 DESC 返回的类型（更准确，如 varchar(50)），回退到 DATA_TYPE
+            let actual_type = type_map.get(&name).cloned().unwrap_or(data_type);
+            ColumnInfo {
+                name,
+                col_type: actual_type,
+                length: char_max_len.or(num_precision).map(|l| l as u32),
+                scale,
+                nullable: nullable == "YES",
+                is_primary_key: key == "PRI",
+                default_value: default_val,
+                comment,
+            }
+        }).map_err(|e| format!("Failed to fetch columns: {}", e))?;
+
+        // 获取表信息
+        let table_query = "SELECT TABLE_ROWS, DATA_LENGTH, ENGINE, TABLE_COLLATION, TABLE_COMMENT \
+            FROM information_schema.TABLES \
+            WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?";
+
+        let table_info: Option<TableRow> =
+            conn.exec_first(table_query, (db, table)).map_err(|e| format!("Failed to fetch table info: {}", e))?;
+
+        let (rows, size_bytes, engine, collation, comment) = table_info.unwrap_or((None, None, None, None, None));
+
+        // 获取建表语句
+        let ddl_query = format!("SHOW CREATE TABLE {}.{}", Dialect::MySql.quote_ident(db), Dialect::MySql.quote_ident(table));
+        let ddl: Option<(String, String)> = conn.query_first(&ddl_query).map_err(|e| format!("Failed to fetch DDL (Query: {}): {}", ddl_query, e))?;
+        let ddl_statement = ddl.map(|(_, create_sql)| create_sql).unwrap_or_default();
+
+        // Doris 额外解析分区、分桶等信息
+        let doris = (dialect == Dialect::Doris).then(|| doris::inspect(conn, db, table, &ddl_statement));
+
+        Ok(TableDetail {
+            name: table.to_string(),
+            rows: rows.unwrap_or(0) as i64,
+            size: format_size(size_bytes.unwrap_or(0)),
+            engine,
+            collation,
+            comment,
+            columns,
+            ddl: ddl_statement,
+            doris,
+        })
+    }
+
+    fn stream(&mut self, sql: &str, params: Option<serde_json::Value>, sink: &mut dyn FnMut(StreamEvent) -> bool) -> Result<(), String> {
+        let conn = self.conn();
+        let result = match super::params::to_params(params)? {
+            Some(params) => conn.exec_iter(sql, params).and_then(|result| stream_result(result, sink)),
+            None => conn.query_iter(sql).and_then(|result| stream_result(result, sink)),
+        };
+        result.map_err(|e| format!("Query failed: {}", e))
+    }
+
+    fn execute(&mut self, sql: &str) -> Result<u64, String> {
+        let conn = self.conn();
+        conn.query_drop(sql).map_err(|e| format!("Query failed: {}", e))?;
+        Ok(conn.affected_rows())
+    }
+
+    fn execute_detailed(&mut self, sql: &str) -> Result<ExecOutcome, String> {
+        let conn = self.conn();
+        let mut outcome = ExecOutcome::default();
+        let mut result = conn.query_iter(sql).map_err(|e| e.to_string())?;
+        outcome.affected_rows = result.affected_rows();
+        outcome.last_insert_id = result.last_insert_id().filter(|id| *id > 0);
+        outcome.warnings = result.warnings();
+        outcome.info = result.info_str().to_string();
+        // 语句若返回结果集，需读完才能执行下一条
+        while let Some(set) = result.iter() {
+            for row in set {
+                row.map_err(|e| e.to_string())?;
+            }
+        }
+        drop(result);
+
+        if outcome.warnings > 0 {
+            outcome.warning_messages = conn
+                .query_map("SHOW WARNINGS", |(level, code, message): (String, u32, String)| format!("{} {}: {}", level, code, message))
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(outcome)
+    }
+
+    fn canceller(&mut self) -> Result<Canceller, String> {
+        let opts = self.opts.clone();
+        Ok(kill_handle(self.conn(), opts))
+    }
+}
+
+// 连接的中止句柄：在旁路连接上执行 KILL QUERY
+pub fn kill_handle(conn: &mysql::Conn, opts: mysql::Opts) -> Canceller {
+    let thread_id = conn.connection_id();
+    Canceller::new(Some(thread_id as u64), move || {
+        let killed = mysql::Conn::new(opts.clone()).and_then(|mut side| side.query_drop(format!("KILL QUERY {}", thread_id)));
+        if let Err(e) = killed {
+            eprintln!("Failed to kill query on connection {}: {}", thread_id, e);
+        }
+    })
+}
+
+// 只读取第一个结果集；通过结果集的列信息转换，结果为空时也能返回列
+// 提前停止时 QueryResult 析构会读完剩余行，调用方需先中止查询
+fn stream_result<P: mysql::prelude::Protocol>(mut result: mysql::QueryResult<'_, '_, '_, P>, sink: &mut dyn FnMut(StreamEvent) -> bool) -> mysql::Result<()> {
+    let columns: Vec<mysql::Column> = result.columns().as_ref().to_vec();
+    if !sink(StreamEvent::Columns(columns.iter().map(column_meta).collect())) {
+        return Ok(());
+    }
+    for row in result.by_ref() {
+        if !sink(StreamEvent::Row(row_to_json(&columns, row?))) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+const BINARY_CHARSET: u16 = 63;
+
+fn charset_name(id: u16) -> String {
+    match id {
+        BINARY_CHARSET => "binary",
+        8 | 31 | 47 | 48 | 49 | 94 => "latin1",
+        11 | 65 => "ascii",
+        24 | 86 => "gb2312",
+        28 | 87 => "gbk",
+        248..=250 => "gb18030",
+        33 | 76 | 83 | 192..=215 => "utf8mb3",
+        45 | 46 | 224..=247 | 255..=323 => "utf8mb4",
+        _ => return format!("charset#{}", id),
+    }
+    .to_string()
+}
+
+// 二进制串/BLOB/GEOMETRY；数值、日期、JSON 列的 charset 同样为 binary，但值为文本
+pub fn is_binary(col: &mysql::Column) -> bool {
+    use mysql::consts::ColumnType::*;
+    col.character_set() == BINARY_CHARSET
+        && matches!(
+            col.column_type(),
+            MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB | MYSQL_TYPE_BLOB | MYSQL_TYPE_VARCHAR | MYSQL_TYPE_VAR_STRING | MYSQL_TYPE_STRING | MYSQL_TYPE_GEOMETRY
+        )
+}
+
+fn type_name(col: &mysql::Column) -> &'static str {
+    use mysql::consts::ColumnFlags;
+    use mysql::consts::ColumnType::*;
+    match col.column_type() {
+        MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => "DECIMAL",
+        MYSQL_TYPE_TINY => "TINYINT",
+        MYSQL_TYPE_SHORT => "SMALLINT",
+        MYSQL_TYPE_INT24 => "MEDIUMINT",
+        MYSQL_TYPE_LONG => "INT",
+        MYSQL_TYPE_LONGLONG => "BIGINT",
+        MYSQL_TYPE_FLOAT => "FLOAT",
+        MYSQL_TYPE_DOUBLE => "DOUBLE",
+        MYSQL_TYPE_NULL => "NULL",
+        MYSQL_TYPE_TIMESTAMP | MYSQL_TYPE_TIMESTAMP2 => "TIMESTAMP",
+        MYSQL_TYPE_DATE | MYSQL_TYPE_NEWDATE => "DATE",
+        MYSQL_TYPE_TIME | MYSQL_TYPE_TIME2 => "TIME",
+        MYSQL_TYPE_DATETIME | MYSQL_TYPE_DATETIME2 => "DATETIME",
+        MYSQL_TYPE_YEAR => "YEAR",
+        MYSQL_TYPE_BIT => "BIT",
+        MYSQL_TYPE_JSON => "JSON",
+        MYSQL_TYPE_ENUM => "ENUM",
+        MYSQL_TYPE_SET => "SET",
+        MYSQL_TYPE_GEOMETRY => "GEOMETRY",
+        MYSQL_TYPE_TINY_BLOB | MYSQL_TYPE_MEDIUM_BLOB | MYSQL_TYPE_LONG_BLOB | MYSQL_TYPE_BLOB => {
+            if is_binary(col) { "BLOB" } else { "TEXT" }
+        }
+        MYSQL_TYPE_VARCHAR | MYSQL_TYPE_VAR_STRING => {
+            if is_binary(col) { "VARBINARY" } else { "VARCHAR" }
+        }
+        // ENUM/SET 在结果集中以 STRING 类型加标志位出现
+        MYSQL_TYPE_STRING if col.flags().contains(ColumnFlags::ENUM_FLAG) => "ENUM",
+        MYSQL_TYPE_STRING if col.flags().contains(ColumnFlags::SET_FLAG) => "SET",
+        MYSQL_TYPE_STRING => {
+            if is_binary(col) { "BINARY" } else { "CHAR" }
+        }
+        _ => "UNKNOWN",
+    }
+}
+
+pub fn column_meta(col: &mysql::Column) -> QueryColumn {
+    use mysql::consts::ColumnFlags;
+    const FLAGS: [(ColumnFlags, &str); 10] = [
+        (ColumnFlags::NOT_NULL_FLAG, "NOT_NULL"),
+        (ColumnFlags::PRI_KEY_FLAG, "PRIMARY_KEY"),
+        (ColumnFlags::UNIQUE_KEY_FLAG, "UNIQUE_KEY"),
+        (ColumnFlags::MULTIPLE_KEY_FLAG, "MULTIPLE_KEY"),
+        (ColumnFlags::UNSIGNED_FLAG, "UNSIGNED"),
+        (ColumnFlags::ZEROFILL_FLAG, "ZEROFILL"),
+        (ColumnFlags::BINARY_FLAG, "BINARY"),
+        (ColumnFlags::AUTO_INCREMENT_FLAG, "AUTO_INCREMENT"),
+        (ColumnFlags::ENUM_FLAG, "ENUM"),
+        (ColumnFlags::SET_FLAG, "SET"),
+    ];
+    let flags = col.flags();
+    QueryColumn {
+        name: col.name_str().to_string(),
+        sql_type: type_name(col).to_string(),
+        flags: FLAGS.iter().filter(|(f, _)| flags.contains(*f)).map(|(_, n)| n.to_string()).collect(),
+        // 非字符串列没有字符集，binary 只标记以 base64 返回的列
+        charset: match col.character_set() {
+            BINARY_CHARSET if !is_binary(col) => None,
+            id => Some(charset_name(id)),
+        },
+        length: Some(col.column_length()),
+        decimals: Some(col.decimals() as u32),
+    }
+}
+
+// 秒的小数部分按列定义的精度 (DATETIME(6)/TIME(3) 等) 输出
+fn fraction(col: &mysql::Column, micros: u32) -> String {
+    match col.decimals() as usize {
+        fsp @ 1..=6 => format!(".{}", &format!("{:06}", micros)[..fsp]),
+        0 => String::new(),
+        // 表达式列的精度未知 (0x1f)，有小数时输出 6 位
+        _ if micros > 0 => format!(".{:06}", micros),
+        _ => String::new(),
+    }
+}
+
+fn number(f: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(f).map(serde_json::Value::Number).unwrap_or(serde_json::Value::Null)
+}
+
+// 文本协议下所有值均为字节串，按列类型还原
+fn bytes_to_json(col: &mysql::Column, bytes: Vec<u8>) -> serde_json::Value {
+    use mysql::consts::ColumnType::*;
+    let text = || String::from_utf8_lossy(&bytes).to_string();
+    match col.column_type() {
+        MYSQL_TYPE_TINY | MYSQL_TYPE_SHORT | MYSQL_TYPE_INT24 | MYSQL_TYPE_LONG | MYSQL_TYPE_LONGLONG | MYSQL_TYPE_YEAR => {
+            let s = text();
+            s.parse::<i64>()
+                .map(serde_json::Value::from)
+                .or_else(|_| s.parse::<u64>().map(serde_json::Value::from))
+                .unwrap_or(serde_json::Value::String(s))
+        }
+        MYSQL_TYPE_FLOAT | MYSQL_TYPE_DOUBLE => {
+            let s = text();
+            s.parse::<f64>().map(number).unwrap_or(serde_json::Value::String(s))
+        }
+        // DECIMAL 保持字符串，避免精度丢失
+        MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => serde_json::Value::String(text()),
+        // BIT(n) 为大端字节
+        MYSQL_TYPE_BIT => serde_json::Value::from(bytes.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64)),
+        MYSQL_TYPE_JSON => serde_json::from_slice(&bytes).unwrap_or_else(|_| serde_json::Value::String(text())),
+        // 二进制串/BLOB/GEOMETRY 以 base64 返回 (列信息中 charset 为 binary)
+        _ if is_binary(col) => serde_json::Value::String(B64.encode(&bytes)),
+        _ => serde_json::Value::String(text()),
+    }
+}
+
+// 将 MySQL 值按列类型转换为 JSON Value
+pub fn value_to_json(col: &mysql::Column, value: mysql::Value) -> serde_json::Value {
+    use mysql::consts::ColumnType::*;
+    match value {
+        mysql::Value::NULL => serde_json::Value::Null,
+        mysql::Value::Bytes(bytes) => bytes_to_json(col, bytes),
+        mysql::Value::Int(i) => serde_json::Value::from(i),
+        mysql::Value::UInt(u) => serde_json::Value::from(u),
+        // 经 f32 的十进制表示转换，避免 1.1 变成 1.100000023841858
+        mysql::Value::Float(f) => f.to_string().parse::<f64>().map(number).unwrap_or(serde_json::Value::Null),
+        mysql::Value::Double(d) => match col.column_type() {
+            MYSQL_TYPE_DECIMAL | MYSQL_TYPE_NEWDECIMAL => serde_json::Value::String(d.to_string()),
+            _ => number(d),
+        },
+        mysql::Value::Date(year, month, day, hour, min, sec, micros) => {
+            let date = format!("{:04}-{:02}-{:02}", year, month, day);
+            serde_json::Value::String(match col.column_type() {
+                MYSQL_TYPE_DATE | MYSQL_TYPE_NEWDATE => date,
+                _ => format!("{} {:02}:{:02}:{:02}{}", date, hour, min, sec, fraction(col, micros)),
+            })
+        }
+        // TIME 可为负数且超过 24 小时 (范围 -838:59:59 ~ 838:59:59)
+        mysql::Value::Time(negative, days, hours, min, sec, micros) => serde_json::Value::String(format!(
+            "{}{:02}:{:02}:{:02}{}",
+            if negative { "-" } else { "" },
+            days * 24 + hours as u32,
+            min,
+            sec,
+            fraction(col, micros)
+        )),
+    }
+}
+
+fn row_to_json(columns: &[mysql::Column], row: mysql::Row) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+    for (col, value) in columns.iter().zip(row.unwrap()) {
+        map.insert(col.name_str().to_string(), value_to_json(col, value));
+    }
+    serde_json::Value::Object(map)
+}
@@ -0,0 +1,35 @@
+// 前端传入的查询参数 (JSON) 转为 MySQL 绑定参数
+
+use mysql::{Params, Value};
+use std::collections::HashMap;
+
+pub fn json_to_value(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::NULL,
+        serde_json::Value::Bool(b) => Value::Int(*b as i64),
+        serde_json::Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => Value::Int(i),
+            (None, Some(u)) => Value::UInt(u),
+            _ => Value::Double(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Bytes(s.as_bytes().to_vec()),
+        // 数组/对象按 JSON 文本绑定 (对应 JSON 列)
+        other => Value::Bytes(other.to_string().into_bytes()),
+    }
+}
+
+// 数组 -> 位置参数 (?)，对象 -> 命名参数 (:name)；空值或空集合返回 None (不走预处理语句)
+pub fn to_params(params: Option<serde_json::Value>) -> Result<Option<Params>, String> {
+    match params {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(serde_json::Value::Array(items)) if items.is_empty() => Ok(None),
+        Some(serde_json::Value::Object(map)) if map.is_empty() => Ok(None),
+        Some(serde_json::Value::Array(items)) => Ok(Some(Params::Positional(items.iter().map(json_to_value).collect()))),
+        Some(serde_json::Value::Object(map)) => Ok(Some(Params::Named(
+            map.iter()
+                .map(|(k, v)| (k.trim_start_matches(':').as_bytes().to_vec(), json_to_value(v)))
+                .collect::<HashMap<_, _>>(),
+        ))),
+        Some(other) => Err(format!("Query params must be an array or object, got {}", other)),
+    }
+}
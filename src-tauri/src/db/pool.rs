@@ -0,0 +1,209 @@
+// MySQL 连接池缓存：按 (连接 id, 数据库) 复用 mysql::Pool，空闲超时后释放
+
+use super::driver::{backend_of, Backend};
+use super::registry::{ConnectionRegistry, DbConfig};
+use crate::AppState;
+use mysql::{Pool, PoolConstraints, PoolOpts, PooledConn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::State;
+
+// 连接配置中的连接池参数，省略的字段使用默认值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PoolSettings {
+    pub min_size: usize,
+    pub max_size: usize,
+    // 连接池整体 (所有连接均未借出) 空闲超过该时间后关闭；mysql 连接池不支持单个连接的空闲回收
+    #[serde(alias = "idleTimeoutSecs")]
+    pub pool_idle_timeout_secs: u64,
+    // 等待可用连接的超时
+    pub acquire_timeout_secs: u64,
+    // 复用连接前先 ping，失效则重建
+    pub check_health: bool,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        PoolSettings { min_size: 1, max_size: 10, pool_idle_timeout_secs: 300, acquire_timeout_secs: 10, check_health: true }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    in_use: AtomicUsize,
+    acquired: AtomicU64,
+    failures: AtomicU64,
+    wait_ms_total: AtomicU64,
+    wait_ms_max: AtomicU64,
+}
+
+struct CachedPool {
+    pool: Pool,
+    settings: PoolSettings,
+    created_at: chrono::DateTime<chrono::Local>,
+    last_used: Mutex<Instant>,
+    counters: Arc<Counters>,
+}
+
+impl CachedPool {
+    fn idle_for(&self) -> Duration {
+        self.last_used.lock().unwrap().elapsed()
+    }
+
+    fn expired(&self) -> bool {
+        self.counters.in_use.load(Ordering::Relaxed) == 0 && self.idle_for() > Duration::from_secs(self.settings.pool_idle_timeout_secs)
+    }
+}
+
+// 借出的连接，归还时更新统计
+pub struct PoolConn {
+    conn: PooledConn,
+    counters: Arc<Counters>,
+}
+
+impl Deref for PoolConn {
+    type Target = PooledConn;
+    fn deref(&self) -> &PooledConn {
+        &self.conn
+    }
+}
+
+impl DerefMut for PoolConn {
+    fn deref_mut(&mut self) -> &mut PooledConn {
+        &mut self.conn
+    }
+}
+
+impl Drop for PoolConn {
+    fn drop(&mut self) {
+        self.counters.in_use.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    pub id: String,
+    pub database: Option<String>,
+    pub min_size: usize,
+    pub max_size: usize,
+    pub in_use: usize,
+    pub acquired: u64,
+    pub failures: u64,
+    pub avg_wait_ms: f64,
+    pub max_wait_ms: u64,
+    pub idle_secs: u64,
+    pub pool_idle_timeout_secs: u64,
+    pub created_at: String,
+}
+
+type PoolKey = (String, Option<String>);
+
+#[derive(Default)]
+pub struct PoolManager {
+    pools: Mutex<HashMap<PoolKey, Arc<CachedPool>>>,
+}
+
+fn build_pool(config: &DbConfig, db: Option<&str>) -> Result<CachedPool, String> {
+    // 连接池仅用于 MySQL 协议的连接 (MySQL/Doris)
+    if backend_of(config)? != Backend::MySql {
+        return Err(format!("{} connections do not support this operation", config.db_type));
+    }
+    let settings = config.pool.clone().unwrap_or_default();
+    let constraints = PoolConstraints::new(settings.min_size, settings.max_size.max(1))
+        .ok_or_else(|| format!("Invalid pool size: min {} > max {}", settings.min_size, settings.max_size))?;
+    let opts = mysql::OptsBuilder::from_opts(config.mysql_opts(db)?)
+        .pool_opts(PoolOpts::default().with_constraints(constraints).with_check_health(settings.check_health));
+    let pool = Pool::new(opts).map_err(|e| format!("Failed to connect: {}", e))?;
+    Ok(CachedPool {
+        pool,
+        settings,
+        created_at: chrono::Local::now(),
+        last_used: Mutex::new(Instant::now()),
+        counters: Arc::default(),
+    })
+}
+
+impl PoolManager {
+    // 借出一个连接，连接池不存在时按注册表中的配置创建
+    pub fn get(&self, registry: &ConnectionRegistry, id: &str, db: Option<&str>) -> Result<PoolConn, String> {
+        self.sweep();
+        let key: PoolKey = (id.to_string(), db.filter(|d| !d.is_empty()).map(str::to_string));
+        let cached = self.pools.lock().unwrap().get(&key).cloned();
+        let cached = match cached {
+            Some(p) => p,
+            None => {
+                // 建池会建立 min_size 个连接，不在持锁期间进行
+                let created = Arc::new(build_pool(&registry.get(id)?, key.1.as_deref())?);
+                self.pools.lock().unwrap().entry(key).or_insert(created).clone()
+            }
+        };
+
+        *cached.last_used.lock().unwrap() = Instant::now();
+        let counters = &cached.counters;
+        let started = Instant::now();
+        let conn = cached
+            .pool
+            .try_get_conn(Duration::from_secs(cached.settings.acquire_timeout_secs.max(1)))
+            .map_err(|e| {
+                counters.failures.fetch_add(1, Ordering::Relaxed);
+                format!("Failed to get connection: {}", e)
+            })?;
+        let waited = started.elapsed().as_millis() as u64;
+        counters.acquired.fetch_add(1, Ordering::Relaxed);
+        counters.wait_ms_total.fetch_add(waited, Ordering::Relaxed);
+        counters.wait_ms_max.fetch_max(waited, Ordering::Relaxed);
+        counters.in_use.fetch_add(1, Ordering::Relaxed);
+        Ok(PoolConn { conn, counters: counters.clone() })
+    }
+
+    // 连接配置变更或删除后关闭其所有连接池
+    pub fn evict(&self, id: &str) {
+        self.pools.lock().unwrap().retain(|(pool_id, _), _| pool_id != id);
+    }
+
+    // 关闭空闲超时的连接池
+    pub fn sweep(&self) {
+        self.pools.lock().unwrap().retain(|_, p| !p.expired());
+    }
+
+    pub fn stats(&self) -> Vec<PoolStats> {
+        let mut stats: Vec<PoolStats> = self
+            .pools
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|((id, db), p)| {
+                let c = &p.counters;
+                let acquired = c.acquired.load(Ordering::Relaxed);
+                PoolStats {
+                    id: id.clone(),
+                    database: db.clone(),
+                    min_size: p.settings.min_size,
+                    max_size: p.settings.max_size,
+                    in_use: c.in_use.load(Ordering::Relaxed),
+                    acquired,
+                    failures: c.failures.load(Ordering::Relaxed),
+                    avg_wait_ms: if acquired == 0 { 0.0 } else { c.wait_ms_total.load(Ordering::Relaxed) as f64 / acquired as f64 },
+                    max_wait_ms: c.wait_ms_max.load(Ordering::Relaxed),
+                    idle_secs: p.idle_for().as_secs(),
+                    pool_idle_timeout_secs: p.settings.pool_idle_timeout_secs,
+                    created_at: p.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| (&a.id, &a.database).cmp(&(&b.id, &b.database)));
+        stats
+    }
+}
+
+#[tauri::command]
+pub fn db_pool_stats(state: State<'_, AppState>) -> Vec<PoolStats> {
+    state.pools.sweep();
+    state.pools.stats()
+}
@@ -0,0 +1,600 @@
+// PostgreSQL 驱动：元数据来自 pg_catalog，DDL 按目录信息重建，值转换为与 MySQL 相同的 JSON 结构
+
+use super::driver::{Canceller, DbDriver, StreamEvent};
+use super::model::{format_size, ColumnInfo, QueryColumn, TableDetail, TableInfo};
+use super::registry::DbConfig;
+use crate::sql::Dialect;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use bytes::BytesMut;
+use postgres::types::{to_sql_checked, FromSql, Format, IsNull, Kind, ToSql, Type};
+use postgres::config::SslMode;
+use postgres::{Client, SimpleQueryMessage};
+use postgres_native_tls::MakeTlsConnector;
+use std::error::Error;
+use std::time::Duration;
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+const CONNECT_TIMEOUT_SECS: u64 = 10;
+// 未指定数据库时连接的默认库
+const DEFAULT_DATABASE: &str = "postgres";
+// 服务端游标每次读取的行数
+const PORTAL_BATCH: i32 = 500;
+
+// 非 public schema 下的表以 schema.table 形式列出，查询表结构时按同样的形式解析
+const TABLE_FILTER: &str = "c.relkind IN ('r', 'p') \
+    AND n.nspname NOT IN ('pg_catalog', 'information_schema') \
+    AND n.nspname NOT LIKE 'pg\\_toast%' AND n.nspname NOT LIKE 'pg\\_temp\\_%'";
+
+pub struct PostgresDriver {
+    config: DbConfig,
+    db: Option<String>,
+    // 首次使用时连接，保证连接在阻塞线程中建立
+    client: Option<Client>,
+    // 取消查询时同样按连接的 TLS 设置发送
+    tls: Option<MakeTlsConnector>,
+}
+
+// 与 libpq 一致：prefer/require 只加密不校验证书，verify-full 校验证书链与主机名
+fn tls_settings(config: &DbConfig) -> Result<(SslMode, MakeTlsConnector), String> {
+    let mode = config.ssl_mode.as_deref().map(|m| m.trim().to_lowercase()).filter(|m| !m.is_empty());
+    let (ssl_mode, verify) = match mode.as_deref().unwrap_or("prefer") {
+        "disable" => (SslMode::Disable, false),
+        "prefer" => (SslMode::Prefer, false),
+        "require" => (SslMode::Require, false),
+        "verify-full" => (SslMode::Require, true),
+        other => return Err(format!("Unsupported sslMode: {} (expected disable, prefer, require or verify-full)", other)),
+    };
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(!verify)
+        .danger_accept_invalid_hostnames(!verify)
+        .build()
+        .map_err(|e| format!("Failed to initialize TLS: {}", e))?;
+    Ok((ssl_mode, MakeTlsConnector::new(connector)))
+}
+
+// postgres::Error 的 Display 不含服务端消息，这里展开
+fn describe(e: &postgres::Error) -> String {
+    if let Some(db) = e.as_db_error() {
+        return db.to_string();
+    }
+    match e.source() {
+        Some(source) => format!("{}: {}", e, source),
+        None => e.to_string(),
+    }
+}
+
+impl PostgresDriver {
+    pub fn new(config: DbConfig, db: Option<&str>) -> PostgresDriver {
+        PostgresDriver { config, db: db.filter(|d| !d.is_empty()).map(str::to_string), client: None, tls: None }
+    }
+
+    fn client(&mut self) -> Result<&mut Client, String> {
+        if self.client.is_none() {
+            let c = &self.config;
+            let port: u16 = c.port.trim().parse().map_err(|_| format!("Invalid port: {}", c.port))?;
+            let dbname = self
+                .db
+                .clone()
+                .or_else(|| c.database.clone().filter(|d| !d.is_empty()))
+                .or_else(|| c.default_database.clone().filter(|d| !d.is_empty()))
+                .unwrap_or_else(|| DEFAULT_DATABASE.to_string());
+            let mut pg = postgres::Config::new();
+            pg.host(c.host.trim())
+                .port(port)
+                .user(&c.user)
+                .dbname(&dbname)
+                .application_name("devtoolbox")
+                .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS));
+            if let Some(password) = &c.password {
+                pg.password(password);
+            }
+            let (ssl_mode, tls) = tls_settings(c)?;
+            pg.ssl_mode(ssl_mode);
+            let client = pg
+                .connect(tls.clone())
+                .map_err(|e| format!("Failed to connect to {}@{}:{} - {}", c.user, c.host, c.port, describe(&e)))?;
+            self.client = Some(client);
+            self.tls = Some(tls);
+        }
+        Ok(self.client.as_mut().unwrap())
+    }
+}
+
+// varchar/char 的长度与 numeric 的精度、小数位编码在 atttypmod 中
+fn type_length(typname: &str, typmod: i32) -> (Option<u32>, Option<u32>) {
+    if typmod < 4 {
+        return (None, None);
+    }
+    let m = (typmod - 4) as u32;
+    match typname {
+        "varchar" | "bpchar" => (Some(m), None),
+        "numeric" => (Some((m >> 16) & 0xffff), Some(m & 0xffff)),
+        _ => (None, None),
+    }
+}
+
+impl DbDriver for PostgresDriver {
+    fn ping(&mut self) -> Result<(), String> {
+        self.client()?
+            .simple_query("SELECT 1")
+            .map(|_| ())
+            .map_err(|e| format!("Connection established but query failed: {}", describe(&e)))
+    }
+
+    fn databases(&mut self) -> Result<Vec<String>, String> {
+        let rows = self
+            .client()?
+            .query("SELECT datname::text FROM pg_database WHERE NOT datistemplate AND datallowconn ORDER BY datname", &[])
+            .map_err(|e| format!("Failed to fetch databases: {}", describe(&e)))?;
+        Ok(rows.iter().map(|r| r.get(0)).collect())
+    }
+
+    fn tables(&mut self, _db: &str) -> Result<Vec<TableInfo>, String> {
+        // 连接已切换到目标库
+        let query = format!(
+            "SELECT n.nspname::text, c.relname::text, GREATEST(c.reltuples, 0)::bigint, pg_total_relation_size(c.oid), obj_description(c.oid, 'pg_class') \
+            FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace \
+            WHERE {} \
+            ORDER BY n.nspname <> 'public', n.nspname, c.relname",
+            TABLE_FILTER
+        );
+        let rows = self.client()?.query(&query, &[]).map_err(|e| format!("Failed to fetch tables: {}", describe(&e)))?;
+        Ok(rows
+            .iter()
+            .map(|r| {
+                let schema: String = r.get(0);
+                let name: String = r.get(1);
+                TableInfo {
+                    name: if schema == "public" { name } else { format!("{}.{}", schema, name) },
+                    rows: r.get(2),
+                    size: format_size(r.get::<_, i64>(3).max(0) as u64),
+                    comment: r.get(4),
+                    table_type: None,
+                }
+            })
+            .collect())
+    }
+
+    fn table_schema(&mut self, _db: &str, table: &str) -> Result<TableDetail, String> {
+        let client = self.client()?;
+
+        // 获取表信息 (public 下的同名表优先于 schema.table 形式的匹配)
+        let table_query = format!(
+            "SELECT c.oid, quote_ident(n.nspname) || '.' || quote_ident(c.relname), GREATEST(c.reltuples, 0)::bigint, \
+                pg_total_relation_size(c.oid), obj_description(c.oid, 'pg_class'), am.amname::text, pg_get_partkeydef(c.oid), \
+                (SELECT datcollate::text FROM pg_database WHERE datname = current_database()) \
+            FROM pg_class c JOIN pg_namespace n ON n.oid = c.relnamespace LEFT JOIN pg_am am ON am.oid = c.relam \
+            WHERE {} AND ((n.nspname = 'public' AND c.relname = $1) OR n.nspname || '.' || c.relname = $1) \
+            ORDER BY (n.nspname = 'public' AND c.relname = $1) DESC \
+            LIMIT 1",
+            TABLE_FILTER
+        );
+        let info = client
+            .query_opt(&table_query, &[&table])
+            .map_err(|e| format!("Failed to fetch table info: {}", describe(&e)))?
+            .ok_or_else(|| format!("Table not found: {}", table))?;
+        let oid: u32 = info.get(0);
+        let qualified: String = info.get(1);
+        let comment: Option<String> = info.get(4);
+        let partition_key: Option<String> = info.get(6);
+
+        // 获取列信息
+        let column_query = "SELECT a.attname::text, format_type(a.atttypid, a.atttypmod), t.typname::text, a.atttypmod, a.attnotnull, \
+                pg_get_expr(d.adbin, d.adrelid), col_description(a.attrelid, a.attnum), COALESCE(a.attnum = ANY(pk.conkey), false), \
+                a.attidentity::text, a.attgenerated::text, quote_ident(a.attname) \
+            FROM pg_attribute a \
+            JOIN pg_type t ON t.oid = a.atttypid \
+            LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum \
+            LEFT JOIN pg_constraint pk ON pk.conrelid = a.attrelid AND pk.contype = 'p' \
+            WHERE a.attrelid = $1 AND a.attnum > 0 AND NOT a.attisdropped \
+            ORDER BY a.attnum";
+        let rows = client.query(column_query, &[&oid]).map_err(|e| format!("Failed to fetch columns: {}", describe(&e)))?;
+
+        let pg = Dialect::Postgres;
+        let mut columns = Vec::with_capacity(rows.len());
+        let mut lines = Vec::new();
+        let mut column_comments = Vec::new();
+        for r in &rows {
+            let name: String = r.get(0);
+            let col_type: String = r.get(1);
+            let typname: String = r.get(2);
+            let not_null: bool = r.get(4);
+            let expr: Option<String> = r.get(5);
+            let comment: Option<String> = r.get(6);
+            let identity: String = r.get(8);
+            let generated: String = r.get(9);
+            let quoted: String = r.get(10);
+
+            let mut line = format!("    {} {}", quoted, col_type);
+            let default_value = match (identity.as_str(), generated.as_str()) {
+                ("a", _) => {
+                    line.push_str(" GENERATED ALWAYS AS IDENTITY");
+                    None
+                }
+                ("d", _) => {
+                    line.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+                    None
+                }
+                (_, "s") => {
+                    line.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expr.unwrap_or_default()));
+                    None
+                }
+                _ => {
+                    if let Some(expr) = &expr {
+                        line.push_str(&format!(" DEFAULT {}", expr));
+                    }
+                    expr
+                }
+            };
+            if not_null {
+                line.push_str(" NOT NULL");
+            }
+            lines.push(line);
+            if let Some(c) = &comment {
+                column_comments.push(format!("COMMENT ON COLUMN {}.{} IS {};", qualified, quoted, pg.quote_str(c)));
+            }
+
+            let (length, scale) = type_length(&typname, r.get(3));
+            columns.push(ColumnInfo {
+                name,
+                col_type,
+                length,
+                scale,
+                nullable: !not_null,
+                is_primary_key: r.get(7),
+                default_value,
+                comment,
+            });
+        }
+
+        // 约束 (主键、唯一、检查、外键、排他)
+        let constraint_query = "SELECT quote_ident(conname), pg_get_constraintdef(oid) \
+            FROM pg_constraint \
+            WHERE conrelid = $1 AND contype IN ('p', 'u', 'c', 'f', 'x') \
+            ORDER BY CASE contype WHEN 'p' THEN 0 WHEN 'u' THEN 1 WHEN 'c' THEN 2 ELSE 3 END, conname";
+        for r in client.query(constraint_query, &[&oid]).map_err(|e| format!("Failed to fetch constraints: {}", describe(&e)))? {
+            lines.push(format!("    CONSTRAINT {} {}", r.get::<_, String>(0), r.get::<_, String>(1)));
+        }
+
+        let mut ddl = format!("CREATE TABLE {} (\n{}\n)", qualified, lines.join(",\n"));
+        if let Some(key) = partition_key {
+            ddl.push_str(&format!(" PARTITION BY {}", key));
+        }
+        ddl.push(';');
+
+        // 约束之外单独创建的索引
+        let index_query = "SELECT pg_get_indexdef(i.indexrelid) \
+            FROM pg_index i \
+            WHERE i.indrelid = $1 AND NOT EXISTS (SELECT 1 FROM pg_constraint c WHERE c.conindid = i.indexrelid AND c.conrelid = i.indrelid) \
+            ORDER BY i.indexrelid";
+        for r in client.query(index_query, &[&oid]).map_err(|e| format!("Failed to fetch indexes: {}", describe(&e)))? {
+            ddl.push_str(&format!("\n{};", r.get::<_, String>(0)));
+        }
+        if let Some(c) = &comment {
+            ddl.push_str(&format!("\nCOMMENT ON TABLE {} IS {};", qualified, pg.quote_str(c)));
+        }
+        for c in column_comments {
+            ddl.push('\n');
+            ddl.push_str(&c);
+        }
+
+        Ok(TableDetail {
+            name: table.to_string(),
+            rows: info.get(2),
+            size: format_size(info.get::<_, i64>(3).max(0) as u64),
+            engine: info.get(5),
+            collation: info.get(7),
+            comment,
+            columns,
+            ddl,
+            doris: None,
+        })
+    }
+
+    fn stream(&mut self, sql: &str, params: Option<serde_json::Value>, sink: &mut dyn FnMut(StreamEvent) -> bool) -> Result<(), String> {
+        let params: Vec<TextParam> = match params {
+            None | Some(serde_json::Value::Null) => Vec::new(),
+            Some(serde_json::Value::Array(items)) => items.into_iter().map(TextParam).collect(),
+            Some(serde_json::Value::Object(map)) if map.is_empty() => Vec::new(),
+            Some(_) => return Err("PostgreSQL only supports positional parameters ($1, $2, ...)".to_string()),
+        };
+        let refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p as &(dyn ToSql + Sync)).collect();
+        let client = self.client()?;
+        // 先 prepare 以便结果为空时也能返回列信息
+        let stmt = client.prepare(sql).map_err(|e| format!("Query failed: {}", describe(&e)))?;
+        let columns: Vec<QueryColumn> = stmt.columns().iter().map(column_meta).collect();
+        if columns.is_empty() {
+            // 不返回行的语句直接执行 (部分 DDL 不能放在事务中)
+            client.execute(&stmt, &refs).map_err(|e| format!("Query failed: {}", describe(&e)))?;
+            sink(StreamEvent::Columns(columns));
+            return Ok(());
+        }
+        if !sink(StreamEvent::Columns(columns)) {
+            return Ok(());
+        }
+
+        // 服务端游标 (portal) 只能在事务中使用，每次取一批
+        let mut tx = client.transaction().map_err(|e| format!("Query failed: {}", describe(&e)))?;
+        let portal = tx.bind(&stmt, &refs).map_err(|e| format!("Query failed: {}", describe(&e)))?;
+        loop {
+            let rows = tx.query_portal(&portal, PORTAL_BATCH).map_err(|e| format!("Query failed: {}", describe(&e)))?;
+            for row in &rows {
+                if !sink(StreamEvent::Row(row_to_json(row)?)) {
+                    return Ok(());
+                }
+            }
+            if rows.len() < PORTAL_BATCH as usize {
+                break;
+            }
+        }
+        // 读完后提交，保证 DML ... RETURNING 生效；提前停止时事务回滚
+        tx.commit().map_err(|e| format!("Query failed: {}", describe(&e)))
+    }
+
+    fn execute(&mut self, sql: &str) -> Result<u64, String> {
+        // 简单查询协议，不经过 prepare
+        let client = self.client()?;
+        let messages = client.simple_query(sql).map_err(|e| format!("Query failed: {}", describe(&e)))?;
+        Ok(messages
+            .iter()
+            .filter_map(|m| match m {
+                SimpleQueryMessage::CommandComplete(n) => Some(*n),
+                _ => None,
+            })
+            .sum())
+    }
+
+    fn canceller(&mut self) -> Result<Canceller, String> {
+        let client = self.client()?;
+        let pid: Option<i32> = client.query_one("SELECT pg_backend_pid()", &[]).ok().map(|row| row.get(0));
+        let token = client.cancel_token();
+        let tls = self.tls.clone().ok_or("Not connected")?;
+        Ok(Canceller::new(pid.map(|p| p as u64), move || {
+            if let Err(e) = token.cancel_query(tls.clone()) {
+                eprintln!("Failed to cancel query: {}", describe(&e));
+            }
+        }))
+    }
+}
+
+impl Drop for PostgresDriver {
+    fn drop(&mut self) {
+        // 同步客户端关闭时会阻塞等待，连接在阻塞线程中释放
+        if let Some(client) = self.client.take() {
+            let _ = client.close();
+        }
+    }
+}
+
+fn column_meta(col: &postgres::Column) -> QueryColumn {
+    match *col.type_() {
+        Type::BYTEA => QueryColumn::binary(col.name(), col.type_().name()),
+        _ => QueryColumn::new(col.name(), col.type_().name()),
+    }
+}
+
+// 将 PostgreSQL Row 转换为 JSON Value
+fn row_to_json(row: &postgres::Row) -> Result<serde_json::Value, String> {
+    let mut map = serde_json::Map::new();
+    for (idx, col) in row.columns().iter().enumerate() {
+        let value: PgValue = row.try_get(idx).map_err(|e| format!("Failed to read column {}: {}", col.name(), describe(&e)))?;
+        map.insert(col.name().to_string(), value.0);
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+// 接受任意类型的列值
+struct PgValue(serde_json::Value);
+
+fn number(f: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(f).map(serde_json::Value::Number).unwrap_or(serde_json::Value::Null)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// numeric 二进制格式：位数、权重、符号、小数位数，之后为 10000 进制的各位
+fn numeric_to_string(raw: &[u8]) -> Result<String, BoxError> {
+    let read = |i: usize| -> Result<i16, BoxError> {
+        raw.get(i..i + 2).map(|b| i16::from_be_bytes([b[0], b[1]])).ok_or_else(|| "invalid numeric value".into())
+    };
+    let ndigits = read(0)?.max(0) as usize;
+    let weight = read(2)? as i32;
+    let sign = read(4)? as u16;
+    let dscale = read(6)?.max(0) as usize;
+    match sign {
+        0xC000 => return Ok("NaN".to_string()),
+        0xD000 => return Ok("Infinity".to_string()),
+        0xF000 => return Ok("-Infinity".to_string()),
+        _ => {}
+    }
+    let digits = (0..ndigits).map(|i| read(8 + i * 2)).collect::<Result<Vec<i16>, _>>()?;
+    let digit = |g: i32| if g < 0 { 0 } else { digits.get(g as usize).copied().unwrap_or(0) };
+
+    let mut out = String::new();
+    if sign == 0x4000 {
+        out.push('-');
+    }
+    if weight < 0 {
+        out.push('0');
+    } else {
+        for g in 0..=weight {
+            out.push_str(&if g == 0 { digit(g).to_string() } else { format!("{:04}", digit(g)) });
+        }
+    }
+    if dscale > 0 {
+        let mut frac = String::new();
+        let mut g = weight + 1;
+        while frac.len() < dscale {
+            frac.push_str(&format!("{:04}", digit(g)));
+            g += 1;
+        }
+        frac.truncate(dscale);
+        out.push('.');
+        out.push_str(&frac);
+    }
+    Ok(out)
+}
+
+// interval 二进制格式：微秒 (i64)、天 (i32)、月 (i32)
+fn interval_to_string(raw: &[u8]) -> Result<String, BoxError> {
+    if raw.len() != 16 {
+        return Err("invalid interval value".into());
+    }
+    let micros = i64::from_be_bytes(raw[0..8].try_into()?);
+    let days = i32::from_be_bytes(raw[8..12].try_into()?);
+    let months = i32::from_be_bytes(raw[12..16].try_into()?);
+    let unit = |n: i32, one: &str, many: &str| format!("{} {}", n, if n.abs() == 1 { one } else { many });
+    let mut parts = Vec::new();
+    if months / 12 != 0 {
+        parts.push(unit(months / 12, "year", "years"));
+    }
+    if months % 12 != 0 {
+        parts.push(unit(months % 12, "mon", "mons"));
+    }
+    if days != 0 {
+        parts.push(unit(days, "day", "days"));
+    }
+    if micros != 0 || parts.is_empty() {
+        let sign = if micros < 0 { "-" } else { "" };
+        let m = micros.unsigned_abs();
+        let mut time = format!("{}{:02}:{:02}:{:02}", sign, m / 3_600_000_000, m / 60_000_000 % 60, m / 1_000_000 % 60);
+        if m % 1_000_000 != 0 {
+            time.push_str(format!(".{:06}", m % 1_000_000).trim_end_matches('0'));
+        }
+        parts.push(time);
+    }
+    Ok(parts.join(" "))
+}
+
+fn uuid_to_string(raw: &[u8]) -> Result<String, BoxError> {
+    if raw.len() != 16 {
+        return Err("invalid uuid value".into());
+    }
+    let h = hex(raw);
+    Ok(format!("{}-{}-{}-{}-{}", &h[0..8], &h[8..12], &h[12..16], &h[16..20], &h[20..]))
+}
+
+// inet/cidr 二进制格式：地址族、前缀长度、是否 cidr、地址长度、地址
+fn inet_to_string(raw: &[u8]) -> Result<String, BoxError> {
+    if raw.len() < 4 || raw.len() != 4 + raw[3] as usize {
+        return Err("invalid inet value".into());
+    }
+    let (bits, is_cidr, addr) = (raw[1], raw[2] == 1, &raw[4..]);
+    let ip: std::net::IpAddr = match addr.len() {
+        4 => <[u8; 4]>::try_from(addr)?.into(),
+        16 => <[u8; 16]>::try_from(addr)?.into(),
+        _ => return Err("invalid inet value".into()),
+    };
+    let full = if ip.is_ipv4() { 32 } else { 128 };
+    Ok(if bits == full && !is_cidr { ip.to_string() } else { format!("{}/{}", ip, bits) })
+}
+
+fn decode(ty: &Type, raw: &[u8]) -> Result<serde_json::Value, BoxError> {
+    use serde_json::Value;
+    match ty.kind() {
+        Kind::Array(_) => {
+            // 多维数组不支持按 Vec 解码，回退为类型名
+            return Ok(match Vec::<PgValue>::from_sql(ty, raw) {
+                Ok(items) => Value::Array(items.into_iter().map(|v| v.0).collect()),
+                Err(_) => Value::String(format!("<{}>", ty.name())),
+            });
+        }
+        Kind::Domain(base) => return decode(base, raw),
+        Kind::Enum(_) => return Ok(Value::String(String::from_utf8_lossy(raw).to_string())),
+        _ => {}
+    }
+    Ok(match *ty {
+        Type::BOOL => Value::Bool(bool::from_sql(ty, raw)?),
+        Type::INT2 => i16::from_sql(ty, raw)?.into(),
+        Type::INT4 => i32::from_sql(ty, raw)?.into(),
+        Type::INT8 => i64::from_sql(ty, raw)?.into(),
+        Type::OID => u32::from_sql(ty, raw)?.into(),
+        Type::FLOAT4 => number(f32::from_sql(ty, raw)? as f64),
+        Type::FLOAT8 => number(f64::from_sql(ty, raw)?),
+        // 与 MySQL 的 DECIMAL 一致，按字符串返回以免丢失精度
+        Type::NUMERIC => Value::String(numeric_to_string(raw)?),
+        Type::MONEY => {
+            let cents = i64::from_sql(&Type::INT8, raw)?;
+            Value::String(format!("{}{}.{:02}", if cents < 0 { "-" } else { "" }, (cents / 100).unsigned_abs(), (cents % 100).unsigned_abs()))
+        }
+        Type::JSON | Type::JSONB => serde_json::Value::from_sql(ty, raw)?,
+        Type::DATE => Value::String(chrono::NaiveDate::from_sql(ty, raw)?.format("%Y-%m-%d").to_string()),
+        Type::TIME => Value::String(chrono::NaiveTime::from_sql(ty, raw)?.format("%H:%M:%S%.f").to_string()),
+        Type::TIMESTAMP => Value::String(chrono::NaiveDateTime::from_sql(ty, raw)?.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        Type::TIMESTAMPTZ => {
+            Value::String(chrono::DateTime::<chrono::Local>::from_sql(ty, raw)?.format("%Y-%m-%d %H:%M:%S%.f").to_string())
+        }
+        Type::INTERVAL => Value::String(interval_to_string(raw)?),
+        Type::UUID => Value::String(uuid_to_string(raw)?),
+        Type::INET | Type::CIDR => Value::String(inet_to_string(raw)?),
+        // 与 MySQL 的二进制列一致，以 base64 返回
+        Type::BYTEA => Value::String(B64.encode(raw)),
+        Type::CHAR => Value::String((raw.first().copied().unwrap_or(0) as char).to_string()),
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::XML | Type::UNKNOWN => {
+            Value::String(String::from_utf8_lossy(raw).to_string())
+        }
+        // citext 等扩展文本类型的二进制格式即文本
+        _ if ty.name().contains("text") => Value::String(String::from_utf8_lossy(raw).to_string()),
+        _ => Value::String(format!("<{}>", ty.name())),
+    })
+}
+
+impl<'a> FromSql<'a> for PgValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        decode(ty, raw).map(PgValue)
+    }
+
+    fn from_sql_null(_: &Type) -> Result<Self, BoxError> {
+        Ok(PgValue(serde_json::Value::Null))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+// 查询参数以文本格式发送，由服务端按推断出的参数类型解析
+#[derive(Debug)]
+struct TextParam(serde_json::Value);
+
+fn array_literal(items: &[serde_json::Value]) -> String {
+    let items: Vec<String> = items
+        .iter()
+        .map(|v| match v {
+            serde_json::Value::Null => "NULL".to_string(),
+            serde_json::Value::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+            other => other.to_string(),
+        })
+        .collect();
+    format!("{{{}}}", items.join(","))
+}
+
+impl ToSql for TextParam {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        let text = match &self.0 {
+            serde_json::Value::Null => return Ok(IsNull::Yes),
+            serde_json::Value::Bool(b) if *ty == Type::BOOL => b.to_string(),
+            serde_json::Value::Bool(b) => (*b as i32).to_string(),
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Array(items) if matches!(ty.kind(), Kind::Array(_)) => array_literal(items),
+            // 数组/对象按 JSON 文本绑定 (对应 json/jsonb 列)
+            other => other.to_string(),
+        };
+        out.extend_from_slice(text.as_bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    fn encode_format(&self, _ty: &Type) -> Format {
+        Format::Text
+    }
+
+    to_sql_checked!();
+}
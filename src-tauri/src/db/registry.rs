@@ -0,0 +1,246 @@
+// 连接注册表：按 DbConfig.id 保存连接配置，持久化到应用配置目录 (密码存于 SecretStore)
+
+use super::pool::PoolSettings;
+use crate::secrets::{SecretStore, DB_KIND};
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+const REGISTRY_FILE: &str = "connections.json";
+
+// 数据库连接配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbConfig {
+    pub id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(rename = "type")]
+    pub db_type: String, // 'type' 是 Rust 关键字，所以重命名
+    pub host: String,
+    pub port: String,
+    pub user: String,
+    // 仅在保存/测试时由前端传入，不会持久化或返回
+    pub password: Option<String>,
+    #[serde(rename = "passwordHandle", default)]
+    pub password_handle: Option<String>,
+    pub database: Option<String>,
+    #[serde(rename = "defaultDatabase")]
+    pub default_database: Option<String>,
+    #[serde(default)]
+    pub pool: Option<PoolSettings>,
+    // PostgreSQL 的 TLS 模式：disable、prefer (默认)、require、verify-full
+    #[serde(rename = "sslMode", default)]
+    pub ssl_mode: Option<String>,
+}
+
+impl DbConfig {
+    // 直接构造连接参数，避免用户名/密码中的特殊字符破坏 URL
+    pub fn mysql_opts(&self, db: Option<&str>) -> Result<mysql::Opts, String> {
+        let port: u16 = self.port.trim().parse().map_err(|_| format!("Invalid port: {}", self.port))?;
+        let builder = mysql::OptsBuilder::new()
+            .ip_or_hostname(Some(self.host.trim()))
+            .tcp_port(port)
+            .user(Some(&self.user))
+            .pass(self.password.as_deref())
+            .db_name(db.filter(|d| !d.is_empty()));
+        Ok(builder.into())
+    }
+
+    fn port_number(&self) -> Option<u16> {
+        self.port.trim().parse().ok()
+    }
+
+    // 密码绑定的连接地址 (主机:端口)
+    fn secret_hosts(&self) -> Vec<String> {
+        match self.port_number() {
+            Some(port) => vec![format!("{}:{}", self.host.trim(), port)],
+            None => vec![self.host.trim().to_string()],
+        }
+    }
+
+    // 类型、地址与用户均未改动时才能沿用已保存的密码
+    fn same_endpoint(&self, other: &DbConfig) -> bool {
+        self.db_type.eq_ignore_ascii_case(&other.db_type)
+            && self.host.trim() == other.host.trim()
+            && self.port.trim() == other.port.trim()
+            && self.user == other.user
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    path: Option<PathBuf>,
+    items: Vec<DbConfig>,
+}
+
+pub struct ConnectionRegistry {
+    inner: Mutex<Registry>,
+    secrets: Arc<SecretStore>,
+}
+
+impl ConnectionRegistry {
+    pub fn new(secrets: Arc<SecretStore>) -> ConnectionRegistry {
+        ConnectionRegistry { inner: Mutex::new(Registry::default()), secrets }
+    }
+
+    // 启动时从配置目录加载，文件不存在视为空列表；须在 SecretStore 加载之后调用
+    pub fn load(&self, dir: &Path) -> Result<(), String> {
+        let path = dir.join(REGISTRY_FILE);
+        let mut items: Vec<DbConfig> = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        // 旧版本明文保存的密码迁移到密钥存储；未绑定主机的旧密码绑定到所属连接的地址
+        let mut migrated = false;
+        for item in items.iter_mut() {
+            if let Some(password) = item.password.take().filter(|p| !p.is_empty()) {
+                item.password_handle = Some(self.secrets.set(DB_KIND, item.name.clone(), item.secret_hosts(), &password)?.handle);
+                migrated = true;
+            } else if let Some(handle) = &item.password_handle {
+                if self.secrets.list().iter().any(|s| s.handle == *handle && s.hosts.is_empty()) {
+                    self.secrets.set_hosts(handle, item.secret_hosts())?;
+                }
+            }
+        }
+
+        let mut inner = self.inner.lock().unwrap();
+        inner.path = Some(path);
+        inner.items = items;
+        if migrated {
+            persist(&inner)?;
+        }
+        Ok(())
+    }
+
+    // 返回带密码的完整配置，仅供后端建立连接使用
+    pub fn get(&self, id: &str) -> Result<DbConfig, String> {
+        let config = self
+            .inner
+            .lock()
+            .unwrap()
+            .items
+            .iter()
+            .find(|c| c.id.as_deref() == Some(id))
+            .cloned()
+            .ok_or_else(|| format!("Connection not found: {}", id))?;
+        self.resolve_password(config)
+    }
+
+    // 测试连接时未重新输入密码：id 对应的已保存连接类型、地址与用户均未改动时才返回已保存的密码
+    pub fn saved_password(&self, id: &str, config: &DbConfig) -> Result<Option<String>, String> {
+        let Ok(saved) = self.get(id) else { return Ok(None) };
+        Ok(if saved.same_endpoint(config) { saved.password } else { None })
+    }
+
+    // 按 password_handle 从密钥存储读取，密钥须绑定到该连接的地址
+    fn resolve_password(&self, mut config: DbConfig) -> Result<DbConfig, String> {
+        if let Some(handle) = &config.password_handle {
+            config.password = Some(self.secrets.get_for_host(handle, &config.host, config.port_number())?);
+        }
+        Ok(config)
+    }
+
+    fn list(&self) -> Vec<DbConfig> {
+        self.inner.lock().unwrap().items.clone()
+    }
+
+    // 新增或更新；password 为 None 时保留原密码，为空字符串时清除密码
+    // 前端传入的 passwordHandle 被忽略；修改类型、地址或用户后须重新输入密码
+    fn save(&self, mut config: DbConfig) -> Result<DbConfig, String> {
+        let mut inner = self.inner.lock().unwrap();
+        let id = match config.id.as_deref().map(str::trim).filter(|id| !id.is_empty()) {
+            Some(id) => id.to_string(),
+            None => {
+                let base = format!("conn-{}", chrono::Local::now().timestamp_millis());
+                (0..)
+                    .map(|n| if n == 0 { base.clone() } else { format!("{}-{}", base, n) })
+                    .find(|id| !inner.items.iter().any(|c| c.id.as_deref() == Some(id.as_str())))
+                    .unwrap_or(base)
+            }
+        };
+        config.id = Some(id.clone());
+
+        let existing = inner.items.iter().position(|c| c.id.as_deref() == Some(id.as_str()));
+        let handle = existing.and_then(|i| inner.items[i].password_handle.clone());
+        if let (Some(i), Some(_), None) = (existing, &handle, &config.password) {
+            if !inner.items[i].same_endpoint(&config) {
+                return Err("Re-enter the password after changing the type, host, port or user of a connection".to_string());
+            }
+        }
+        config.password_handle = match config.password.take() {
+            Some(password) if password.is_empty() => {
+                if let Some(handle) = &handle {
+                    self.secrets.delete(handle)?;
+                }
+                None
+            }
+            Some(password) => Some(match handle {
+                Some(handle) if self.secrets.rotate(&handle, &password).is_ok() => {
+                    self.secrets.set_hosts(&handle, config.secret_hosts())?;
+                    handle
+                }
+                _ => self.secrets.set(DB_KIND, config.name.clone(), config.secret_hosts(), &password)?.handle,
+            }),
+            None => handle,
+        };
+
+        match existing {
+            Some(i) => inner.items[i] = config.clone(),
+            None => inner.items.push(config.clone()),
+        }
+        persist(&inner)?;
+        Ok(config)
+    }
+
+    fn delete(&self, id: &str) -> Result<bool, String> {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(idx) = inner.items.iter().position(|c| c.id.as_deref() == Some(id)) else {
+            return Ok(false);
+        };
+        let removed = inner.items.remove(idx);
+        persist(&inner)?;
+        if let Some(handle) = removed.password_handle {
+            self.secrets.delete(&handle)?;
+        }
+        Ok(true)
+    }
+}
+
+// 先写临时文件再替换，避免写入中断损坏配置
+fn persist(registry: &Registry) -> Result<(), String> {
+    let Some(path) = &registry.path else {
+        return Err("Connection registry is not loaded".to_string());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let text = serde_json::to_string_pretty(&registry.items).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, text).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// 列出已保存的连接 (只含密码句柄)
+#[tauri::command]
+pub fn db_list_connections(state: State<'_, AppState>) -> Vec<DbConfig> {
+    state.connections.list()
+}
+
+// 保存后关闭该连接已缓存的连接池，下次使用时按新配置重建
+#[tauri::command]
+pub fn db_save_connection(state: State<'_, AppState>, payload: DbConfig) -> Result<DbConfig, String> {
+    let saved = state.connections.save(payload)?;
+    state.pools.evict(saved.id.as_deref().unwrap_or_default());
+    Ok(saved)
+}
+
+// 返回连接是否存在
+#[tauri::command]
+pub fn db_delete_connection(state: State<'_, AppState>, id: String) -> Result<bool, String> {
+    state.pools.evict(&id);
+    state.connections.delete(&id)
+}
@@ -0,0 +1,330 @@
+// 由数据对比结果生成修复 SQL：按对比键生成 DELETE / UPDATE / INSERT 使目标表与源表一致
+// 可返回预览、写入 .sql 文件，或在目标连接上以事务执行
+
+use super::compare::{ColumnPair, CompareRow, CompareRowStatus, CompareTarget};
+use super::driver::{self, DbDriver};
+use super::export::{self, column_value};
+use crate::sql::Dialect;
+use crate::AppState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Instant;
+use tauri::State;
+
+// 返回给前端预览的语句数上限
+const PREVIEW_LIMIT: usize = 200;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairRequest {
+    pub compare_id: String,
+    // 要修复的差异类型，默认 only_source、diff、only_target 全部
+    pub categories: Option<Vec<CompareRowStatus>>,
+    // 写入 .sql 文件
+    pub output_path: Option<String>,
+    // 在目标连接上以事务执行，任一语句失败则回滚
+    #[serde(default)]
+    pub execute: bool,
+    // 执行时的查询 id，可用 db_cancel_query 取消
+    pub repair_id: Option<String>,
+    // 对比未完成或样本被截断时仍然执行 (只修复已记录的差异)
+    #[serde(default)]
+    pub allow_partial: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairSummary {
+    pub compare_id: String,
+    pub deletes: usize,
+    pub updates: usize,
+    pub inserts: usize,
+    // 对比未完成或样本被截断，语句只覆盖已记录的差异
+    pub partial: bool,
+    // 前 200 条语句
+    pub preview: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+    pub bytes_written: u64,
+    pub executed: bool,
+    pub affected_rows: u64,
+    pub elapsed_ms: u64,
+}
+
+struct Generator<'a> {
+    compare: &'a CompareTarget,
+    dialect: Dialect,
+    reference: String,
+}
+
+impl Generator<'_> {
+    fn literal(&self, column: &str, binary: bool, value: Option<&Value>) -> Result<String, String> {
+        Ok(column_value(column, binary, value.unwrap_or(&Value::Null))?.to_sql(self.dialect))
+    }
+
+    // 按目标表中的键值定位行
+    fn condition(&self, target: &Value) -> Result<String, String> {
+        let mut conditions = Vec::with_capacity(self.compare.keys.len());
+        for k in &self.compare.keys {
+            let column = self.dialect.quote_ident(&k.target);
+            conditions.push(match target.get(&k.target) {
+                None | Some(Value::Null) => format!("{} IS NULL", column),
+                value => format!("{} = {}", column, self.literal(&k.target, k.target_binary, value)?),
+            });
+        }
+        Ok(conditions.join(" AND "))
+    }
+
+    // 未参与对比的列 (ignoreColumns 等) 不更新，插入时取目标表默认值
+    fn statement(&self, row: &CompareRow) -> Result<Option<String>, String> {
+        let q = |name: &str| self.dialect.quote_ident(name);
+        let source_value = |c: &ColumnPair, source: &Value| self.literal(&c.source, c.source_binary, source.get(&c.source));
+        Ok(match (row.status, &row.source_data, &row.target_data) {
+            (CompareRowStatus::OnlyTarget, _, Some(target)) => Some(format!("DELETE FROM {} WHERE {}", self.reference, self.condition(target)?)),
+            (CompareRowStatus::Diff, Some(source), Some(target)) => {
+                let set = self
+                    .compare
+                    .columns
+                    .iter()
+                    .filter(|c| row.diff_fields.contains(&c.source))
+                    .map(|c| Ok(format!("{} = {}", q(&c.target), source_value(c, source)?)))
+                    .collect::<Result<Vec<String>, String>>()?;
+                if set.is_empty() {
+                    None
+                } else {
+                    Some(format!("UPDATE {} SET {} WHERE {}", self.reference, set.join(", "), self.condition(target)?))
+                }
+            }
+            (CompareRowStatus::OnlySource, Some(source), _) => {
+                let pairs: Vec<_> = self.compare.keys.iter().chain(&self.compare.columns).collect();
+                let columns: Vec<String> = pairs.iter().map(|c| q(&c.target)).collect();
+                let values = pairs.iter().map(|&c| source_value(c, source)).collect::<Result<Vec<String>, String>>()?;
+                Some(format!("INSERT INTO {} ({}) VALUES ({})", self.reference, columns.join(", "), values.join(", ")))
+            }
+            _ => None,
+        })
+    }
+}
+
+// 先删除再更新、插入，避免唯一索引冲突
+fn rank(status: CompareRowStatus) -> u8 {
+    match status {
+        CompareRowStatus::OnlyTarget => 0,
+        CompareRowStatus::Diff => 1,
+        CompareRowStatus::OnlySource => 2,
+        CompareRowStatus::Match => 3,
+    }
+}
+
+fn begin(dialect: Dialect) -> &'static str {
+    match dialect {
+        Dialect::MySql | Dialect::Doris => "START TRANSACTION",
+        Dialect::Postgres | Dialect::Sqlite => "BEGIN",
+    }
+}
+
+// 在同一事务中逐条执行，失败或取消时回滚，返回影响行数
+fn run(target: &mut dyn DbDriver, dialect: Dialect, statements: &[String], cancelled: &dyn Fn() -> bool) -> Result<u64, String> {
+    target.execute(begin(dialect)).map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut affected = 0;
+    for (i, sql) in statements.iter().enumerate() {
+        let result = if cancelled() { Err("Query was cancelled".to_string()) } else { target.execute(sql) };
+        match result {
+            Ok(n) => affected += n,
+            Err(e) => {
+                let rollback = match target.execute("ROLLBACK") {
+                    Ok(_) => "transaction rolled back".to_string(),
+                    Err(re) => format!("rollback failed: {}", re),
+                };
+                return Err(format!("Statement #{} failed ({}): {}", i + 1, rollback, e));
+            }
+        }
+    }
+    target.execute("COMMIT").map_err(|e| format!("Commit failed: {}", e))?;
+    Ok(affected)
+}
+
+// 生成修复目标表的语句；只覆盖对比时保留的样本，样本截断时 partial 为 true
+#[tauri::command]
+pub async fn db_compare_repair(state: State<'_, AppState>, request: RepairRequest) -> Result<RepairSummary, String> {
+    let started = Instant::now();
+    let categories = request.categories.clone().unwrap_or_else(|| vec![CompareRowStatus::OnlySource, CompareRowStatus::Diff, CompareRowStatus::OnlyTarget]);
+    let (compare, mut rows) = state.compares.rows(&request.compare_id, &categories)?;
+    let config = state.connections.get(&compare.side.conn_id)?;
+    let (dialect, reference) = driver::table_ref(&config, &compare.side.db, &compare.side.table)?;
+    // Doris 的事务只支持 INSERT
+    let doris = Dialect::parse(&config.db_type) == Some(Dialect::Doris);
+    if request.execute && doris {
+        return Err("Doris does not support transactional UPDATE/DELETE; write the repair SQL to a file instead".to_string());
+    }
+    // 不完整的样本只能修复部分差异，执行后目标表仍与源表不一致
+    if request.execute && !compare.complete && !request.allow_partial {
+        return Err("The comparison was incomplete or its samples were truncated; re-run it or set allowPartial to execute a partial repair".to_string());
+    }
+
+    rows.sort_by_key(|row| rank(row.status));
+    let generator = Generator { compare: &compare, dialect, reference };
+    let mut summary = RepairSummary {
+        compare_id: request.compare_id.clone(),
+        deletes: 0,
+        updates: 0,
+        inserts: 0,
+        partial: !compare.complete,
+        preview: Vec::new(),
+        output_path: request.output_path.clone(),
+        bytes_written: 0,
+        executed: false,
+        affected_rows: 0,
+        elapsed_ms: 0,
+    };
+    let mut statements = Vec::new();
+    for row in &rows {
+        let Some(sql) = generator.statement(row)? else {
+            continue;
+        };
+        match row.status {
+            CompareRowStatus::OnlyTarget => summary.deletes += 1,
+            CompareRowStatus::Diff => summary.updates += 1,
+            _ => summary.inserts += 1,
+        }
+        statements.push(sql);
+    }
+    summary.preview = statements.iter().take(PREVIEW_LIMIT).map(|sql| format!("{};", sql)).collect();
+    let statements = Arc::new(statements);
+
+    if let Some(path) = request.output_path.clone() {
+        let header = format!(
+            "-- DevToolbox Pro repair SQL\n-- Target: {}    Compare: {}\n-- Generated at: {}\n{}\n",
+            generator.reference,
+            request.compare_id,
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+            if summary.partial { "-- Partial: the comparison was incomplete or its samples were truncated\n" } else { "" }
+        );
+        let statements = statements.clone();
+        summary.bytes_written = driver::blocking(move || {
+            let mut out = export::create(&path)?;
+            let io_error = |e: std::io::Error| format!("Failed to write {}: {}", path, e);
+            out.write_all(header.as_bytes()).map_err(io_error)?;
+            if !doris {
+                writeln!(out, "{};", begin(dialect)).map_err(io_error)?;
+            }
+            for sql in statements.iter() {
+                writeln!(out, "{};", sql).map_err(io_error)?;
+            }
+            if !doris {
+                writeln!(out, "COMMIT;").map_err(io_error)?;
+            }
+            out.flush().map_err(io_error)?;
+            Ok(out.bytes)
+        })
+        .await?;
+    }
+
+    if request.execute && !statements.is_empty() {
+        let repair_id = request.repair_id.clone().filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.cursors.next_id("repair"));
+        let target = driver::open_writable(&state, &compare.side.conn_id, Some(&compare.side.db))?;
+        let (mut target, cancel) = driver::blocking(move || {
+            let mut target = target;
+            let cancel = target.canceller()?;
+            Ok((target, Arc::new(cancel)))
+        })
+        .await?;
+        let label = format!("repair {}", generator.reference);
+        if let Err(e) = state.queries.register(&repair_id, &compare.side.conn_id, &label, cancel.clone()) {
+            let _ = driver::blocking(move || {
+                drop(target);
+                Ok(())
+            })
+            .await;
+            return Err(e);
+        }
+        let result = driver::blocking(move || {
+            let affected = run(target.as_mut(), dialect, &statements, &|| cancel.is_cancelled());
+            drop(target);
+            affected
+        })
+        .await;
+        state.queries.remove(&repair_id);
+        summary.affected_rows = result?;
+        summary.executed = true;
+    }
+    summary.elapsed_ms = started.elapsed().as_millis() as u64;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::compare::SideConfig;
+    use serde_json::json;
+
+    fn pair(name: &str, binary: bool, numeric: bool) -> ColumnPair {
+        ColumnPair { source: name.to_string(), target: name.to_string(), source_binary: binary, target_binary: binary, numeric }
+    }
+
+    fn target() -> CompareTarget {
+        CompareTarget {
+            side: SideConfig { conn_id: "c".to_string(), db: "d".to_string(), table: "t".to_string() },
+            keys: vec![pair("id", false, true)],
+            columns: vec![pair("name", false, false), pair("data", true, false)],
+            complete: true,
+        }
+    }
+
+    fn row(status: CompareRowStatus, source: Option<Value>, target: Option<Value>, diff: &[&str]) -> CompareRow {
+        CompareRow {
+            key_display: String::new(),
+            status,
+            source_data: source,
+            target_data: target,
+            diff_fields: diff.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn statement(dialect: Dialect, row: &CompareRow) -> Option<String> {
+        let compare = target();
+        let reference = dialect.quote_ident("t");
+        Generator { compare: &compare, dialect, reference }.statement(row).unwrap()
+    }
+
+    #[test]
+    fn deletes_rows_only_in_target() {
+        let r = row(CompareRowStatus::OnlyTarget, None, Some(json!({"id": 7, "name": "x"})), &[]);
+        assert_eq!(statement(Dialect::MySql, &r).unwrap(), "DELETE FROM `t` WHERE `id` = 7");
+        let r = row(CompareRowStatus::OnlyTarget, None, Some(json!({"id": null})), &[]);
+        assert_eq!(statement(Dialect::Postgres, &r).unwrap(), "DELETE FROM \"t\" WHERE \"id\" IS NULL");
+    }
+
+    #[test]
+    fn updates_only_differing_columns() {
+        let r = row(CompareRowStatus::Diff, Some(json!({"id": 1, "name": "it's", "data": "AAE="})), Some(json!({"id": 1, "name": "b"})), &["name", "data"]);
+        assert_eq!(statement(Dialect::MySql, &r).unwrap(), "UPDATE `t` SET `name` = 'it''s', `data` = 0x0001 WHERE `id` = 1");
+        assert_eq!(statement(Dialect::Postgres, &r).unwrap(), "UPDATE \"t\" SET \"name\" = 'it''s', \"data\" = '\\x0001'::bytea WHERE \"id\" = 1");
+        // 差异列不在对比列中时不生成语句
+        let r = row(CompareRowStatus::Diff, Some(json!({"id": 1})), Some(json!({"id": 1})), &["ignored"]);
+        assert_eq!(statement(Dialect::MySql, &r), None);
+    }
+
+    #[test]
+    fn inserts_rows_only_in_source() {
+        let r = row(CompareRowStatus::OnlySource, Some(json!({"id": 2, "name": "a\\b", "data": null})), None, &[]);
+        assert_eq!(statement(Dialect::MySql, &r).unwrap(), "INSERT INTO `t` (`id`, `name`, `data`) VALUES (2, 'a\\\\b', NULL)");
+        assert_eq!(statement(Dialect::Sqlite, &r).unwrap(), "INSERT INTO \"t\" (\"id\", \"name\", \"data\") VALUES (2, 'a\\b', NULL)");
+    }
+
+    #[test]
+    fn rejects_invalid_binary_values() {
+        let compare = target();
+        let generator = Generator { compare: &compare, dialect: Dialect::MySql, reference: "`t`".to_string() };
+        let r = row(CompareRowStatus::OnlySource, Some(json!({"id": 1, "data": "not base64!"})), None, &[]);
+        assert!(generator.statement(&r).unwrap_err().contains("data"));
+    }
+
+    #[test]
+    fn skips_matching_rows() {
+        let r = row(CompareRowStatus::Match, Some(json!({"id": 1})), Some(json!({"id": 1})), &[]);
+        assert_eq!(statement(Dialect::MySql, &r), None);
+    }
+}
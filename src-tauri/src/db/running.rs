@@ -0,0 +1,97 @@
+// 执行中的查询：按查询 id 记录中止句柄与服务端连接 id，供取消命令使用
+
+use super::driver::Canceller;
+use crate::AppState;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::State;
+
+struct RunningQuery {
+    // 查询结束 (或游标关闭) 后只剩这里持有，视为已结束
+    cancel: Arc<Canceller>,
+    connection: String,
+    sql: String,
+    started: Instant,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunningQueryInfo {
+    pub query_id: String,
+    // 连接配置 id
+    pub connection: String,
+    // MySQL 线程 id / PostgreSQL backend pid
+    pub session_id: Option<u64>,
+    pub sql: String,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Default)]
+pub struct QueryRegistry {
+    queries: Mutex<HashMap<String, RunningQuery>>,
+}
+
+impl QueryRegistry {
+    fn prune(queries: &mut HashMap<String, RunningQuery>) {
+        queries.retain(|_, q| Arc::strong_count(&q.cancel) > 1);
+    }
+
+    pub fn register(&self, query_id: &str, connection: &str, sql: &str, cancel: Arc<Canceller>) -> Result<(), String> {
+        let mut queries = self.queries.lock().unwrap();
+        Self::prune(&mut queries);
+        if queries.contains_key(query_id) {
+            return Err(format!("Query {} is already running", query_id));
+        }
+        let query = RunningQuery { cancel, connection: connection.to_string(), sql: sql.to_string(), started: Instant::now() };
+        queries.insert(query_id.to_string(), query);
+        Ok(())
+    }
+
+    pub fn remove(&self, query_id: &str) {
+        self.queries.lock().unwrap().remove(query_id);
+    }
+
+    fn get(&self, query_id: &str) -> Option<Arc<Canceller>> {
+        let mut queries = self.queries.lock().unwrap();
+        Self::prune(&mut queries);
+        queries.get(query_id).map(|q| q.cancel.clone())
+    }
+
+    pub fn list(&self) -> Vec<RunningQueryInfo> {
+        let mut queries = self.queries.lock().unwrap();
+        Self::prune(&mut queries);
+        let mut list: Vec<RunningQueryInfo> = queries
+            .iter()
+            .map(|(id, q)| RunningQueryInfo {
+                query_id: id.clone(),
+                connection: q.connection.clone(),
+                session_id: q.cancel.session_id,
+                sql: q.sql.clone(),
+                elapsed_ms: q.started.elapsed().as_millis() as u64,
+            })
+            .collect();
+        list.sort_by_key(|q| std::cmp::Reverse(q.elapsed_ms));
+        list
+    }
+}
+
+// 取消 db_query (options.queryId) 或游标上正在执行的查询，返回查询是否存在
+// MySQL 在旁路连接上执行 KILL QUERY，原连接保持可用
+#[tauri::command]
+pub async fn db_cancel_query(state: State<'_, AppState>, query_id: String) -> Result<bool, String> {
+    let Some(cancel) = state.queries.get(&query_id) else {
+        return Ok(false);
+    };
+    super::driver::blocking(move || {
+        cancel.cancel();
+        Ok(true)
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn db_running_queries(state: State<'_, AppState>) -> Vec<RunningQueryInfo> {
+    state.queries.list()
+}
@@ -0,0 +1,350 @@
+// SQLite 驱动：DbConfig.host 为本地数据库文件路径，"数据库"对应 main 及 ATTACH 的 schema
+
+use super::driver::{Canceller, DbDriver, StreamEvent};
+use super::model::{format_size, ColumnInfo, QueryColumn, TableDetail, TableInfo};
+use super::registry::DbConfig;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub struct SqliteDriver {
+    conn: Connection,
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// 声明类型中的长度/精度，如 VARCHAR(20)、DECIMAL(10,2)
+fn type_length(decl: &str) -> (Option<u32>, Option<u32>) {
+    let Some(args) = decl.split_once('(').and_then(|(_, rest)| rest.split_once(')')).map(|(args, _)| args) else {
+        return (None, None);
+    };
+    let mut parts = args.split(',').map(|p| p.trim().parse::<u32>().ok());
+    (parts.next().flatten(), parts.next().flatten())
+}
+
+impl SqliteDriver {
+    // 不自动创建文件，避免路径写错时生成空库；浏览与查询以只读方式打开，仅执行 SQL 时可写
+    pub fn open(config: &DbConfig, writable: bool) -> Result<SqliteDriver, String> {
+        let path = config.host.trim();
+        if path.is_empty() {
+            return Err("SQLite database file path is empty".to_string());
+        }
+        if !Path::new(path).is_file() {
+            return Err(format!("SQLite database file not found: {}", path));
+        }
+        let mode = if writable { OpenFlags::SQLITE_OPEN_READ_WRITE } else { OpenFlags::SQLITE_OPEN_READ_ONLY };
+        let conn = Connection::open_with_flags(path, mode | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+            .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+        Ok(SqliteDriver { conn })
+    }
+
+    fn table_size(&self, db: &str, name: &str) -> u64 {
+        // dbstat 虚拟表未编译进来时大小记为 0
+        self.conn
+            .query_row("SELECT SUM(pgsize) FROM dbstat WHERE schema = ?1 AND name = ?2", [db, name], |r| r.get::<_, Option<i64>>(0))
+            .ok()
+            .flatten()
+            .unwrap_or(0)
+            .max(0) as u64
+    }
+
+    // ANALYZE 生成的 sqlite_stat1 行数估计，未分析过的库为空
+    fn row_estimates(&self, db: &str) -> HashMap<String, i64> {
+        let query = format!("SELECT tbl, MAX(CAST(stat AS INTEGER)) FROM {}.sqlite_stat1 GROUP BY tbl", quote(db));
+        let Ok(mut stmt) = self.conn.prepare(&query) else { return HashMap::new() };
+        stmt.query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, Option<i64>>(1)?.unwrap_or(0))))
+            .and_then(|rows| rows.collect::<Result<HashMap<_, _>, _>>())
+            .unwrap_or_default()
+    }
+
+    fn row_count(&self, db: &str, name: &str) -> Result<i64, String> {
+        self.conn
+            .query_row(&format!("SELECT COUNT(*) FROM {}.{}", quote(db), quote(name)), [], |r| r.get(0))
+            .map_err(|e| format!("Failed to count rows of {}: {}", name, e))
+    }
+}
+
+impl DbDriver for SqliteDriver {
+    fn ping(&mut self) -> Result<(), String> {
+        // 读取 schema 时才会校验文件格式
+        self.conn
+            .query_row("SELECT COUNT(*) FROM sqlite_master", [], |r| r.get::<_, i64>(0))
+            .map(|_| ())
+            .map_err(|e| format!("Connection established but query failed: {}", e))
+    }
+
+    fn databases(&mut self) -> Result<Vec<String>, String> {
+        let mut stmt = self.conn.prepare("PRAGMA database_list").map_err(|e| format!("Failed to fetch databases: {}", e))?;
+        let names = stmt
+            .query_map([], |r| r.get::<_, String>(1))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to fetch databases: {}", e))?;
+        Ok(names.into_iter().filter(|n| n != "temp").collect())
+    }
+
+    fn tables(&mut self, db: &str) -> Result<Vec<TableInfo>, String> {
+        let query = format!(
+            "SELECT name, type FROM {}.sqlite_master WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\' ORDER BY type, name",
+            quote(db)
+        );
+        let mut stmt = self.conn.prepare(&query).map_err(|e| format!("Failed to fetch tables: {}", e))?;
+        let entries = stmt
+            .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, String>(1)?)))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to fetch tables: {}", e))?;
+        drop(stmt);
+
+        // 列表中不逐表 COUNT(*)，行数取统计信息估计值；精确行数见 table_schema
+        let estimates = self.row_estimates(db);
+        Ok(entries
+            .into_iter()
+            .map(|(name, kind)| {
+                let is_view = kind == "view";
+                TableInfo {
+                    rows: if is_view { 0 } else { estimates.get(&name).copied().unwrap_or(0) },
+                    size: format_size(if is_view { 0 } else { self.table_size(db, &name) }),
+                    comment: None,
+                    table_type: Some(if is_view { "VIEW" } else { "TABLE" }.to_string()),
+                    name,
+                }
+            })
+            .collect())
+    }
+
+    fn table_schema(&mut self, db: &str, table: &str) -> Result<TableDetail, String> {
+        let master = format!("{}.sqlite_master", quote(db));
+        let (kind, create_sql): (String, Option<String>) = self
+            .conn
+            .query_row(&format!("SELECT type, sql FROM {} WHERE type IN ('table', 'view') AND name = ?1", master), [table], |r| {
+                Ok((r.get(0)?, r.get(1)?))
+            })
+            .optional()
+            .map_err(|e| format!("Failed to fetch table info: {}", e))?
+            .ok_or_else(|| format!("Table not found: {}", table))?;
+
+        // table_xinfo 比 table_info 多出生成列 (hidden = 2/3)
+        let column_query = format!("PRAGMA {}.table_xinfo({})", quote(db), quote(table));
+        let mut stmt = self.conn.prepare(&column_query).map_err(|e| format!("Failed to fetch columns: {}", e))?;
+        let columns = stmt
+            .query_map([], |r| {
+                let col_type: String = r.get(2)?;
+                let (length, scale) = type_length(&col_type);
+                let pk: i64 = r.get(5)?;
+                Ok(ColumnInfo {
+                    name: r.get(1)?,
+                    col_type,
+                    length,
+                    scale,
+                    // 主键列按不可为空返回 (SQLite 历史原因允许非 INTEGER 主键为 NULL)
+                    nullable: r.get::<_, i64>(3)? == 0 && pk == 0,
+                    is_primary_key: pk > 0,
+                    default_value: r.get(4)?,
+                    comment: None,
+                })
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to fetch columns: {}", e))?;
+        drop(stmt);
+
+        // 原始建表语句，附带该表的索引与触发器
+        let mut ddl = create_sql.map(|s| format!("{};", s)).unwrap_or_default();
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT sql FROM {} WHERE type IN ('index', 'trigger') AND tbl_name = ?1 AND sql IS NOT NULL ORDER BY type, name", master))
+            .map_err(|e| format!("Failed to fetch DDL: {}", e))?;
+        let extras = stmt
+            .query_map([table], |r| r.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Failed to fetch DDL: {}", e))?;
+        drop(stmt);
+        for sql in extras {
+            ddl.push_str(&format!("\n{};", sql));
+        }
+
+        let is_view = kind == "view";
+        let encoding: Option<String> = self.conn.query_row("PRAGMA encoding", [], |r| r.get(0)).ok();
+        Ok(TableDetail {
+            name: table.to_string(),
+            rows: if is_view { 0 } else { self.row_count(db, table)? },
+            size: format_size(if is_view { 0 } else { self.table_size(db, table) }),
+            engine: Some(if is_view { "VIEW" } else { "SQLite" }.to_string()),
+            collation: encoding,
+            comment: None,
+            columns,
+            ddl,
+            doris: None,
+        })
+    }
+
+    fn stream(&mut self, sql: &str, params: Option<serde_json::Value>, sink: &mut dyn FnMut(StreamEvent) -> bool) -> Result<(), String> {
+        let mut stmt = self.conn.prepare(sql).map_err(|e| format!("Query failed: {}", e))?;
+        match params {
+            None | Some(serde_json::Value::Null) => {}
+            Some(serde_json::Value::Array(items)) => {
+                for (i, item) in items.iter().enumerate() {
+                    stmt.raw_bind_parameter(i + 1, json_to_value(item)).map_err(|e| format!("Failed to bind parameter {}: {}", i + 1, e))?;
+                }
+            }
+            // 名称可带或不带 :/@/$ 前缀
+            Some(serde_json::Value::Object(map)) => {
+                for (key, item) in &map {
+                    let candidates = if key.starts_with([':', '@', '$']) { vec![key.clone()] } else { [":", "@", "$"].iter().map(|p| format!("{}{}", p, key)).collect() };
+                    let index = candidates
+                        .iter()
+                        .find_map(|name| stmt.parameter_index(name).ok().flatten())
+                        .ok_or_else(|| format!("Unknown parameter: {}", key))?;
+                    stmt.raw_bind_parameter(index, json_to_value(item)).map_err(|e| format!("Failed to bind parameter {}: {}", key, e))?;
+                }
+            }
+            Some(_) => return Err("Query parameters must be an array or an object".to_string()),
+        }
+
+        // 表达式列没有声明类型
+        let columns: Vec<QueryColumn> = stmt
+            .columns()
+            .iter()
+            .map(|c| {
+                let decl = c.decl_type().unwrap_or("");
+                if decl.to_uppercase().contains("BLOB") { QueryColumn::binary(c.name(), decl) } else { QueryColumn::new(c.name(), decl) }
+            })
+            .collect();
+        let names: Vec<String> = columns.iter().map(|c| c.name.clone()).collect();
+        if !sink(StreamEvent::Columns(columns)) {
+            return Ok(());
+        }
+        // 逐步执行语句，停止读取时不再继续 step
+        let mut rows = stmt.raw_query();
+        while let Some(row) = rows.next().map_err(|e| format!("Query failed: {}", e))? {
+            let mut map = serde_json::Map::new();
+            for (idx, name) in names.iter().enumerate() {
+                let value = row.get_ref(idx).map_err(|e| format!("Query failed: {}", e))?;
+                map.insert(name.clone(), value_to_json(value));
+            }
+            if !sink(StreamEvent::Row(serde_json::Value::Object(map))) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(&mut self, sql: &str) -> Result<u64, String> {
+        self.conn.execute(sql, []).map(|n| n as u64).map_err(|e| format!("Query failed: {}", e))
+    }
+
+    fn canceller(&mut self) -> Result<Canceller, String> {
+        let handle = self.conn.get_interrupt_handle();
+        Ok(Canceller::new(None, move || handle.interrupt()))
+    }
+}
+
+fn json_to_value(value: &serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        // 数组/对象按 JSON 文本绑定
+        other => Value::Text(other.to_string()),
+    }
+}
+
+// BLOB 与 MySQL、PostgreSQL 的二进制值一致，以 base64 返回
+fn value_to_json(value: ValueRef<'_>) -> serde_json::Value {
+    match value {
+        ValueRef::Null => serde_json::Value::Null,
+        ValueRef::Integer(i) => serde_json::Value::Number(i.into()),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map(serde_json::Value::Number).unwrap_or(serde_json::Value::Null),
+        ValueRef::Text(bytes) => serde_json::Value::String(String::from_utf8_lossy(bytes).to_string()),
+        ValueRef::Blob(bytes) => serde_json::Value::String(B64.encode(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> (tempfile::TempDir, DbConfig) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name VARCHAR(20) NOT NULL, score DECIMAL(10,2) DEFAULT 0, avatar BLOB);
+             CREATE INDEX idx_users_name ON users (name);
+             CREATE TRIGGER trg_users AFTER DELETE ON users BEGIN SELECT 1; END;
+             CREATE VIEW active_users AS SELECT id, name FROM users;
+             INSERT INTO users (id, name, score, avatar) VALUES (1, 'a', 1.5, x'0102'), (2, 'b', 2, NULL), (3, 'c', 3, NULL);",
+        )
+        .unwrap();
+        let config: DbConfig = serde_json::from_value(serde_json::json!({
+            "id": "t", "name": "t", "type": "sqlite", "host": path.to_string_lossy(), "port": "", "user": "", "database": "main"
+        }))
+        .unwrap();
+        (dir, config)
+    }
+
+    fn collect(driver: &mut SqliteDriver, sql: &str, params: Option<serde_json::Value>) -> Vec<serde_json::Value> {
+        let mut rows = Vec::new();
+        driver
+            .stream(sql, params, &mut |event| {
+                if let StreamEvent::Row(row) = event {
+                    rows.push(row);
+                }
+                true
+            })
+            .unwrap();
+        rows
+    }
+
+    #[test]
+    fn lists_tables_and_views() {
+        let (_dir, config) = fixture();
+        let mut driver = SqliteDriver::open(&config, false).unwrap();
+        let tables = driver.tables("main").unwrap();
+        let kinds: Vec<(&str, &str)> = tables.iter().map(|t| (t.name.as_str(), t.table_type.as_deref().unwrap())).collect();
+        assert_eq!(kinds, vec![("users", "TABLE"), ("active_users", "VIEW")]);
+    }
+
+    #[test]
+    fn describes_keys_ddl_indexes_and_triggers() {
+        let (_dir, config) = fixture();
+        let mut driver = SqliteDriver::open(&config, false).unwrap();
+        let detail = driver.table_schema("main", "users").unwrap();
+        assert_eq!(detail.rows, 3);
+        let id = &detail.columns[0];
+        assert!(id.is_primary_key && !id.nullable);
+        let name = &detail.columns[1];
+        assert_eq!((name.length, name.nullable), (Some(20), false));
+        assert_eq!((detail.columns[2].length, detail.columns[2].scale), (Some(10), Some(2)));
+        assert!(detail.ddl.starts_with("CREATE TABLE users"));
+        assert!(detail.ddl.contains("CREATE INDEX idx_users_name"));
+        assert!(detail.ddl.contains("CREATE TRIGGER trg_users"));
+        assert_eq!(driver.table_schema("main", "active_users").unwrap().engine.as_deref(), Some("VIEW"));
+    }
+
+    #[test]
+    fn streams_with_positional_and_named_params() {
+        let (_dir, config) = fixture();
+        let mut driver = SqliteDriver::open(&config, false).unwrap();
+        let rows = collect(&mut driver, "SELECT id, avatar FROM users WHERE id >= ? AND id < ? ORDER BY id", Some(serde_json::json!([1, 3])));
+        assert_eq!(rows, vec![serde_json::json!({"id": 1, "avatar": "AQI="}), serde_json::json!({"id": 2, "avatar": null})]);
+        let rows = collect(&mut driver, "SELECT name FROM users WHERE name = :name", Some(serde_json::json!({"name": "c"})));
+        assert_eq!(rows, vec![serde_json::json!({"name": "c"})]);
+        let rows = collect(&mut driver, "SELECT name FROM users WHERE id = :id", Some(serde_json::json!({":id": 2})));
+        assert_eq!(rows, vec![serde_json::json!({"name": "b"})]);
+    }
+
+    #[test]
+    fn read_only_unless_writable() {
+        let (_dir, config) = fixture();
+        assert!(SqliteDriver::open(&config, false).unwrap().execute("DELETE FROM users").is_err());
+        assert_eq!(SqliteDriver::open(&config, true).unwrap().execute("DELETE FROM users").unwrap(), 3);
+    }
+}
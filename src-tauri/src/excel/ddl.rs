@@ -0,0 +1,683 @@
+// 根据 Excel 数据推断列类型并生成建表语句
+
+use super::{cell_to_string, read_sheet, sheet_headers, ExcelError};
+use crate::sql::Dialect;
+use calamine::{Data, Range};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const DEFAULT_SAMPLE_ROWS: usize = 1000;
+const DEFAULT_BUCKETS: u32 = 10;
+// DECIMAL 最大精度 (MySQL/Doris 为 38 的公共子集)
+const MAX_DECIMAL_PRECISION: u32 = 38;
+
+pub const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d"];
+pub const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+];
+
+// 建表选项 (均可省略)
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DdlOptions {
+    pub table_name: Option<String>,
+    // 表头所在行 (从 1 开始，与 ExcelImport 的 headerRowIdx 一致)
+    pub header_row: Option<usize>,
+    pub sample_rows: Option<usize>,
+    // 显式指定主键列 (使用推断后的列名)
+    pub primary_keys: Option<Vec<String>>,
+    // Doris 数据模型: duplicate / unique
+    pub key_model: Option<String>,
+    pub buckets: Option<u32>,
+    pub replication_num: Option<u32>,
+}
+
+// 推断出的列类型
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ColumnType {
+    TinyInt,
+    SmallInt,
+    Int,
+    BigInt,
+    Decimal { precision: u32, scale: u32 },
+    Double,
+    Boolean,
+    Date,
+    DateTime,
+    Time,
+    Varchar { length: u32 },
+    Text,
+}
+
+impl ColumnType {
+    pub fn is_integer(&self) -> bool {
+        matches!(self, ColumnType::TinyInt | ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt)
+    }
+
+    // 渲染为目标方言的类型名
+    pub fn to_sql(&self, dialect: Dialect) -> String {
+        match (self, dialect) {
+            (ColumnType::TinyInt, Dialect::Postgres) => "SMALLINT".to_string(),
+            (ColumnType::TinyInt, _) => "TINYINT".to_string(),
+            (ColumnType::SmallInt, _) => "SMALLINT".to_string(),
+            (ColumnType::Int, Dialect::Postgres) => "INTEGER".to_string(),
+            (ColumnType::Int, _) => "INT".to_string(),
+            (ColumnType::BigInt, _) => "BIGINT".to_string(),
+            (ColumnType::Decimal { precision, scale }, Dialect::Postgres) => format!("NUMERIC({},{})", precision, scale),
+            (ColumnType::Decimal { precision, scale }, _) => format!("DECIMAL({},{})", precision, scale),
+            (ColumnType::Double, Dialect::Postgres) => "DOUBLE PRECISION".to_string(),
+            (ColumnType::Double, _) => "DOUBLE".to_string(),
+            (ColumnType::Boolean, Dialect::MySql) => "TINYINT(1)".to_string(),
+            (ColumnType::Boolean, _) => "BOOLEAN".to_string(),
+            (ColumnType::Date, _) => "DATE".to_string(),
+            (ColumnType::DateTime, Dialect::Postgres) => "TIMESTAMP".to_string(),
+            (ColumnType::DateTime, _) => "DATETIME".to_string(),
+            (ColumnType::Time, Dialect::Doris) => "VARCHAR(16)".to_string(),
+            (ColumnType::Time, _) => "TIME".to_string(),
+            // Doris 的 VARCHAR 长度按字节计算，按 UTF-8 三字节放大
+            (ColumnType::Varchar { length }, Dialect::Doris) => {
+                let bytes = length.saturating_mul(3);
+                if bytes > 65533 {
+                    "STRING".to_string()
+                } else {
+                    format!("VARCHAR({})", bytes)
+                }
+            }
+            (ColumnType::Varchar { length }, _) => format!("VARCHAR({})", length),
+            (ColumnType::Text, Dialect::Doris) => "STRING".to_string(),
+            (ColumnType::Text, _) => "TEXT".to_string(),
+        }
+    }
+}
+
+// 推断结果
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InferredColumn {
+    pub name: String,
+    pub header: String,
+    pub col_type: ColumnType,
+    pub nullable: bool,
+    pub candidate_key: bool,
+}
+
+// 单列采样统计
+#[derive(Default)]
+struct ColumnStats {
+    non_null: usize,
+    nulls: usize,
+    max_len: usize,
+    int_min: i64,
+    int_max: i64,
+    has_int: bool,
+    has_decimal: bool,
+    has_double: bool,
+    int_digits: u32,
+    scale: u32,
+    has_bool: bool,
+    has_date: bool,
+    has_datetime: bool,
+    has_time: bool,
+    has_text: bool,
+    distinct: HashSet<String>,
+    duplicated: bool,
+}
+
+impl ColumnStats {
+    fn observe_int(&mut self, v: i64) {
+        if !self.has_int {
+            self.int_min = v;
+            self.int_max = v;
+        }
+        self.has_int = true;
+        self.int_min = self.int_min.min(v);
+        self.int_max = self.int_max.max(v);
+        self.int_digits = self.int_digits.max(v.unsigned_abs().to_string().len() as u32);
+    }
+
+    // 按十进制文本统计整数位与小数位
+    fn observe_decimal(&mut self, text: &str) {
+        let digits = text.trim_start_matches(['-', '+']);
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        let int_len = int_part.trim_start_matches('0').len().max(1) as u32;
+        let frac_len = frac_part.trim_end_matches('0').len() as u32;
+        if int_len + frac_len > MAX_DECIMAL_PRECISION {
+            self.has_double = true;
+            return;
+        }
+        self.has_decimal = true;
+        self.int_digits = self.int_digits.max(int_len);
+        self.scale = self.scale.max(frac_len);
+    }
+
+    fn observe_string(&mut self, s: &str) {
+        if let Some(v) = parse_plain_int(s) {
+            self.observe_int(v);
+        } else if is_plain_decimal(s) {
+            self.observe_decimal(s);
+        } else if DATETIME_FORMATS.iter().any(|f| NaiveDateTime::parse_from_str(s, f).is_ok()) {
+            self.has_datetime = true;
+        } else if DATE_FORMATS.iter().any(|f| NaiveDate::parse_from_str(s, f).is_ok()) {
+            self.has_date = true;
+        } else if NaiveTime::parse_from_str(s, "%H:%M:%S").is_ok() {
+            self.has_time = true;
+        } else {
+            self.has_text = true;
+        }
+    }
+
+    fn observe(&mut self, cell: &Data) {
+        let text = cell_to_string(cell);
+        if text.is_empty() {
+            self.nulls += 1;
+            return;
+        }
+        self.non_null += 1;
+        self.max_len = self.max_len.max(text.chars().count());
+        if !self.duplicated && !self.distinct.insert(text.clone()) {
+            self.duplicated = true;
+            self.distinct.clear();
+        }
+
+        match cell {
+            Data::Int(i) => self.observe_int(*i),
+            Data::Float(f) if f.fract() == 0.0 && f.abs() < 9.0e15 => self.observe_int(*f as i64),
+            Data::Float(f) if f.is_finite() => self.observe_decimal(&f.to_string()),
+            Data::Float(_) => self.has_double = true,
+            Data::Bool(_) => self.has_bool = true,
+            Data::DateTime(dt) if dt.is_duration() => self.has_time = true,
+            Data::DateTime(_) => {
+                if text.len() > 10 {
+                    self.has_datetime = true;
+                } else {
+                    self.has_date = true;
+                }
+            }
+            Data::Error(_) => self.has_text = true,
+            _ => self.observe_string(&text),
+        }
+    }
+
+    fn resolve(&self) -> ColumnType {
+        if self.non_null == 0 {
+            return ColumnType::Varchar { length: 255 };
+        }
+
+        let numeric = self.has_int || self.has_decimal || self.has_double;
+        let temporal = self.has_date || self.has_datetime || self.has_time;
+        let mixed = [numeric, temporal, self.has_bool].iter().filter(|b| **b).count() > 1
+            || (self.has_time && (self.has_date || self.has_datetime));
+
+        if self.has_text || mixed {
+            return varchar_for(self.max_len);
+        }
+        if self.has_bool {
+            return ColumnType::Boolean;
+        }
+        if temporal {
+            return if self.has_time {
+                ColumnType::Time
+            } else if self.has_datetime {
+                ColumnType::DateTime
+            } else {
+                ColumnType::Date
+            };
+        }
+        if self.has_double {
+            return ColumnType::Double;
+        }
+        if self.has_decimal {
+            let precision = (self.int_digits + self.scale).max(1);
+            if precision > MAX_DECIMAL_PRECISION {
+                return ColumnType::Double;
+            }
+            return ColumnType::Decimal { precision, scale: self.scale };
+        }
+
+        if self.int_min >= i8::MIN as i64 && self.int_max <= i8::MAX as i64 {
+            ColumnType::TinyInt
+        } else if self.int_min >= i16::MIN as i64 && self.int_max <= i16::MAX as i64 {
+            ColumnType::SmallInt
+        } else if self.int_min >= i32::MIN as i64 && self.int_max <= i32::MAX as i64 {
+            ColumnType::Int
+        } else {
+            ColumnType::BigInt
+        }
+    }
+}
+
+// 不带前导零的整数文本 (00123 之类的编码按字符串处理)
+fn parse_plain_int(s: &str) -> Option<i64> {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    if digits.len() > 1 && digits.starts_with('0') {
+        return None;
+    }
+    s.parse().ok()
+}
+
+fn is_plain_decimal(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    match digits.split_once('.') {
+        Some((i, f)) => {
+            !i.is_empty()
+                && !f.is_empty()
+                && i.bytes().all(|b| b.is_ascii_digit())
+                && f.bytes().all(|b| b.is_ascii_digit())
+                && !(i.len() > 1 && i.starts_with('0'))
+        }
+        None => false,
+    }
+}
+
+// 字符串长度向上取整到常用档位
+fn varchar_for(max_len: usize) -> ColumnType {
+    const STEPS: [u32; 8] = [32, 64, 128, 255, 512, 1024, 2048, 4096];
+    STEPS
+        .iter()
+        .find(|s| **s as usize >= max_len)
+        .map(|s| ColumnType::Varchar { length: *s })
+        .unwrap_or(ColumnType::Text)
+}
+
+// 表头转列名：空白与符号替换为下划线，重复名追加序号
+pub fn normalize_column_names(headers: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    headers
+        .iter()
+        .map(|h| {
+            let mut name: String = h
+                .trim()
+                .chars()
+                .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+                .collect();
+            name = name.trim_matches('_').to_string();
+            if name.is_empty() {
+                name = "column".to_string();
+            }
+            if name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                name = format!("c_{}", name);
+            }
+            let base = name.clone();
+            let mut n = 2;
+            while !seen.insert(name.to_lowercase()) {
+                name = format!("{}_{}", base, n);
+                n += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+// 采样推断每一列的类型、可空性与候选主键
+pub fn infer_columns(range: &Range<Data>, header_row: usize, sample_rows: usize) -> Vec<InferredColumn> {
+    let headers = sheet_headers(range, header_row);
+    let names = normalize_column_names(&headers);
+    let mut stats: Vec<ColumnStats> = headers.iter().map(|_| ColumnStats::default()).collect();
+
+    for row in range.rows().skip(header_row + 1).take(sample_rows) {
+        if row.iter().all(|c| cell_to_string(c).is_empty()) {
+            continue;
+        }
+        for (i, stat) in stats.iter_mut().enumerate() {
+            stat.observe(row.get(i).unwrap_or(&Data::Empty));
+        }
+    }
+
+    headers
+        .into_iter()
+        .zip(names)
+        .zip(stats)
+        .map(|((header, name), stat)| {
+            let col_type = stat.resolve();
+            let keyable = col_type.is_integer()
+                || matches!(col_type, ColumnType::Varchar { length } if length <= 255)
+                || matches!(col_type, ColumnType::Date | ColumnType::DateTime);
+            InferredColumn {
+                candidate_key: keyable && stat.nulls == 0 && stat.non_null > 0 && !stat.duplicated,
+                nullable: stat.nulls > 0 || stat.non_null == 0,
+                name,
+                header,
+                col_type,
+            }
+        })
+        .collect()
+}
+
+// 选择主键：显式指定优先，其次名称像 id 的候选列，再其次第一个候选列
+fn choose_primary_keys(columns: &[InferredColumn], requested: Option<&Vec<String>>) -> Vec<String> {
+    if let Some(keys) = requested {
+        return keys.iter().filter(|k| columns.iter().any(|c| &c.name == *k)).cloned().collect();
+    }
+    let candidates: Vec<&InferredColumn> = columns.iter().filter(|c| c.candidate_key).collect();
+    candidates
+        .iter()
+        .find(|c| {
+            let n = c.name.to_lowercase();
+            n == "id" || n.ends_with("_id") || n.ends_with("编号")
+        })
+        .or_else(|| candidates.first())
+        .map(|c| vec![c.name.clone()])
+        .unwrap_or_default()
+}
+
+// 在 Doris 中生成为 STRING 的列 (STRING 不能作为 Key)
+fn doris_string(col_type: &ColumnType) -> bool {
+    match col_type {
+        ColumnType::Text => true,
+        ColumnType::Varchar { length } => length.saturating_mul(3) > 65533,
+        _ => false,
+    }
+}
+
+// Doris 的 Key 列：有主键时按主键顺序，否则取第一个非浮点、非 STRING 的列，再其次第一个非浮点列
+// 浮点列不能作为 Key；STRING 列作为 Key 时改为 VARCHAR(65533)
+fn doris_keys(columns: &[InferredColumn], pks: &[String]) -> Result<Vec<String>, ExcelError> {
+    let keys = if pks.is_empty() {
+        columns
+            .iter()
+            .find(|c| c.col_type != ColumnType::Double && !doris_string(&c.col_type))
+            .or_else(|| columns.iter().find(|c| c.col_type != ColumnType::Double))
+            .map(|c| vec![c.name.clone()])
+            .ok_or_else(|| ExcelError::InvalidMapping("no column can be used as a Doris key".to_string()))?
+    } else {
+        pks.to_vec()
+    };
+    match columns.iter().find(|c| keys.contains(&c.name) && c.col_type == ColumnType::Double) {
+        Some(c) => Err(ExcelError::InvalidMapping(format!("column {} is DOUBLE and cannot be a Doris key", c.name))),
+        None => Ok(keys),
+    }
+}
+
+// 生成建表语句
+pub fn render_ddl(dialect: Dialect, table: &str, sheet: &str, columns: &[InferredColumn], opts: &DdlOptions) -> Result<String, ExcelError> {
+    let pks = choose_primary_keys(columns, opts.primary_keys.as_ref());
+    let candidates: Vec<&str> = columns.iter().filter(|c| c.candidate_key).map(|c| c.name.as_str()).collect();
+    let q = |n: &str| dialect.quote_ident(n);
+
+    // Doris 要求 Key 列按 KEY(...) 中的顺序位于最前
+    let keys = if dialect == Dialect::Doris { doris_keys(columns, &pks)? } else { Vec::new() };
+    let mut ordered: Vec<&InferredColumn> = keys.iter().filter_map(|k| columns.iter().find(|c| &c.name == k)).collect();
+    ordered.extend(columns.iter().filter(|c| !keys.contains(&c.name)));
+
+    let mut sql = format!("-- Generated SQL for Sheet: {}\n-- Target DB: {}\n", sheet, dialect.name());
+    if !candidates.is_empty() {
+        sql.push_str(&format!("-- Candidate primary keys: {}\n", candidates.join(", ")));
+    }
+    sql.push('\n');
+
+    let col_defs: Vec<String> = ordered
+        .iter()
+        .map(|c| {
+            let is_pk = pks.contains(&c.name);
+            // 自增主键类的小整数列放宽到 INT，避免样本之外的数据溢出
+            let col_type = match c.col_type {
+                ColumnType::TinyInt | ColumnType::SmallInt if is_pk => ColumnType::Int,
+                ref t => t.clone(),
+            };
+            let sql_type = if keys.contains(&c.name) && doris_string(&col_type) {
+                "VARCHAR(65533)".to_string()
+            } else {
+                col_type.to_sql(dialect)
+            };
+            let mut def = format!("  {} {}", q(&c.name), sql_type);
+            if !c.nullable || is_pk {
+                def.push_str(" NOT NULL");
+            }
+            if dialect != Dialect::Postgres {
+                def.push_str(&format!(" COMMENT {}", dialect.quote_str(&c.header)));
+            }
+            def
+        })
+        .collect();
+    let pk_list = pks.iter().map(|k| q(k)).collect::<Vec<_>>().join(", ");
+
+    match dialect {
+        Dialect::MySql => {
+            sql.push_str(&format!("CREATE TABLE IF NOT EXISTS {} (\n", q(table)));
+            sql.push_str(&col_defs.join(",\n"));
+            if !pks.is_empty() {
+                sql.push_str(&format!(",\n  PRIMARY KEY ({})", pk_list));
+            }
+            sql.push_str(&format!(
+                "\n) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COMMENT={};",
+                dialect.quote_str(&format!("Generated from {}", sheet))
+            ));
+        }
+        Dialect::Doris => {
+            let unique = opts.key_model.as_deref().map(|m| m.eq_ignore_ascii_case("unique")).unwrap_or(!pks.is_empty());
+            let key_list = keys.iter().map(|k| q(k)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!("CREATE TABLE IF NOT EXISTS {} (\n", q(table)));
+            sql.push_str(&col_defs.join(",\n"));
+            sql.push_str("\n) ENGINE=OLAP\n");
+            sql.push_str(&format!("{} KEY({})\n", if unique { "UNIQUE" } else { "DUPLICATE" }, key_list));
+            sql.push_str(&format!(
+                "COMMENT {}\n",
+                dialect.quote_str(&format!("Generated from {}", sheet))
+            ));
+            sql.push_str(&format!(
+                "DISTRIBUTED BY HASH({}) BUCKETS {}\n",
+                q(&keys[0]),
+                opts.buckets.unwrap_or(DEFAULT_BUCKETS)
+            ));
+            sql.push_str(&format!("PROPERTIES (\n  \"replication_num\" = \"{}\"", opts.replication_num.unwrap_or(1)));
+            if unique {
+                sql.push_str(",\n  \"enable_unique_key_merge_on_write\" = \"true\"");
+            }
+            sql.push_str("\n);");
+        }
+        // SQLite 不经 Dialect::parse 产生，按标准 SQL 处理
+        Dialect::Postgres | Dialect::Sqlite => {
+            sql.push_str(&format!("CREATE TABLE IF NOT EXISTS {} (\n", q(table)));
+            sql.push_str(&col_defs.join(",\n"));
+            if !pks.is_empty() {
+                sql.push_str(&format!(",\n  PRIMARY KEY ({})", pk_list));
+            }
+            sql.push_str("\n);\n");
+            sql.push_str(&format!(
+                "COMMENT ON TABLE {} IS {};\n",
+                q(table),
+                dialect.quote_str(&format!("Generated from {}", sheet))
+            ));
+            for c in columns {
+                sql.push_str(&format!(
+                    "COMMENT ON COLUMN {}.{} IS {};\n",
+                    q(table),
+                    q(&c.name),
+                    dialect.quote_str(&c.header)
+                ));
+            }
+        }
+    }
+
+    Ok(sql)
+}
+
+// 默认表名：工作表名小写，非字母数字替换为下划线
+pub fn default_table_name(sheet: &str) -> String {
+    sheet
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[tauri::command]
+pub fn generate_excel_sql(
+    file_name: String,
+    sheet_name: String,
+    db_type: String,
+    options: Option<DdlOptions>,
+) -> Result<String, ExcelError> {
+    let dialect = Dialect::parse(&db_type).ok_or_else(|| ExcelError::Unsupported(format!("database type {}", db_type)))?;
+    let opts = options.unwrap_or_default();
+    let range = read_sheet(&file_name, &sheet_name)?;
+    let columns = infer_columns(
+        &range,
+        opts.header_row.unwrap_or(1).saturating_sub(1),
+        opts.sample_rows.unwrap_or(DEFAULT_SAMPLE_ROWS),
+    );
+    let table = opts
+        .table_name
+        .clone()
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| default_table_name(&sheet_name));
+
+    render_ddl(dialect, &table, &sheet_name, &columns, &opts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 第一行为表头，其余为数据行
+    fn sheet(rows: Vec<Vec<Data>>) -> Range<Data> {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(1) as u32;
+        let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width - 1));
+        for (r, row) in rows.into_iter().enumerate() {
+            for (c, cell) in row.into_iter().enumerate() {
+                range.set_value((r as u32, c as u32), cell);
+            }
+        }
+        range
+    }
+
+    fn text(s: &str) -> Data {
+        Data::String(s.to_string())
+    }
+
+    fn column_type(cells: Vec<Data>) -> ColumnType {
+        let mut rows = vec![vec![text("value")]];
+        rows.extend(cells.into_iter().map(|c| vec![c]));
+        infer_columns(&sheet(rows), 0, DEFAULT_SAMPLE_ROWS).remove(0).col_type
+    }
+
+    #[test]
+    fn picks_smallest_integer_type() {
+        assert_eq!(column_type(vec![Data::Int(1), Data::Float(-100.0)]), ColumnType::TinyInt);
+        assert_eq!(column_type(vec![Data::Int(1), text("30000")]), ColumnType::SmallInt);
+        assert_eq!(column_type(vec![Data::Int(70_000)]), ColumnType::Int);
+        assert_eq!(column_type(vec![Data::Int(3_000_000_000)]), ColumnType::BigInt);
+    }
+
+    #[test]
+    fn infers_decimal_precision_and_scale() {
+        assert_eq!(column_type(vec![text("12.50"), text("3.125")]), ColumnType::Decimal { precision: 5, scale: 3 });
+        assert_eq!(column_type(vec![Data::Float(1.5), Data::Int(100)]), ColumnType::Decimal { precision: 4, scale: 1 });
+        assert_eq!(column_type(vec![text("0.1234567890123456789012345678901234567890")]), ColumnType::Double);
+    }
+
+    #[test]
+    fn keeps_codes_with_leading_zeros_as_strings() {
+        assert_eq!(column_type(vec![text("00123"), text("00456")]), ColumnType::Varchar { length: 32 });
+        assert_eq!(column_type(vec![text("01.5")]), ColumnType::Varchar { length: 32 });
+    }
+
+    #[test]
+    fn infers_temporal_and_boolean_types() {
+        assert_eq!(column_type(vec![text("2024-01-02"), text("2024/03/04")]), ColumnType::Date);
+        assert_eq!(column_type(vec![text("2024-01-02"), text("2024-01-02 10:30:00")]), ColumnType::DateTime);
+        assert_eq!(column_type(vec![text("10:30:00")]), ColumnType::Time);
+        assert_eq!(column_type(vec![Data::Bool(true), Data::Bool(false)]), ColumnType::Boolean);
+    }
+
+    #[test]
+    fn mixed_or_empty_columns_fall_back_to_varchar() {
+        assert_eq!(column_type(vec![Data::Int(1), text("2024-01-02")]), ColumnType::Varchar { length: 32 });
+        assert_eq!(column_type(vec![text("abc"), Data::Int(1)]), ColumnType::Varchar { length: 32 });
+        assert_eq!(column_type(vec![text(&"x".repeat(300))]), ColumnType::Varchar { length: 512 });
+        assert_eq!(column_type(vec![text(&"x".repeat(5000))]), ColumnType::Text);
+        assert_eq!(column_type(vec![Data::Empty]), ColumnType::Varchar { length: 255 });
+    }
+
+    #[test]
+    fn finds_nullability_and_candidate_keys() {
+        let range = sheet(vec![
+            vec![text("Name"), text("User ID"), text("Score")],
+            vec![text("a"), Data::Int(1), Data::Int(5)],
+            vec![text("a"), Data::Int(2), Data::Empty],
+        ]);
+        let columns = infer_columns(&range, 0, DEFAULT_SAMPLE_ROWS);
+        let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Name", "User_ID", "Score"]);
+        assert!(!columns[0].candidate_key);
+        assert!(columns[1].candidate_key && !columns[1].nullable);
+        assert!(columns[2].nullable && !columns[2].candidate_key);
+        assert_eq!(choose_primary_keys(&columns, None), ["User_ID"]);
+    }
+
+    #[test]
+    fn normalizes_header_names() {
+        let headers: Vec<String> = ["Name", "name", "1st col", "  ", "单价(元)"].iter().map(|h| h.to_string()).collect();
+        assert_eq!(normalize_column_names(&headers), ["Name", "name_2", "c_1st_col", "column", "单价_元"]);
+    }
+
+    fn column(name: &str, col_type: ColumnType) -> InferredColumn {
+        InferredColumn { name: name.to_string(), header: name.to_string(), col_type, nullable: true, candidate_key: false }
+    }
+
+    fn doris(columns: &[InferredColumn], primary_keys: Option<&[&str]>) -> Result<String, ExcelError> {
+        let opts = DdlOptions { primary_keys: primary_keys.map(|k| k.iter().map(|k| k.to_string()).collect()), ..Default::default() };
+        render_ddl(Dialect::Doris, "t", "Sheet1", columns, &opts)
+    }
+
+    // 各列定义在 DDL 中出现的顺序
+    fn column_order(ddl: &str) -> Vec<String> {
+        ddl.lines()
+            .filter(|l| l.starts_with("  `"))
+            .map(|l| l.trim_start().split('`').nth(1).unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn doris_key_columns_come_first_in_key_order() {
+        let columns = [column("name", ColumnType::Varchar { length: 10 }), column("b", ColumnType::Int), column("c", ColumnType::Int)];
+        let ddl = doris(&columns, Some(&["c", "b"])).unwrap();
+        assert_eq!(column_order(&ddl), ["c", "b", "name"]);
+        assert!(ddl.contains("UNIQUE KEY(`c`, `b`)"));
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`c`)"));
+    }
+
+    #[test]
+    fn doris_moves_the_chosen_key_to_the_front() {
+        let columns = [column("score", ColumnType::Double), column("note", ColumnType::Text), column("code", ColumnType::Varchar { length: 10 })];
+        let ddl = doris(&columns, None).unwrap();
+        assert_eq!(column_order(&ddl), ["code", "score", "note"]);
+        assert!(ddl.contains("DUPLICATE KEY(`code`)"));
+        assert!(ddl.contains("DISTRIBUTED BY HASH(`code`)"));
+    }
+
+    #[test]
+    fn doris_string_key_becomes_varchar() {
+        let columns = [column("a", ColumnType::Text), column("b", ColumnType::Text)];
+        let ddl = doris(&columns, None).unwrap();
+        assert!(ddl.contains("`a` VARCHAR(65533)"));
+        assert!(ddl.contains("`b` STRING"));
+        assert!(ddl.contains("DUPLICATE KEY(`a`)"));
+    }
+
+    #[test]
+    fn doris_rejects_tables_without_a_usable_key() {
+        assert!(doris(&[], None).is_err());
+        assert!(doris(&[column("x", ColumnType::Double)], None).is_err());
+        assert!(doris(&[column("x", ColumnType::Double), column("y", ColumnType::Int)], Some(&["x"])).is_err());
+    }
+
+    #[test]
+    fn renders_types_per_dialect() {
+        let varchar = ColumnType::Varchar { length: 100 };
+        assert_eq!(varchar.to_sql(Dialect::MySql), "VARCHAR(100)");
+        assert_eq!(varchar.to_sql(Dialect::Doris), "VARCHAR(300)");
+        assert_eq!(ColumnType::Varchar { length: 30000 }.to_sql(Dialect::Doris), "STRING");
+        assert_eq!(ColumnType::Boolean.to_sql(Dialect::MySql), "TINYINT(1)");
+        assert_eq!(ColumnType::TinyInt.to_sql(Dialect::Postgres), "SMALLINT");
+    }
+}
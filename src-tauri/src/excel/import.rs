@@ -0,0 +1,247 @@
+// Excel 直接导入 MySQL：按批次在事务内写入，返回逐行拒绝报告
+
+use super::insert::{data_rows, InsertMode, RowMapper, MAX_REJECTIONS};
+use super::value::SqlValue;
+use super::{read_sheet, sheet_headers, ColumnMapping, ExcelError, RowRejection};
+use crate::db::driver;
+use crate::sql::Dialect;
+use crate::AppState;
+use mysql::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use tauri::State;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+
+// 与前端 ExcelImport 的 ImportProfile 一致 (只取导入需要的字段)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProfile {
+    pub target_table: Option<String>,
+    pub db: Option<String>,
+    pub header_row_idx: usize,
+    pub mappings: Vec<ColumnMapping>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    pub batch_size: Option<usize>,
+    // 只做转换与列校验，不写入数据库
+    #[serde(default)]
+    pub dry_run: bool,
+    // 出现任意拒绝行即整体回滚
+    #[serde(default)]
+    pub stop_on_error: bool,
+    #[serde(default)]
+    pub mode: InsertMode,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub total_rows: usize,
+    pub inserted: u64,
+    pub rejected: usize,
+    pub batches: usize,
+    pub dry_run: bool,
+    pub committed: bool,
+    pub elapsed_ms: u128,
+    pub rejections: Vec<RowRejection>,
+}
+
+impl ImportReport {
+    fn reject(&mut self, row: usize, column: String, reason: String) {
+        self.rejected += 1;
+        if self.rejections.len() < MAX_REJECTIONS {
+            self.rejections.push(RowRejection { row, column, reason });
+        }
+    }
+}
+
+// 生成带占位符的批量语句
+fn insert_statement(table: &str, columns: &[String], keys: &[String], rows: usize, mode: InsertMode) -> String {
+    let q = |n: &str| Dialect::MySql.quote_ident(n);
+    let placeholders = format!("({})", vec!["?"; columns.len()].join(", "));
+    let mut sql = format!(
+        "{} {} ({}) VALUES {}",
+        if mode == InsertMode::Ignore { "INSERT IGNORE INTO" } else { "INSERT INTO" },
+        q(table),
+        columns.iter().map(|c| q(c)).collect::<Vec<_>>().join(", "),
+        vec![placeholders; rows].join(", ")
+    );
+    let updates: Vec<String> =
+        columns.iter().filter(|c| !keys.contains(c)).map(|c| format!("{0} = VALUES({0})", q(c))).collect();
+    if mode == InsertMode::Upsert && !updates.is_empty() {
+        sql.push_str(&format!(" ON DUPLICATE KEY UPDATE {}", updates.join(", ")));
+    }
+    sql
+}
+
+fn flatten(rows: &[(usize, Vec<SqlValue>)]) -> Vec<mysql::Value> {
+    rows.iter().flat_map(|(_, values)| values.iter().map(SqlValue::to_mysql)).collect()
+}
+
+// 服务端错误信息中的列名 (如 Data too long for column 'x')，没有时取错误码
+fn rejected_column(e: &mysql::Error) -> String {
+    let mysql::Error::MySqlError(server) = e else {
+        return String::new();
+    };
+    let message = server.message.as_str();
+    message
+        .to_ascii_lowercase()
+        .find("column '")
+        .and_then(|start| {
+            let rest = &message[start + "column '".len()..];
+            rest.find('\'').map(|end| rest[..end].to_string())
+        })
+        .unwrap_or_else(|| format!("#{}", server.code))
+}
+
+// 写入一个批次；批量失败时逐行重试以定位具体的错误行
+fn write_batch(
+    tx: &mut mysql::Transaction,
+    table: &str,
+    mapper: &RowMapper,
+    mode: InsertMode,
+    batch: &[(usize, Vec<SqlValue>)],
+    report: &mut ImportReport,
+) {
+    let sql = insert_statement(table, &mapper.columns, &mapper.keys, batch.len(), mode);
+    report.batches += 1;
+    if tx.exec_drop(&sql, flatten(batch)).is_ok() {
+        report.inserted += batch.len() as u64;
+        return;
+    }
+
+    let single = insert_statement(table, &mapper.columns, &mapper.keys, 1, mode);
+    for row in batch {
+        match tx.exec_drop(&single, flatten(std::slice::from_ref(row))) {
+            Ok(_) => report.inserted += 1,
+            Err(e) => report.reject(row.0, rejected_column(&e), e.to_string()),
+        }
+    }
+}
+
+// 校验映射列在目标表中存在
+fn check_columns(conn: &mut mysql::Conn, db: &str, table: &str, columns: &[String]) -> Result<(), ExcelError> {
+    let existing: Vec<String> = conn
+        .exec(
+            "SELECT COLUMN_NAME FROM information_schema.COLUMNS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+            (db, table),
+        )
+        .map_err(|e| ExcelError::Database(e.to_string()))?;
+    if existing.is_empty() {
+        return Err(ExcelError::InvalidMapping(format!("table {}.{} not found", db, table)));
+    }
+    let missing: Vec<&str> = columns
+        .iter()
+        .filter(|c| !existing.iter().any(|e| e.eq_ignore_ascii_case(c)))
+        .map(|c| c.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(ExcelError::InvalidMapping(format!("columns not in {}: {}", table, missing.join(", "))));
+    }
+    Ok(())
+}
+
+pub fn run_import(
+    conn: &mut mysql::Conn,
+    file_name: &str,
+    sheet_name: &str,
+    profile: &ImportProfile,
+    opts: &ImportOptions,
+) -> Result<ImportReport, ExcelError> {
+    let started = Instant::now();
+    let table = profile
+        .target_table
+        .as_deref()
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| ExcelError::InvalidMapping("target table is not set".to_string()))?;
+    let db = profile
+        .db
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| ExcelError::InvalidMapping("target database is not set".to_string()))?;
+
+    let header_row = profile.header_row_idx.max(1) - 1;
+    let range = read_sheet(file_name, sheet_name)?;
+    let headers = sheet_headers(&range, header_row);
+    let mapper = RowMapper::new(&headers, &profile.mappings)?;
+
+    check_columns(conn, db, table, &mapper.columns)?;
+
+    let mut report = ImportReport {
+        total_rows: 0,
+        inserted: 0,
+        rejected: 0,
+        batches: 0,
+        dry_run: opts.dry_run,
+        committed: false,
+        elapsed_ms: 0,
+        rejections: Vec::new(),
+    };
+
+    // 逐行转换，凑满一批即写入，转换失败的行不进入写入批次
+    // stopOnError 时遇到第一条拒绝即停止并回滚，totalRows 只统计到停止处
+    let mut tx = match opts.dry_run {
+        true => None,
+        false => Some(conn.start_transaction(mysql::TxOpts::default()).map_err(|e| ExcelError::Database(e.to_string()))?),
+    };
+    let batch_size = opts.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    let mut batch: Vec<(usize, Vec<SqlValue>)> = Vec::with_capacity(batch_size);
+    let stopped = |report: &ImportReport| opts.stop_on_error && report.rejected > 0;
+    for (row_no, row) in data_rows(&range, header_row) {
+        report.total_rows += 1;
+        match mapper.map(row) {
+            Ok(values) if tx.is_some() => batch.push((row_no, values)),
+            Ok(_) => {}
+            Err((column, reason)) => report.reject(row_no, column, reason),
+        }
+        let Some(tx) = tx.as_mut() else {
+            continue;
+        };
+        if stopped(&report) {
+            break;
+        }
+        if batch.len() >= batch_size {
+            write_batch(tx, table, &mapper, opts.mode, &batch, &mut report);
+            batch.clear();
+        }
+    }
+
+    let Some(mut tx) = tx else {
+        report.elapsed_ms = started.elapsed().as_millis();
+        return Ok(report);
+    };
+    if !batch.is_empty() && !stopped(&report) {
+        write_batch(&mut tx, table, &mapper, opts.mode, &batch, &mut report);
+    }
+
+    if stopped(&report) {
+        tx.rollback().map_err(|e| ExcelError::Database(e.to_string()))?;
+        report.inserted = 0;
+    } else {
+        tx.commit().map_err(|e| ExcelError::Database(e.to_string()))?;
+        report.committed = true;
+    }
+
+    report.elapsed_ms = started.elapsed().as_millis();
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn excel_import_to_db(
+    state: State<'_, AppState>,
+    id: String,
+    file_name: String,
+    sheet_name: String,
+    profile: ImportProfile,
+    options: Option<ImportOptions>,
+) -> Result<ImportReport, ExcelError> {
+    let mut conn = state.pools.get(&state.connections, &id, profile.db.as_deref()).map_err(ExcelError::Database)?;
+    // 读取工作簿与写入都会阻塞
+    driver::blocking(move || Ok(run_import(conn.as_mut(), &file_name, &sheet_name, &profile, &options.unwrap_or_default())))
+        .await
+        .map_err(ExcelError::Database)?
+}
@@ -0,0 +1,320 @@
+// 根据列映射将 Excel 行生成批量 INSERT / UPSERT 语句，直接写入 .sql 文件
+
+use super::value::{convert_cell, convert_text, SqlValue, TargetType};
+use super::{cell_to_string, read_sheet, sheet_headers, ColumnMapping, ExcelError, RowRejection};
+use crate::sql::Dialect;
+use calamine::{Data, Range};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+// 返回给前端的拒绝记录上限
+pub const MAX_REJECTIONS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InsertMode {
+    #[default]
+    Insert,
+    // 主键冲突时更新 (MySQL: ON DUPLICATE KEY UPDATE, PostgreSQL/SQLite: ON CONFLICT DO UPDATE)
+    // Doris 只有 UNIQUE KEY 模型的表按 Key 覆盖，DUPLICATE/AGGREGATE 模型会追加或聚合
+    Upsert,
+    // 主键冲突时跳过 (Doris 不支持)
+    Ignore,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertRequest {
+    pub file_name: String,
+    pub sheet_name: String,
+    pub table_name: String,
+    pub db_type: String,
+    // 表头所在行 (从 1 开始)
+    pub header_row_idx: Option<usize>,
+    pub mappings: Vec<ColumnMapping>,
+    #[serde(default)]
+    pub mode: InsertMode,
+    pub batch_size: Option<usize>,
+    pub output_path: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InsertSummary {
+    pub path: String,
+    pub rows: usize,
+    pub statements: usize,
+    pub bytes: u64,
+    pub rejected: usize,
+    pub rejections: Vec<RowRejection>,
+}
+
+// 单列的取值来源
+enum Source {
+    Column(usize),
+    Constant(SqlValue),
+}
+
+// 按映射把 Excel 行转换为目标列值
+pub struct RowMapper {
+    pub columns: Vec<String>,
+    pub keys: Vec<String>,
+    plan: Vec<(Source, TargetType)>,
+}
+
+impl RowMapper {
+    // 未配置表头与固定值的映射会被忽略 (与前端行为一致)
+    pub fn new(headers: &[String], mappings: &[ColumnMapping]) -> Result<RowMapper, ExcelError> {
+        let mut columns = Vec::new();
+        let mut keys = Vec::new();
+        let mut plan = Vec::new();
+
+        for m in mappings {
+            let custom = m.custom_value.as_deref().filter(|v| !v.is_empty());
+            if m.excel_header.is_empty() && custom.is_none() {
+                continue;
+            }
+            let target = TargetType::parse(&m.db_type);
+            let source = match custom {
+                Some(v) => Source::Constant(
+                    convert_text(v, &target).map_err(|e| ExcelError::InvalidMapping(format!("{}: {}", m.db_column, e)))?,
+                ),
+                None => {
+                    let idx = headers
+                        .iter()
+                        .position(|h| h == &m.excel_header)
+                        .or_else(|| headers.iter().position(|h| h.eq_ignore_ascii_case(&m.excel_header)))
+                        .ok_or_else(|| {
+                            ExcelError::InvalidMapping(format!("{}: header '{}' not found", m.db_column, m.excel_header))
+                        })?;
+                    Source::Column(idx)
+                }
+            };
+            columns.push(m.db_column.clone());
+            if m.is_pk {
+                keys.push(m.db_column.clone());
+            }
+            plan.push((source, target));
+        }
+
+        if columns.is_empty() {
+            return Err(ExcelError::InvalidMapping("no mapped columns".to_string()));
+        }
+        Ok(RowMapper { columns, keys, plan })
+    }
+
+    // 转换一行，失败时返回 (列名, 原因)
+    pub fn map(&self, row: &[Data]) -> Result<Vec<SqlValue>, (String, String)> {
+        self.plan
+            .iter()
+            .zip(&self.columns)
+            .map(|((source, target), column)| match source {
+                Source::Constant(v) => Ok(v.clone()),
+                Source::Column(i) => {
+                    convert_cell(row.get(*i).unwrap_or(&Data::Empty), target).map_err(|e| (column.clone(), e))
+                }
+            })
+            .collect()
+    }
+}
+
+// 表头之后的数据行，附带 Excel 实际行号，跳过空行
+pub fn data_rows(range: &Range<Data>, header_row: usize) -> impl Iterator<Item = (usize, &[Data])> {
+    let start = range.start().map(|(r, _)| r as usize).unwrap_or(0);
+    range
+        .rows()
+        .enumerate()
+        .skip(header_row + 1)
+        .filter(|(_, row)| row.iter().any(|c| !cell_to_string(c).is_empty()))
+        .map(move |(i, row)| (start + i + 1, row))
+}
+
+// 校验写入模式在目标库上可用
+fn check_mode(dialect: Dialect, mode: InsertMode, keys: &[String]) -> Result<(), ExcelError> {
+    match (mode, dialect) {
+        (InsertMode::Ignore, Dialect::Doris) => {
+            Err(ExcelError::Unsupported("Doris has no INSERT IGNORE; use upsert on a UNIQUE KEY table".to_string()))
+        }
+        // ON CONFLICT 需要指定冲突列
+        (InsertMode::Upsert | InsertMode::Ignore, Dialect::Postgres | Dialect::Sqlite) if keys.is_empty() => {
+            Err(ExcelError::InvalidMapping("upsert requires at least one primary key mapping".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+// 生成单条批量语句
+pub fn render_insert(
+    dialect: Dialect,
+    table: &str,
+    columns: &[String],
+    keys: &[String],
+    rows: &[Vec<SqlValue>],
+    mode: InsertMode,
+) -> Result<String, ExcelError> {
+    check_mode(dialect, mode, keys)?;
+    let q = |n: &str| dialect.quote_ident(n);
+    let col_list = columns.iter().map(|c| q(c)).collect::<Vec<_>>().join(", ");
+    let values = rows
+        .iter()
+        .map(|r| format!("({})", r.iter().map(|v| v.to_sql(dialect)).collect::<Vec<_>>().join(", ")))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    let updates: Vec<&String> = columns.iter().filter(|c| !keys.contains(c)).collect();
+
+    let verb = match (mode, dialect) {
+        (InsertMode::Ignore, Dialect::MySql) => "INSERT IGNORE INTO",
+        _ => "INSERT INTO",
+    };
+    let mut sql = format!("{} {} ({}) VALUES\n{}", verb, q(table), col_list, values);
+
+    match (mode, dialect) {
+        (InsertMode::Upsert, Dialect::MySql) if !updates.is_empty() => {
+            let set = updates.iter().map(|c| format!("{0} = VALUES({0})", q(c))).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!("\nON DUPLICATE KEY UPDATE {}", set));
+        }
+        (InsertMode::Upsert, Dialect::Postgres | Dialect::Sqlite) if !updates.is_empty() => {
+            let set = updates.iter().map(|c| format!("{0} = EXCLUDED.{0}", q(c))).collect::<Vec<_>>().join(", ");
+            let key_list = keys.iter().map(|k| q(k)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!("\nON CONFLICT ({}) DO UPDATE SET {}", key_list, set));
+        }
+        (InsertMode::Upsert | InsertMode::Ignore, Dialect::Postgres | Dialect::Sqlite) => {
+            let key_list = keys.iter().map(|k| q(k)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!("\nON CONFLICT ({}) DO NOTHING", key_list));
+        }
+        // Doris UNIQUE KEY 模型按 Key 覆盖写入，普通 INSERT 即为 UPSERT
+        _ => {}
+    }
+    sql.push_str(";\n");
+    Ok(sql)
+}
+
+// 生成 INSERT 文件
+pub fn write_inserts(req: &InsertRequest) -> Result<InsertSummary, ExcelError> {
+    let dialect =
+        Dialect::parse(&req.db_type).ok_or_else(|| ExcelError::Unsupported(format!("database type {}", req.db_type)))?;
+    let header_row = req.header_row_idx.unwrap_or(1).saturating_sub(1);
+    let range = read_sheet(&req.file_name, &req.sheet_name)?;
+    let headers = sheet_headers(&range, header_row);
+    let mapper = RowMapper::new(&headers, &req.mappings)?;
+
+    check_mode(dialect, req.mode, &mapper.keys)?;
+
+    let batch_size = req.batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1);
+    let file = File::create(&req.output_path)?;
+    let mut out = BufWriter::new(file);
+    writeln!(
+        out,
+        "-- Import to {}\n-- Source: {} [{}]\n-- Generated at {}\n",
+        req.table_name,
+        req.file_name,
+        req.sheet_name,
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+    )?;
+
+    let mut summary = InsertSummary {
+        path: req.output_path.clone(),
+        rows: 0,
+        statements: 0,
+        bytes: 0,
+        rejected: 0,
+        rejections: Vec::new(),
+    };
+    let mut batch: Vec<Vec<SqlValue>> = Vec::with_capacity(batch_size);
+
+    for (row_no, row) in data_rows(&range, header_row) {
+        match mapper.map(row) {
+            Ok(values) => batch.push(values),
+            Err((column, reason)) => {
+                summary.rejected += 1;
+                if summary.rejections.len() < MAX_REJECTIONS {
+                    summary.rejections.push(RowRejection { row: row_no, column, reason });
+                }
+                continue;
+            }
+        }
+        if batch.len() >= batch_size {
+            out.write_all(render_insert(dialect, &req.table_name, &mapper.columns, &mapper.keys, &batch, req.mode)?.as_bytes())?;
+            summary.rows += batch.len();
+            summary.statements += 1;
+            batch.clear();
+        }
+    }
+    if !batch.is_empty() {
+        out.write_all(render_insert(dialect, &req.table_name, &mapper.columns, &mapper.keys, &batch, req.mode)?.as_bytes())?;
+        summary.rows += batch.len();
+        summary.statements += 1;
+    }
+
+    out.flush()?;
+    summary.bytes = std::fs::metadata(&req.output_path)?.len();
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn generate_excel_inserts(request: InsertRequest) -> Result<InsertSummary, ExcelError> {
+    write_inserts(&request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn renders_insert_modes_per_dialect() {
+        let columns = names(&["id", "name"]);
+        let keys = names(&["id"]);
+        let rows = vec![vec![SqlValue::Int(1), SqlValue::Str("it's".to_string())], vec![SqlValue::Int(2), SqlValue::Null]];
+        let cases = [
+            (Dialect::MySql, InsertMode::Insert, "INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'it''s'),\n(2, NULL);\n"),
+            (Dialect::MySql, InsertMode::Ignore, "INSERT IGNORE INTO `t` (`id`, `name`) VALUES\n(1, 'it''s'),\n(2, NULL);\n"),
+            (
+                Dialect::MySql,
+                InsertMode::Upsert,
+                "INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'it''s'),\n(2, NULL)\nON DUPLICATE KEY UPDATE `name` = VALUES(`name`);\n",
+            ),
+            (
+                Dialect::Postgres,
+                InsertMode::Upsert,
+                "INSERT INTO \"t\" (\"id\", \"name\") VALUES\n(1, 'it''s'),\n(2, NULL)\nON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\";\n",
+            ),
+            (Dialect::Postgres, InsertMode::Ignore, "INSERT INTO \"t\" (\"id\", \"name\") VALUES\n(1, 'it''s'),\n(2, NULL)\nON CONFLICT (\"id\") DO NOTHING;\n"),
+            (Dialect::Sqlite, InsertMode::Ignore, "INSERT INTO \"t\" (\"id\", \"name\") VALUES\n(1, 'it''s'),\n(2, NULL)\nON CONFLICT (\"id\") DO NOTHING;\n"),
+            (Dialect::Doris, InsertMode::Upsert, "INSERT INTO `t` (`id`, `name`) VALUES\n(1, 'it''s'),\n(2, NULL);\n"),
+        ];
+        for (dialect, mode, expected) in cases {
+            assert_eq!(render_insert(dialect, "t", &columns, &keys, &rows, mode).unwrap(), expected, "{:?} {:?}", dialect, mode);
+        }
+    }
+
+    #[test]
+    fn postgres_upsert_of_key_columns_only_does_nothing() {
+        let columns = names(&["id"]);
+        let sql = render_insert(Dialect::Postgres, "t", &columns, &columns, &[vec![SqlValue::Int(1)]], InsertMode::Upsert).unwrap();
+        assert_eq!(sql, "INSERT INTO \"t\" (\"id\") VALUES\n(1)\nON CONFLICT (\"id\") DO NOTHING;\n");
+    }
+
+    #[test]
+    fn rejects_unsupported_modes() {
+        let columns = names(&["id", "name"]);
+        let rows = vec![vec![SqlValue::Int(1), SqlValue::Null]];
+        let cases = [
+            (Dialect::Doris, InsertMode::Ignore, names(&["id"])),
+            (Dialect::Postgres, InsertMode::Upsert, Vec::new()),
+            (Dialect::Sqlite, InsertMode::Ignore, Vec::new()),
+        ];
+        for (dialect, mode, keys) in cases {
+            assert!(render_insert(dialect, "t", &columns, &keys, &rows, mode).is_err(), "{:?} {:?}", dialect, mode);
+        }
+        assert!(matches!(
+            render_insert(Dialect::Doris, "t", &columns, &[], &rows, InsertMode::Ignore),
+            Err(ExcelError::Unsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+// Excel 工作簿读取 (基于 calamine)
+
+use calamine::{open_workbook_auto, Data, Range, Reader, Sheets};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+pub mod ddl;
+pub mod import;
+pub mod insert;
+pub mod value;
+
+// 预览行数上限
+const PREVIEW_ROWS: usize = 5;
+
+// Excel 相关错误 (前端根据 kind 区分提示)
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum ExcelError {
+    NotFound(String),
+    Locked(String),
+    Unsupported(String),
+    Corrupt(String),
+    SheetNotFound(String),
+    InvalidMapping(String),
+    Database(String),
+    Io(String),
+}
+
+impl std::fmt::Display for ExcelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExcelError::NotFound(m) => write!(f, "File not found: {}", m),
+            ExcelError::Locked(m) => write!(f, "File is locked: {}", m),
+            ExcelError::Unsupported(m) => write!(f, "Unsupported file: {}", m),
+            ExcelError::Corrupt(m) => write!(f, "Corrupt workbook: {}", m),
+            ExcelError::SheetNotFound(m) => write!(f, "Sheet not found: {}", m),
+            ExcelError::InvalidMapping(m) => write!(f, "Invalid column mapping: {}", m),
+            ExcelError::Database(m) => write!(f, "Database error: {}", m),
+            ExcelError::Io(m) => write!(f, "IO error: {}", m),
+        }
+    }
+}
+
+impl From<std::io::Error> for ExcelError {
+    fn from(e: std::io::Error) -> Self {
+        ExcelError::Io(e.to_string())
+    }
+}
+
+// 工作表概要信息
+#[derive(Debug, Serialize)]
+pub struct SheetInfo {
+    pub name: String,
+    pub rows: usize,
+    pub cols: usize,
+    pub headers: Vec<String>,
+    pub preview: Vec<Vec<String>>,
+}
+
+// 列映射 (与前端 ExcelImport 的 ColumnMapping 一致)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnMapping {
+    pub db_column: String,
+    pub db_type: String,
+    #[serde(default)]
+    pub excel_header: String,
+    #[serde(default)]
+    pub is_pk: bool,
+    pub comment: Option<String>,
+    pub custom_value: Option<String>,
+}
+
+// 单行拒绝记录 (row 为 Excel 中的实际行号，从 1 开始)
+#[derive(Debug, Clone, Serialize)]
+pub struct RowRejection {
+    pub row: usize,
+    pub column: String,
+    pub reason: String,
+}
+
+// 将 IO 错误归类：文件被占用 (Windows 共享冲突 32/33) 或无权限视为 Locked
+fn classify_io_error(path: &str, e: &std::io::Error) -> ExcelError {
+    match (e.kind(), e.raw_os_error()) {
+        (std::io::ErrorKind::NotFound, _) => ExcelError::NotFound(path.to_string()),
+        (std::io::ErrorKind::PermissionDenied, _) | (_, Some(32)) | (_, Some(33)) => {
+            ExcelError::Locked(format!("{} ({})", path, e))
+        }
+        _ => ExcelError::Io(format!("{}: {}", path, e)),
+    }
+}
+
+// 打开工作簿，先做文件级检查以便给出准确的错误类型
+pub fn open_workbook(path: &str) -> Result<Sheets<BufReader<File>>, ExcelError> {
+    let p = Path::new(path);
+    if !p.exists() {
+        return Err(ExcelError::NotFound(path.to_string()));
+    }
+
+    let ext = p
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !matches!(ext.as_str(), "xlsx" | "xlsm" | "xls" | "xlsb" | "ods") {
+        return Err(ExcelError::Unsupported(format!("{} (.{})", path, ext)));
+    }
+
+    // 显式打开一次，捕获被 Excel 占用等情况
+    File::open(p).map_err(|e| classify_io_error(path, &e))?;
+
+    open_workbook_auto(p).map_err(|e| match e {
+        calamine::Error::Io(io) => classify_io_error(path, &io),
+        other => ExcelError::Corrupt(format!("{}: {}", path, other)),
+    })
+}
+
+// 读取指定工作表的数据区域
+pub fn read_sheet(path: &str, sheet: &str) -> Result<Range<Data>, ExcelError> {
+    let mut workbook = open_workbook(path)?;
+    if !workbook.sheet_names().iter().any(|s| s == sheet) {
+        return Err(ExcelError::SheetNotFound(sheet.to_string()));
+    }
+    workbook
+        .worksheet_range(sheet)
+        .map_err(|e| ExcelError::Corrupt(format!("{}: {}", sheet, e)))
+}
+
+// 单元格转字符串 (日期统一为 ISO 格式，整数浮点去掉小数部分)
+pub fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.trim().to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Float(f) => {
+            if f.fract() == 0.0 && f.abs() < 1e15 {
+                format!("{}", *f as i64)
+            } else {
+                f.to_string()
+            }
+        }
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(dt) => {
+            if dt.is_duration() {
+                let secs = dt.as_duration().map(|d| d.num_seconds()).unwrap_or(0);
+                format!("{:02}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+            } else {
+                match dt.as_datetime() {
+                    Some(v) if v.time() == chrono::NaiveTime::MIN => v.format("%Y-%m-%d").to_string(),
+                    Some(v) => v.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    None => dt.as_f64().to_string(),
+                }
+            }
+        }
+        Data::DateTimeIso(s) => s.replace('T', " "),
+        Data::DurationIso(s) => s.clone(),
+        Data::Error(e) => format!("{}", e),
+    }
+}
+
+// 读取表头行 (header_row 为 0 起始的相对行号)，空表头用列序号补齐
+pub fn sheet_headers(range: &Range<Data>, header_row: usize) -> Vec<String> {
+    let width = range.width();
+    let row = range.rows().nth(header_row);
+    (0..width)
+        .map(|i| {
+            let name = row.and_then(|r| r.get(i)).map(cell_to_string).unwrap_or_default();
+            if name.is_empty() {
+                format!("column_{}", i + 1)
+            } else {
+                name
+            }
+        })
+        .collect()
+}
+
+// 解析工作簿中的所有工作表
+pub fn list_sheets(path: &str) -> Result<Vec<SheetInfo>, ExcelError> {
+    let mut workbook = open_workbook(path)?;
+    let mut sheets = Vec::new();
+
+    for name in workbook.sheet_names() {
+        let range = workbook
+            .worksheet_range(&name)
+            .map_err(|e| ExcelError::Corrupt(format!("{}: {}", name, e)))?;
+        let (rows, cols) = range.get_size();
+        let headers = if range.is_empty() { Vec::new() } else { sheet_headers(&range, 0) };
+        let preview = range
+            .rows()
+            .skip(1)
+            .take(PREVIEW_ROWS)
+            .map(|r| r.iter().map(cell_to_string).collect())
+            .collect();
+
+        sheets.push(SheetInfo { name, rows, cols, headers, preview });
+    }
+
+    Ok(sheets)
+}
+
+#[tauri::command]
+pub fn parse_excel_sheets(file_name: String) -> Result<Vec<SheetInfo>, ExcelError> {
+    list_sheets(&file_name)
+}
@@ -0,0 +1,250 @@
+// 单元格按目标列类型转换为 SQL 值
+
+use super::cell_to_string;
+use super::ddl::{DATETIME_FORMATS, DATE_FORMATS};
+use crate::sql::Dialect;
+use calamine::Data;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+// 转换后的值
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Int(i64),
+    // 数值保留原始十进制文本，避免 DECIMAL 精度丢失
+    Number(String),
+    Bool(bool),
+    Str(String),
+    // 二进制列 (BLOB/bytea) 的原始字节
+    Bytes(Vec<u8>),
+}
+
+impl SqlValue {
+    // 渲染为 SQL 字面量
+    pub fn to_sql(&self, dialect: Dialect) -> String {
+        match self {
+            SqlValue::Null => "NULL".to_string(),
+            SqlValue::Int(i) => i.to_string(),
+            SqlValue::Number(n) => n.clone(),
+            SqlValue::Bool(b) => match dialect {
+                Dialect::Postgres => if *b { "TRUE" } else { "FALSE" }.to_string(),
+                _ => if *b { "1" } else { "0" }.to_string(),
+            },
+            SqlValue::Str(s) => dialect.quote_str(s),
+            SqlValue::Bytes(bytes) => {
+                let hex: String = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                match dialect {
+                    // 0x 字面量不能为空
+                    Dialect::MySql | Dialect::Doris if bytes.is_empty() => "''".to_string(),
+                    Dialect::MySql | Dialect::Doris => format!("0x{}", hex),
+                    Dialect::Postgres => format!("'\\x{}'::bytea", hex),
+                    Dialect::Sqlite => format!("X'{}'", hex),
+                }
+            }
+        }
+    }
+
+    // 转为 mysql 绑定参数
+    pub fn to_mysql(&self) -> mysql::Value {
+        match self {
+            SqlValue::Null => mysql::Value::NULL,
+            SqlValue::Int(i) => mysql::Value::Int(*i),
+            SqlValue::Number(n) => mysql::Value::Bytes(n.clone().into_bytes()),
+            SqlValue::Bool(b) => mysql::Value::Int(*b as i64),
+            SqlValue::Str(s) => mysql::Value::Bytes(s.clone().into_bytes()),
+            SqlValue::Bytes(bytes) => mysql::Value::Bytes(bytes.clone()),
+        }
+    }
+}
+
+// 目标列类型分类 (来自 information_schema 的类型字符串，如 varchar(50)、decimal(10,2))
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetType {
+    Integer,
+    Boolean,
+    Decimal,
+    Float,
+    Date,
+    DateTime,
+    Time,
+    Text(Option<usize>),
+}
+
+impl TargetType {
+    pub fn parse(db_type: &str) -> TargetType {
+        let lower = db_type.trim().to_lowercase();
+        let base = lower.split(['(', ' ']).next().unwrap_or_default();
+        let arg: Option<usize> = lower
+            .split_once('(')
+            .and_then(|(_, rest)| rest.split([')', ',']).next())
+            .and_then(|n| n.trim().parse().ok());
+
+        match base {
+            "tinyint" if arg == Some(1) => TargetType::Boolean,
+            "bool" | "boolean" => TargetType::Boolean,
+            "tinyint" | "smallint" | "mediumint" | "int" | "integer" | "bigint" | "largeint" | "int2" | "int4"
+            | "int8" | "serial" | "bigserial" | "year" | "bit" => TargetType::Integer,
+            "decimal" | "numeric" | "decimalv3" => TargetType::Decimal,
+            "float" | "double" | "real" | "float4" | "float8" => TargetType::Float,
+            "date" | "datev2" => TargetType::Date,
+            "datetime" | "datetimev2" | "timestamp" | "timestamptz" => TargetType::DateTime,
+            "time" => TargetType::Time,
+            "char" | "varchar" | "character" => TargetType::Text(arg),
+            _ => TargetType::Text(None),
+        }
+    }
+}
+
+fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" | "是" | "t" => Some(true),
+        "0" | "false" | "no" | "n" | "否" | "f" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_datetime(s: &str) -> Option<NaiveDateTime> {
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|f| NaiveDate::parse_from_str(s, f).ok())
+                .map(|d| d.and_time(NaiveTime::MIN))
+        })
+}
+
+// 文本转换：失败时返回原因，由调用方记录到拒绝报告
+pub fn convert_text(text: &str, target: &TargetType) -> Result<SqlValue, String> {
+    if text.is_empty() {
+        return Ok(SqlValue::Null);
+    }
+    match target {
+        TargetType::Integer => {
+            let cleaned = text.replace(',', "");
+            if let Ok(i) = cleaned.parse::<i64>() {
+                return Ok(SqlValue::Int(i));
+            }
+            match cleaned.parse::<f64>() {
+                Ok(f) if f.fract() == 0.0 && f.abs() < 9.0e15 => Ok(SqlValue::Int(f as i64)),
+                _ => parse_bool(text)
+                    .map(|b| SqlValue::Int(b as i64))
+                    .ok_or_else(|| format!("'{}' is not an integer", text)),
+            }
+        }
+        TargetType::Boolean => parse_bool(text)
+            .map(SqlValue::Bool)
+            .ok_or_else(|| format!("'{}' is not a boolean", text)),
+        TargetType::Decimal | TargetType::Float => {
+            let cleaned = text.replace(',', "");
+            match cleaned.parse::<f64>() {
+                Ok(f) if f.is_finite() => Ok(SqlValue::Number(cleaned)),
+                _ => Err(format!("'{}' is not a number", text)),
+            }
+        }
+        TargetType::Date => parse_datetime(text)
+            .map(|d| SqlValue::Str(d.format("%Y-%m-%d").to_string()))
+            .ok_or_else(|| format!("'{}' is not a date", text)),
+        TargetType::DateTime => parse_datetime(text)
+            .map(|d| SqlValue::Str(d.format("%Y-%m-%d %H:%M:%S").to_string()))
+            .ok_or_else(|| format!("'{}' is not a datetime", text)),
+        TargetType::Time => NaiveTime::parse_from_str(text, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(text, "%H:%M"))
+            .map(|t| SqlValue::Str(t.format("%H:%M:%S").to_string()))
+            .map_err(|_| format!("'{}' is not a time", text)),
+        TargetType::Text(Some(max)) if text.chars().count() > *max => {
+            Err(format!("value length {} exceeds {}", text.chars().count(), max))
+        }
+        TargetType::Text(_) => Ok(SqlValue::Str(text.to_string())),
+    }
+}
+
+// 单元格转换 (数值单元格直接取值，其余按文本处理)
+pub fn convert_cell(cell: &Data, target: &TargetType) -> Result<SqlValue, String> {
+    match (cell, target) {
+        (Data::Empty, _) => Ok(SqlValue::Null),
+        (Data::Bool(b), TargetType::Boolean) => Ok(SqlValue::Bool(*b)),
+        (Data::Bool(b), TargetType::Integer) => Ok(SqlValue::Int(*b as i64)),
+        (Data::Int(i), TargetType::Integer) => Ok(SqlValue::Int(*i)),
+        (Data::Float(f), TargetType::Integer) if f.fract() != 0.0 => Err(format!("{} is not an integer", f)),
+        (Data::Float(f), TargetType::Float) if f.is_finite() => Ok(SqlValue::Number(f.to_string())),
+        (Data::Error(e), _) => Err(format!("cell error {}", e)),
+        _ => convert_text(&cell_to_string(cell), target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_literals_per_dialect() {
+        let cases = [
+            (SqlValue::Str("it's".to_string()), Dialect::MySql, "'it''s'"),
+            (SqlValue::Str("it's".to_string()), Dialect::Postgres, "'it''s'"),
+            (SqlValue::Str("a\\b".to_string()), Dialect::MySql, "'a\\\\b'"),
+            (SqlValue::Str("a\\b".to_string()), Dialect::Doris, "'a\\\\b'"),
+            (SqlValue::Str("a\\b".to_string()), Dialect::Postgres, "'a\\b'"),
+            (SqlValue::Str("a\0b".to_string()), Dialect::MySql, "'a\\0b'"),
+            (SqlValue::Str("a\0b".to_string()), Dialect::Postgres, "'ab'"),
+            (SqlValue::Str("a\nb".to_string()), Dialect::MySql, "'a\\nb'"),
+            (SqlValue::Str("a\nb".to_string()), Dialect::Sqlite, "'a\nb'"),
+            (SqlValue::Number("1234.50".to_string()), Dialect::MySql, "1234.50"),
+            (SqlValue::Int(-7), Dialect::Postgres, "-7"),
+            (SqlValue::Bool(true), Dialect::Postgres, "TRUE"),
+            (SqlValue::Bool(true), Dialect::MySql, "1"),
+            (SqlValue::Null, Dialect::Sqlite, "NULL"),
+            (SqlValue::Bytes(vec![0, 0xAB]), Dialect::MySql, "0x00AB"),
+            (SqlValue::Bytes(Vec::new()), Dialect::MySql, "''"),
+            (SqlValue::Bytes(vec![0xAB]), Dialect::Postgres, "'\\xAB'::bytea"),
+            (SqlValue::Bytes(vec![0xAB]), Dialect::Sqlite, "X'AB'"),
+        ];
+        for (value, dialect, expected) in cases {
+            assert_eq!(value.to_sql(dialect), expected, "{:?} {:?}", value, dialect);
+        }
+    }
+
+    #[test]
+    fn converts_text_by_target_type() {
+        let cases = [
+            ("", TargetType::Integer, Ok(SqlValue::Null)),
+            ("1,234", TargetType::Integer, Ok(SqlValue::Int(1234))),
+            ("12.0", TargetType::Integer, Ok(SqlValue::Int(12))),
+            ("yes", TargetType::Integer, Ok(SqlValue::Int(1))),
+            ("12.5", TargetType::Integer, Err(())),
+            ("否", TargetType::Boolean, Ok(SqlValue::Bool(false))),
+            ("maybe", TargetType::Boolean, Err(())),
+            // 数值保留原始文本 (去掉千分位)
+            ("1,234.500", TargetType::Decimal, Ok(SqlValue::Number("1234.500".to_string()))),
+            ("1e3", TargetType::Float, Ok(SqlValue::Number("1e3".to_string()))),
+            ("1); DROP TABLE t", TargetType::Decimal, Err(())),
+            ("NaN", TargetType::Float, Err(())),
+            ("inf", TargetType::Decimal, Err(())),
+            ("2024/01/02", TargetType::Date, Ok(SqlValue::Str("2024-01-02".to_string()))),
+            ("2024-01-02", TargetType::DateTime, Ok(SqlValue::Str("2024-01-02 00:00:00".to_string()))),
+            ("9:05", TargetType::Time, Ok(SqlValue::Str("09:05:00".to_string()))),
+            ("it's", TargetType::Text(Some(4)), Ok(SqlValue::Str("it's".to_string()))),
+            ("toolong", TargetType::Text(Some(4)), Err(())),
+            ("a\\b\0", TargetType::Text(None), Ok(SqlValue::Str("a\\b\0".to_string()))),
+        ];
+        for (text, target, expected) in cases {
+            assert_eq!(convert_text(text, &target).map_err(|_| ()), expected, "{:?} as {:?}", text, target);
+        }
+    }
+
+    #[test]
+    fn parses_target_types() {
+        let cases = [
+            ("tinyint(1)", TargetType::Boolean),
+            ("BIGINT UNSIGNED", TargetType::Integer),
+            ("decimal(10,2)", TargetType::Decimal),
+            ("varchar(50)", TargetType::Text(Some(50))),
+            ("datetimev2(3)", TargetType::DateTime),
+            ("jsonb", TargetType::Text(None)),
+        ];
+        for (db_type, expected) in cases {
+            assert_eq!(TargetType::parse(db_type), expected, "{}", db_type);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+// 无损压缩：清理无用对象、合并重复流、重新 Deflate
+
+use lopdf::{Document, Object, ObjectId};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionLevel {
+    // 清理无用对象并压缩未压缩的流
+    Low,
+    // 额外合并内容完全相同的流 (合并文件后重复嵌入的字体、图片)
+    Medium,
+    // 额外将已有 Flate 流以最高压缩级别重新压缩
+    High,
+}
+
+impl CompressionLevel {
+    pub fn parse(level: Option<&str>) -> CompressionLevel {
+        match level.map(|l| l.to_lowercase()).as_deref() {
+            Some("low") => CompressionLevel::Low,
+            Some("high") => CompressionLevel::High,
+            _ => CompressionLevel::Medium,
+        }
+    }
+}
+
+fn stream_hash(stream: &lopdf::Stream) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", stream.dict).hash(&mut hasher);
+    stream.content.hash(&mut hasher);
+    hasher.finish()
+}
+
+// 合并重复流并改写引用，返回移除的对象数
+fn dedupe_streams(doc: &mut Document) -> usize {
+    let mut buckets: HashMap<u64, Vec<ObjectId>> = HashMap::new();
+    let mut replace: HashMap<ObjectId, ObjectId> = HashMap::new();
+
+    for (id, object) in &doc.objects {
+        let Object::Stream(stream) = object else { continue };
+        let candidates = buckets.entry(stream_hash(stream)).or_default();
+        let original = candidates
+            .iter()
+            .find(|c| matches!(doc.objects.get(c), Some(Object::Stream(s)) if s.dict == stream.dict && s.content == stream.content));
+        match original {
+            Some(orig) => {
+                replace.insert(*id, *orig);
+            }
+            None => candidates.push(*id),
+        }
+    }
+
+    if replace.is_empty() {
+        return 0;
+    }
+    doc.traverse_objects(|object| {
+        if let Object::Reference(id) = object {
+            if let Some(target) = replace.get(id) {
+                *id = *target;
+            }
+        }
+    });
+    for id in replace.keys() {
+        doc.objects.remove(id);
+    }
+    replace.len()
+}
+
+// 以最高压缩级别重新压缩无预测器的 Flate 流，变大则保留原内容
+fn recompress_streams(doc: &mut Document) {
+    for object in doc.objects.values_mut() {
+        let Object::Stream(stream) = object else { continue };
+        let plain_flate = stream.filters().map(|f| f == ["FlateDecode"]).unwrap_or(false)
+            && !stream.dict.has(b"DecodeParms");
+        if !plain_flate || !stream.allows_compression {
+            continue;
+        }
+        let Ok(data) = stream.decompressed_content() else { continue };
+        let mut candidate = stream.clone();
+        candidate.set_plain_content(data);
+        if candidate.compress().is_ok() && candidate.dict.has(b"Filter") && candidate.content.len() < stream.content.len() {
+            *stream = candidate;
+        }
+    }
+}
+
+pub fn compress_document(doc: &mut Document, level: CompressionLevel) {
+    doc.prune_objects();
+    doc.delete_zero_length_streams();
+    if level != CompressionLevel::Low {
+        dedupe_streams(doc);
+    }
+    if level == CompressionLevel::High {
+        recompress_streams(doc);
+    }
+    doc.compress();
+    doc.prune_objects();
+    doc.renumber_objects();
+}
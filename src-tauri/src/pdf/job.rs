@@ -0,0 +1,146 @@
+// PDF 任务：进度上报、取消与中途产物清理
+
+use super::{save_document, PdfOutput};
+use lopdf::Document;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+// 前端监听的事件名
+pub const PROGRESS_EVENT: &str = "pdf-progress";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfProgress {
+    pub job_id: String,
+    // read / process / write / done
+    pub stage: &'static str,
+    // 当前输入文件序号 (1 起始)
+    pub file_index: usize,
+    pub file_total: usize,
+    pub page: u32,
+    pub page_total: u32,
+    pub bytes_written: u64,
+    // 当前处理或写出的文件
+    pub path: Option<String>,
+}
+
+// 运行中的任务及其取消标记
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    counter: AtomicU64,
+}
+
+impl JobRegistry {
+    pub fn next_id(&self) -> String {
+        let millis = chrono::Local::now().timestamp_millis();
+        format!("pdf-{}-{}", millis, self.counter.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn register(&self, id: &str) -> Result<Arc<AtomicBool>, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(id) {
+            return Err(format!("PDF job {} is already running", id));
+        }
+        let flag = Arc::new(AtomicBool::new(false));
+        jobs.insert(id.to_string(), flag.clone());
+        Ok(flag)
+    }
+
+    pub fn remove(&self, id: &str) {
+        self.jobs.lock().unwrap().remove(id);
+    }
+
+    // 返回任务是否存在
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.jobs.lock().unwrap().get(id) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+type Reporter = Box<dyn Fn(&PdfProgress) + Send>;
+
+// 单个任务的执行上下文，各处理步骤通过它检查取消并上报进度
+pub struct PdfJob {
+    pub id: String,
+    cancel: Arc<AtomicBool>,
+    reporter: Reporter,
+    file_index: usize,
+    file_total: usize,
+    bytes_written: u64,
+    written: Vec<PathBuf>,
+}
+
+impl PdfJob {
+    pub fn new(id: String, cancel: Arc<AtomicBool>, reporter: Reporter) -> PdfJob {
+        PdfJob { id, cancel, reporter, file_index: 0, file_total: 0, bytes_written: 0, written: Vec::new() }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_cancelled() {
+            return Err(format!("PDF job {} was cancelled", self.id));
+        }
+        Ok(())
+    }
+
+    fn emit(&self, stage: &'static str, page: u32, page_total: u32, path: Option<&Path>) {
+        (self.reporter)(&PdfProgress {
+            job_id: self.id.clone(),
+            stage,
+            file_index: self.file_index,
+            file_total: self.file_total,
+            page,
+            page_total,
+            bytes_written: self.bytes_written,
+            path: path.map(|p| p.to_string_lossy().to_string()),
+        });
+    }
+
+    // 开始读取第 index 个输入文件 (1 起始)
+    pub fn start_file(&mut self, index: usize, total: usize, path: &str) -> Result<(), String> {
+        self.check()?;
+        self.file_index = index;
+        self.file_total = total;
+        self.emit("read", 0, 0, Some(Path::new(path)));
+        Ok(())
+    }
+
+    // 逐页处理的进度点，同时检查取消
+    pub fn page(&self, page: u32, page_total: u32) -> Result<(), String> {
+        self.check()?;
+        self.emit("process", page, page_total, None);
+        Ok(())
+    }
+
+    pub fn save(&mut self, doc: &mut Document, path: &Path) -> Result<PdfOutput, String> {
+        self.check()?;
+        let output = save_document(doc, path)?;
+        self.written.push(path.to_path_buf());
+        self.bytes_written += output.bytes;
+        self.emit("write", output.pages, output.pages, Some(path));
+        Ok(output)
+    }
+
+    pub fn finish(&self) {
+        self.emit("done", 0, 0, None);
+    }
+
+    // 取消后删除本任务已写出的文件
+    pub fn discard_outputs(&mut self) {
+        for path in self.written.drain(..) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
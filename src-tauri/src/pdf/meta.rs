@@ -0,0 +1,334 @@
+// PDF 文档信息读取与 Info 元数据改写
+
+use super::{save_document, PdfOutput};
+use lopdf::{Dictionary, Document, Object, ObjectId, StringFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+// Info 字典中的常用字段
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    pub creator: Option<String>,
+    pub producer: Option<String>,
+    pub creation_date: Option<String>,
+    pub mod_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageSize {
+    pub page: u32,
+    // 单位为 pt (1/72 英寸)
+    pub width: f32,
+    pub height: f32,
+    pub rotate: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FontInfo {
+    pub name: String,
+    pub subtype: String,
+    pub embedded: bool,
+    pub subset: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfInfo {
+    pub path: String,
+    pub bytes: u64,
+    pub version: String,
+    pub pages: u32,
+    pub encrypted: bool,
+    pub metadata: PdfMetadata,
+    pub page_sizes: Vec<PageSize>,
+    pub fonts: Vec<FontInfo>,
+}
+
+// Info 字段名，顺序与 PdfMetadata::fields 一致
+const INFO_KEYS: [&str; 8] = ["Title", "Author", "Subject", "Keywords", "Creator", "Producer", "CreationDate", "ModDate"];
+
+impl PdfMetadata {
+    fn fields(&self) -> [&Option<String>; 8] {
+        [
+            &self.title,
+            &self.author,
+            &self.subject,
+            &self.keywords,
+            &self.creator,
+            &self.producer,
+            &self.creation_date,
+            &self.mod_date,
+        ]
+    }
+
+    fn fields_mut(&mut self) -> [&mut Option<String>; 8] {
+        [
+            &mut self.title,
+            &mut self.author,
+            &mut self.subject,
+            &mut self.keywords,
+            &mut self.creator,
+            &mut self.producer,
+            &mut self.creation_date,
+            &mut self.mod_date,
+        ]
+    }
+}
+
+// PDF 文本字符串：带 BOM 的 UTF-16BE 或 PDFDocEncoding (按 Latin-1 近似)
+pub fn decode_pdf_string(bytes: &[u8]) -> String {
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = bytes[2..].chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else if let Ok(s) = std::str::from_utf8(bytes) {
+        s.to_string()
+    } else {
+        bytes.iter().map(|b| *b as char).collect()
+    }
+}
+
+// 非 ASCII 文本以 UTF-16BE 编码写入
+pub fn encode_pdf_string(text: &str) -> Object {
+    if text.is_ascii() {
+        Object::String(text.as_bytes().to_vec(), StringFormat::Literal)
+    } else {
+        let mut bytes = vec![0xFE, 0xFF];
+        bytes.extend(text.encode_utf16().flat_map(|u| u.to_be_bytes()));
+        Object::String(bytes, StringFormat::Hexadecimal)
+    }
+}
+
+// D:YYYYMMDDHHmmSS+08'00' 转为 YYYY-MM-DD HH:MM:SS+08:00，无法识别时原样返回
+pub fn format_pdf_date(raw: &str) -> String {
+    let s = raw.trim().trim_start_matches("D:");
+    let digits: String = s.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.len() < 4 {
+        return raw.to_string();
+    }
+    let part = |from: usize, len: usize, default: &str| digits.get(from..from + len).unwrap_or(default).to_string();
+    let mut out = format!(
+        "{}-{}-{} {}:{}:{}",
+        part(0, 4, "0000"),
+        part(4, 2, "01"),
+        part(6, 2, "01"),
+        part(8, 2, "00"),
+        part(10, 2, "00"),
+        part(12, 2, "00")
+    );
+    let tz = &s[digits.len()..];
+    if tz.starts_with('Z') {
+        out.push('Z');
+    } else if tz.starts_with('+') || tz.starts_with('-') {
+        let tz_digits: String = tz[1..].chars().filter(|c| c.is_ascii_digit()).collect();
+        if tz_digits.len() >= 2 {
+            out.push_str(&format!("{}{}:{}", &tz[..1], &tz_digits[..2], tz_digits.get(2..4).unwrap_or("00")));
+        }
+    }
+    out
+}
+
+pub fn pdf_date_now() -> String {
+    let now = chrono::Local::now();
+    let offset = now.format("%z").to_string();
+    format!("D:{}{}'{}'", now.format("%Y%m%d%H%M%S"), &offset[..3], &offset[3..])
+}
+
+fn info_dict(doc: &Document) -> Option<&Dictionary> {
+    match doc.trailer.get(b"Info").ok()? {
+        Object::Reference(id) => doc.get_dictionary(*id).ok(),
+        Object::Dictionary(d) => Some(d),
+        _ => None,
+    }
+}
+
+pub fn read_metadata(doc: &Document) -> PdfMetadata {
+    let mut meta = PdfMetadata::default();
+    let Some(info) = info_dict(doc) else { return meta };
+    for (key, field) in INFO_KEYS.iter().zip(meta.fields_mut()) {
+        let value = info.get_deref(key.as_bytes(), doc).and_then(Object::as_str).ok().map(decode_pdf_string);
+        *field = value.map(|v| if key.ends_with("Date") { format_pdf_date(&v) } else { v });
+    }
+    meta
+}
+
+// 沿 Parent 链查找页面属性 (只读)
+fn inherited<'a>(doc: &'a Document, page_id: ObjectId, key: &[u8]) -> Option<&'a Object> {
+    let mut node = doc.get_dictionary(page_id).ok()?;
+    for _ in 0..64 {
+        if let Ok(v) = node.get(key) {
+            return doc.dereference(v).ok().map(|(_, o)| o);
+        }
+        node = doc.get_dictionary(node.get(b"Parent").and_then(Object::as_reference).ok()?).ok()?;
+    }
+    None
+}
+
+fn page_sizes(doc: &Document) -> Vec<PageSize> {
+    doc.get_pages()
+        .into_iter()
+        .map(|(page, id)| {
+            let rect: Vec<f32> = inherited(doc, id, b"CropBox")
+                .or_else(|| inherited(doc, id, b"MediaBox"))
+                .and_then(|o| o.as_array().ok())
+                .map(|a| a.iter().filter_map(|v| v.as_float().ok()).collect())
+                .unwrap_or_default();
+            let (width, height) = match rect.as_slice() {
+                [x0, y0, x1, y1] => ((x1 - x0).abs(), (y1 - y0).abs()),
+                _ => (0.0, 0.0),
+            };
+            let rotate = inherited(doc, id, b"Rotate").and_then(|o| o.as_i64().ok()).unwrap_or(0);
+            PageSize { page, width, height, rotate }
+        })
+        .collect()
+}
+
+// 字体是否嵌入：FontDescriptor 含 FontFile/FontFile2/FontFile3 (Type0 取其后代字体)
+fn font_embedded(doc: &Document, font: &Dictionary) -> bool {
+    let descriptor = font.get_deref(b"FontDescriptor", doc).and_then(Object::as_dict).ok().or_else(|| {
+        let descendant = font.get_deref(b"DescendantFonts", doc).and_then(Object::as_array).ok()?.first()?;
+        let descendant = doc.dereference(descendant).ok()?.1.as_dict().ok()?;
+        descendant.get_deref(b"FontDescriptor", doc).and_then(Object::as_dict).ok()
+    });
+    descriptor
+        .map(|d| d.has(b"FontFile") || d.has(b"FontFile2") || d.has(b"FontFile3"))
+        .unwrap_or(false)
+}
+
+fn fonts(doc: &Document) -> Vec<FontInfo> {
+    let mut found: BTreeMap<String, FontInfo> = BTreeMap::new();
+    for page_id in doc.page_iter() {
+        for font in doc.get_page_fonts(page_id).values() {
+            let name = font.get(b"BaseFont").and_then(Object::as_name_str).unwrap_or("Unknown").to_string();
+            if found.contains_key(&name) {
+                continue;
+            }
+            // 子集字体以 6 个大写字母加 + 为前缀，如 ABCDEF+SimSun
+            let subset = name.len() > 7 && name.as_bytes()[6] == b'+' && name[..6].bytes().all(|b| b.is_ascii_uppercase());
+            found.insert(
+                name.clone(),
+                FontInfo {
+                    subtype: font.get(b"Subtype").and_then(Object::as_name_str).unwrap_or("").to_string(),
+                    embedded: font_embedded(doc, font),
+                    subset,
+                    name,
+                },
+            );
+        }
+    }
+    found.into_values().collect()
+}
+
+pub fn inspect(path: &str) -> Result<PdfInfo, String> {
+    if !Path::new(path).exists() {
+        return Err(format!("File not found: {}", path));
+    }
+    let mut doc = Document::load(path).map_err(|e| format!("Failed to read PDF {}: {}", path, e))?;
+    let encrypted = doc.is_encrypted();
+    // 仅设置了权限密码的文件可用空密码解密，否则元数据不可读
+    let readable = !encrypted || doc.decrypt("").is_ok();
+
+    Ok(PdfInfo {
+        path: path.to_string(),
+        bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        version: doc.version.clone(),
+        pages: doc.get_pages().len() as u32,
+        encrypted,
+        metadata: if readable { read_metadata(&doc) } else { PdfMetadata::default() },
+        page_sizes: page_sizes(&doc),
+        fonts: if readable { fonts(&doc) } else { Vec::new() },
+    })
+}
+
+// 改写 Info 字典：None 保持原值，空字符串表示删除该字段
+pub fn write_metadata(doc: &mut Document, meta: &PdfMetadata) -> Result<(), String> {
+    let info_id = match doc.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) => *id,
+        Ok(Object::Dictionary(d)) => {
+            let d = d.clone();
+            doc.add_object(d)
+        }
+        _ => doc.add_object(Dictionary::new()),
+    };
+    doc.trailer.set("Info", info_id);
+
+    let info = doc.get_dictionary_mut(info_id).map_err(|e| format!("Invalid Info dictionary: {}", e))?;
+    for (key, value) in INFO_KEYS.iter().zip(meta.fields()) {
+        match value.as_deref() {
+            Some("") => {
+                info.remove(key.as_bytes());
+            }
+            Some(v) if !key.ends_with("Date") => info.set(*key, encode_pdf_string(v)),
+            _ => {}
+        }
+    }
+    info.set("ModDate", Object::string_literal(pdf_date_now()));
+    if !info.has(b"CreationDate") {
+        info.set("CreationDate", Object::string_literal(pdf_date_now()));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn pdf_inspect(path: String) -> Result<PdfInfo, String> {
+    inspect(&path)
+}
+
+// output_path 省略时覆盖原文件：经同目录临时文件改名写入，失败时原文件保持不变
+pub fn set_metadata(path: &str, metadata: &PdfMetadata, output_path: Option<&str>) -> Result<PdfOutput, String> {
+    let mut doc = super::load_document(path)?;
+    write_metadata(&mut doc, metadata)?;
+    let target = output_path.filter(|p| !p.trim().is_empty()).unwrap_or(path);
+    save_document(&mut doc, Path::new(target))
+}
+
+#[tauri::command]
+pub async fn pdf_set_metadata(path: String, metadata: PdfMetadata, output_path: Option<String>) -> Result<PdfOutput, String> {
+    set_metadata(&path, &metadata, output_path.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    fn write_sample(path: &Path) {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let page_id = doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id, "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()] });
+        doc.objects.insert(pages_id, Object::Dictionary(dictionary! { "Type" => "Pages", "Kids" => vec![page_id.into()], "Count" => 1 }));
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        doc.save(path).unwrap();
+    }
+
+    #[test]
+    fn rewrites_metadata_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.pdf");
+        write_sample(&path);
+        let meta = PdfMetadata { title: Some("标题".to_string()), ..Default::default() };
+        let output = set_metadata(path.to_str().unwrap(), &meta, None).unwrap();
+        assert_eq!(output.pages, 1);
+        let doc = Document::load(&path).unwrap();
+        assert_eq!(read_metadata(&doc).title.as_deref(), Some("标题"));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn failed_save_keeps_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.pdf");
+        write_sample(&path);
+        let before = std::fs::read(&path).unwrap();
+        let missing = dir.path().join("missing/b.pdf");
+        assert!(set_metadata(path.to_str().unwrap(), &PdfMetadata::default(), missing.to_str()).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), before);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
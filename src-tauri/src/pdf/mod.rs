@@ -0,0 +1,295 @@
+// PDF 处理 (基于 lopdf)
+
+use lopdf::Document;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter, State};
+
+pub mod compress;
+pub mod job;
+pub mod meta;
+pub mod ops;
+pub mod pages;
+pub mod stamp;
+
+use crate::AppState;
+use compress::{compress_document, CompressionLevel};
+use job::{PdfJob, PdfProgress, PROGRESS_EVENT};
+use ops::{apply_operations, PageOperation};
+use pages::{merge_documents, parse_ranges, subset_document};
+use stamp::{stamp_document, StampOptions};
+
+// process_pdf 的 meta 参数
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PdfOptions {
+    pub output_dir: Option<String>,
+    pub output_name: Option<String>,
+    // 拆分范围，如 "1-3,4-6,7"，每段输出一个文件
+    pub ranges: Option<String>,
+    // 每 N 页拆分为一个文件
+    pub every: Option<u32>,
+    // low / medium / high
+    pub compression_level: Option<String>,
+    // pages 模式下依次执行的页面操作
+    pub operations: Option<Vec<PageOperation>>,
+    // stamp 模式下叠加的文字水印/页码
+    pub stamps: Option<Vec<StampOptions>>,
+}
+
+// 输出文件信息
+#[derive(Debug, Serialize)]
+pub struct PdfOutput {
+    pub path: String,
+    pub pages: u32,
+    pub bytes: u64,
+}
+
+// 读取 PDF，加密文件直接拒绝
+pub fn load_document(path: &str) -> Result<Document, String> {
+    if !Path::new(path).exists() {
+        return Err(format!("File not found: {}", path));
+    }
+    let doc = Document::load(path).map_err(|e| format!("Failed to read PDF {}: {}", path, e))?;
+    if doc.is_encrypted() {
+        return Err(format!("PDF is encrypted: {}", path));
+    }
+    Ok(doc)
+}
+
+// 保存并返回输出信息；先写入同目录临时文件再改名，失败时目标文件保持原样
+pub fn save_document(doc: &mut Document, path: &Path) -> Result<PdfOutput, String> {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    let written = doc
+        .save(&tmp)
+        .map_err(|e| e.to_string())
+        .and_then(|_| std::fs::rename(&tmp, path).map_err(|e| e.to_string()));
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(format!("Failed to write {}: {}", path.display(), e));
+    }
+    Ok(PdfOutput {
+        path: path.to_string_lossy().to_string(),
+        pages: doc.get_pages().len() as u32,
+        bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+// 输出路径不能指向任一输入文件
+fn check_output(path: &Path, files: &[String]) -> Result<(), String> {
+    let Ok(target) = path.canonicalize() else { return Ok(()) };
+    match files.iter().find(|f| Path::new(f).canonicalize().is_ok_and(|f| f == target)) {
+        Some(file) => Err(format!("Output would overwrite input file: {}", file)),
+        None => Ok(()),
+    }
+}
+
+impl PdfOptions {
+    pub fn from_meta(meta: Option<serde_json::Value>) -> Result<PdfOptions, String> {
+        match meta {
+            Some(v) if !v.is_null() => serde_json::from_value(v).map_err(|e| format!("Invalid options: {}", e)),
+            _ => Ok(PdfOptions::default()),
+        }
+    }
+
+    // 输出目录：未指定时使用第一个输入文件所在目录
+    pub fn output_dir(&self, first_input: &str) -> PathBuf {
+        match self.output_dir.as_deref().filter(|d| !d.trim().is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(first_input).parent().map(Path::to_path_buf).unwrap_or_default(),
+        }
+    }
+
+    // 输出文件名 (不含扩展名)：未指定时使用第一个输入文件名
+    pub fn base_name(&self, first_input: &str) -> String {
+        self.output_name
+            .as_deref()
+            .map(|n| n.trim().trim_end_matches(".pdf"))
+            .filter(|n| !n.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| {
+                Path::new(first_input)
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "output".to_string())
+            })
+    }
+}
+
+fn merge(files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    let mut docs = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        job.start_file(i + 1, files.len(), file)?;
+        let doc = load_document(file)?;
+        let pages = doc.get_pages().len() as u32;
+        job.page(pages, pages)?;
+        docs.push(doc);
+    }
+    let mut merged = merge_documents(docs)?;
+    merged.compress();
+    let path = opts.output_dir(&files[0]).join(format!("{}_merged.pdf", opts.base_name(&files[0])));
+    check_output(&path, files)?;
+    Ok(vec![job.save(&mut merged, &path)?])
+}
+
+fn split(files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    let mut outputs = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        job.start_file(i + 1, files.len(), file)?;
+        let doc = load_document(file)?;
+        let total = doc.get_pages().len() as u32;
+        let ranges = match (&opts.ranges, opts.every) {
+            (Some(spec), _) if !spec.trim().is_empty() => parse_ranges(spec, total)?,
+            (_, every) => {
+                let n = every.unwrap_or(1).max(1);
+                (1..=total).step_by(n as usize).map(|s| (s, (s + n - 1).min(total))).collect()
+            }
+        };
+
+        // 多个输入文件时以各自文件名为前缀
+        let base = if files.len() > 1 { PdfOptions::default().base_name(file) } else { opts.base_name(file) };
+        let dir = opts.output_dir(file);
+        for (start, end) in ranges {
+            job.page(end, total)?;
+            let mut part = subset_document(&doc, &(start..=end).collect::<Vec<_>>())?;
+            let name = if start == end { format!("{}_{}.pdf", base, start) } else { format!("{}_{}-{}.pdf", base, start, end) };
+            outputs.push(job.save(&mut part, &dir.join(name))?);
+        }
+    }
+    Ok(outputs)
+}
+
+fn compress(files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    let level = CompressionLevel::parse(opts.compression_level.as_deref());
+    let mut outputs = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        job.start_file(i + 1, files.len(), file)?;
+        let mut doc = load_document(file)?;
+        compress_document(&mut doc, level);
+        let base = if files.len() > 1 { PdfOptions::default().base_name(file) } else { opts.base_name(file) };
+        let path = opts.output_dir(file).join(format!("{}_compressed.pdf", base));
+        outputs.push(job.save(&mut doc, &path)?);
+    }
+    Ok(outputs)
+}
+
+fn edit_pages(files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    let operations = opts.operations.as_deref().unwrap_or_default();
+    let mut outputs = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        job.start_file(i + 1, files.len(), file)?;
+        let doc = load_document(file)?;
+        let output = apply_operations(doc, operations)?;
+        let base = if files.len() > 1 { PdfOptions::default().base_name(file) } else { opts.base_name(file) };
+        let dir = opts.output_dir(file);
+
+        if let Some(mut doc) = output.edited {
+            outputs.push(job.save(&mut doc, &dir.join(format!("{}_edited.pdf", base)))?);
+        }
+        for (i, (name, mut doc)) in output.extracted.into_iter().enumerate() {
+            let name = name
+                .map(|n| n.trim().trim_end_matches(".pdf").to_string())
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("{}_extract_{}", base, i + 1));
+            let path = dir.join(format!("{}.pdf", name));
+            check_output(&path, files)?;
+            outputs.push(job.save(&mut doc, &path)?);
+        }
+    }
+    Ok(outputs)
+}
+
+fn stamp(files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    let stamps = opts.stamps.as_deref().unwrap_or_default();
+    let mut outputs = Vec::new();
+    for (i, file) in files.iter().enumerate() {
+        job.start_file(i + 1, files.len(), file)?;
+        let mut doc = load_document(file)?;
+        stamp_document(&mut doc, stamps, |page, total| job.page(page, total))?;
+        let base = if files.len() > 1 { PdfOptions::default().base_name(file) } else { opts.base_name(file) };
+        let path = opts.output_dir(file).join(format!("{}_stamped.pdf", base));
+        outputs.push(job.save(&mut doc, &path)?);
+    }
+    Ok(outputs)
+}
+
+// 取消时删除已写出的文件，成功时发送 done 事件
+pub fn run(mode: &str, files: &[String], opts: &PdfOptions, job: &mut PdfJob) -> Result<Vec<PdfOutput>, String> {
+    if files.is_empty() {
+        return Err("No input files".to_string());
+    }
+    let result = match mode {
+        "merge" => merge(files, opts, job),
+        "split" => split(files, opts, job),
+        "compress" => compress(files, opts, job),
+        "pages" => edit_pages(files, opts, job),
+        "stamp" => stamp(files, opts, job),
+        _ => Err(format!("Unsupported PDF mode: {}", mode)),
+    };
+    match &result {
+        Ok(_) => job.finish(),
+        Err(_) if job.is_cancelled() => job.discard_outputs(),
+        Err(_) => {}
+    }
+    result
+}
+
+// 在阻塞线程中执行，进度通过 pdf-progress 事件推送；job_id 省略时自动生成 (可从事件中取得)
+#[tauri::command]
+pub async fn process_pdf(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    mode: String,
+    files: Vec<String>,
+    meta: Option<serde_json::Value>,
+    job_id: Option<String>,
+) -> Result<Vec<PdfOutput>, String> {
+    let opts = PdfOptions::from_meta(meta)?;
+    let id = job_id.filter(|id| !id.trim().is_empty()).unwrap_or_else(|| state.pdf_jobs.next_id());
+    let cancel = state.pdf_jobs.register(&id)?;
+
+    let reporter = Box::new(move |progress: &PdfProgress| {
+        let _ = app.emit(PROGRESS_EVENT, progress.clone());
+    });
+    let mut job = PdfJob::new(id.clone(), cancel, reporter);
+    let result = tauri::async_runtime::spawn_blocking(move || run(&mode, &files, &opts, &mut job)).await;
+    state.pdf_jobs.remove(&id);
+    result.map_err(|e| format!("PDF job {} failed: {}", id, e))?
+}
+
+// 返回任务是否存在
+#[tauri::command]
+pub fn pdf_cancel(state: State<'_, AppState>, job_id: String) -> bool {
+    state.pdf_jobs.cancel(&job_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_output_that_is_an_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("a.pdf");
+        std::fs::write(&input, b"%PDF").unwrap();
+        let files = vec![input.to_string_lossy().to_string()];
+        assert!(check_output(&dir.path().join(".").join("a.pdf"), &files).is_err());
+        assert!(check_output(&dir.path().join("a_merged.pdf"), &files).is_ok());
+    }
+
+    #[test]
+    fn save_replaces_target_without_leaving_temp_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.pdf");
+        std::fs::write(&path, b"old").unwrap();
+        let output = save_document(&mut Document::with_version("1.5"), &path).unwrap();
+        assert_eq!(output.bytes, std::fs::metadata(&path).unwrap().len());
+        assert!(std::fs::read(&path).unwrap().starts_with(b"%PDF-1.5"));
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // 目录不存在时写入失败，不影响任何已有文件
+        assert!(save_document(&mut Document::with_version("1.5"), &dir.path().join("missing/out.pdf")).is_err());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,196 @@
+// 页面操作：旋转、重排、删除、提取
+
+use super::pages::{expand_ranges, materialize_inherited, rebuild_page_tree, subset_document};
+use lopdf::{Document, Object, ObjectId};
+use serde::Deserialize;
+
+// 页码均以 1 起始，且针对前一步操作后的文档
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum PageOperation {
+    // pages 省略时旋转全部页面，angle 须为 90 的倍数 (顺时针)
+    Rotate { pages: Option<String>, angle: i64 },
+    // 新顺序，须为全部页码的一个排列
+    Reorder { order: Vec<u32> },
+    Delete { pages: String },
+    // 提取为新文件，不影响当前文档
+    #[serde(rename_all = "camelCase")]
+    Extract { pages: String, output_name: Option<String> },
+}
+
+// 操作结果：修改后的文档 (无修改时为 None) 与提取出的文档
+pub struct OperationOutput {
+    pub edited: Option<Document>,
+    pub extracted: Vec<(Option<String>, Document)>,
+}
+
+fn page_ids(doc: &Document) -> Vec<ObjectId> {
+    doc.get_pages().into_values().collect()
+}
+
+fn rotate(doc: &mut Document, pages: Option<&str>, angle: i64) -> Result<(), String> {
+    if angle % 90 != 0 {
+        return Err(format!("Rotation angle must be a multiple of 90, got {}", angle));
+    }
+    let ids = page_ids(doc);
+    let targets = match pages {
+        Some(spec) if !spec.trim().is_empty() => expand_ranges(spec, ids.len() as u32)?,
+        _ => (1..=ids.len() as u32).collect(),
+    };
+    for n in targets {
+        let id = ids[n as usize - 1];
+        materialize_inherited(doc, id)?;
+        let page = doc.get_dictionary_mut(id).map_err(|e| e.to_string())?;
+        let current = page.get(b"Rotate").and_then(Object::as_i64).unwrap_or(0);
+        page.set("Rotate", (current + angle).rem_euclid(360));
+    }
+    Ok(())
+}
+
+fn reorder(doc: &mut Document, order: &[u32]) -> Result<(), String> {
+    let ids = page_ids(doc);
+    let total = ids.len() as u32;
+    let mut sorted = order.to_vec();
+    sorted.sort_unstable();
+    if sorted != (1..=total).collect::<Vec<_>>() {
+        return Err(format!("Page order must list every page 1-{} exactly once", total));
+    }
+    let keep: Vec<ObjectId> = order.iter().map(|n| ids[*n as usize - 1]).collect();
+    rebuild_page_tree(doc, &keep)
+}
+
+fn delete(doc: &mut Document, pages: &str) -> Result<(), String> {
+    let ids = page_ids(doc);
+    let removed = expand_ranges(pages, ids.len() as u32)?;
+    if removed.len() == ids.len() {
+        return Err("Cannot delete every page of the document".to_string());
+    }
+    let keep: Vec<ObjectId> = ids
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !removed.contains(&(*i as u32 + 1)))
+        .map(|(_, id)| *id)
+        .collect();
+    rebuild_page_tree(doc, &keep)
+}
+
+// 依次执行操作
+pub fn apply_operations(mut doc: Document, operations: &[PageOperation]) -> Result<OperationOutput, String> {
+    if operations.is_empty() {
+        return Err("No page operations given".to_string());
+    }
+    let mut modified = false;
+    let mut extracted = Vec::new();
+
+    for (i, op) in operations.iter().enumerate() {
+        let step = |e: String| format!("Operation #{}: {}", i + 1, e);
+        match op {
+            PageOperation::Rotate { pages, angle } => rotate(&mut doc, pages.as_deref(), *angle).map_err(step)?,
+            PageOperation::Reorder { order } => reorder(&mut doc, order).map_err(step)?,
+            PageOperation::Delete { pages } => delete(&mut doc, pages).map_err(step)?,
+            PageOperation::Extract { pages, output_name } => {
+                let numbers = expand_ranges(pages, doc.get_pages().len() as u32).map_err(step)?;
+                extracted.push((output_name.clone(), subset_document(&doc, &numbers).map_err(step)?));
+                continue;
+            }
+        }
+        modified = true;
+    }
+
+    Ok(OperationOutput { edited: modified.then_some(doc), extracted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lopdf::dictionary;
+
+    // 每页以 Tag 标记原页码，旋转角度由 Pages 节点继承
+    fn sample(pages: i64) -> Document {
+        let mut doc = Document::with_version("1.5");
+        let pages_id = doc.new_object_id();
+        let kids: Vec<Object> = (1..=pages).map(|tag| doc.add_object(dictionary! { "Type" => "Page", "Parent" => pages_id, "Tag" => tag }).into()).collect();
+        doc.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => kids,
+                "Count" => pages,
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+                "Rotate" => 90,
+            }),
+        );
+        let catalog_id = doc.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        doc.trailer.set("Root", catalog_id);
+        doc
+    }
+
+    fn page_values(doc: &Document, key: &[u8]) -> Vec<i64> {
+        page_ids(doc).into_iter().map(|id| doc.get_dictionary(id).unwrap().get(key).and_then(Object::as_i64).unwrap_or(-1)).collect()
+    }
+
+    fn apply(pages: i64, operations: Vec<PageOperation>) -> Result<OperationOutput, String> {
+        apply_operations(sample(pages), &operations)
+    }
+
+    #[test]
+    fn reorders_pages_by_permutation() {
+        let doc = apply(3, vec![PageOperation::Reorder { order: vec![3, 1, 2] }]).unwrap().edited.unwrap();
+        assert_eq!(page_values(&doc, b"Tag"), [3, 1, 2]);
+        // 继承的属性随页面保留
+        assert_eq!(page_values(&doc, b"Rotate"), [90, 90, 90]);
+
+        for order in [vec![1, 1, 2], vec![1, 2], vec![1, 2, 3, 4], vec![0, 1, 2], vec![]] {
+            let err = apply(3, vec![PageOperation::Reorder { order: order.clone() }]).err();
+            assert!(err.is_some_and(|e| e.contains("exactly once")), "{:?} should be rejected", order);
+        }
+    }
+
+    #[test]
+    fn deletes_pages_but_never_all_of_them() {
+        let doc = apply(3, vec![PageOperation::Delete { pages: "2".to_string() }]).unwrap().edited.unwrap();
+        assert_eq!(page_values(&doc, b"Tag"), [1, 3]);
+
+        let err = apply(3, vec![PageOperation::Delete { pages: "1-3".to_string() }]).err().unwrap();
+        assert!(err.contains("every page"), "{}", err);
+        // 前一步删除后只剩两页
+        let ops = vec![PageOperation::Delete { pages: "1".to_string() }, PageOperation::Delete { pages: "1-".to_string() }];
+        assert!(apply(3, ops).err().unwrap().starts_with("Operation #2"));
+    }
+
+    #[test]
+    fn rejects_out_of_range_pages() {
+        let cases = [
+            PageOperation::Delete { pages: "4".to_string() },
+            PageOperation::Rotate { pages: Some("2-5".to_string()), angle: 90 },
+            PageOperation::Extract { pages: "0".to_string(), output_name: None },
+            PageOperation::Reorder { order: vec![1, 2, 4] },
+        ];
+        for op in cases {
+            let label = format!("{:?}", op);
+            assert!(apply(3, vec![op]).is_err(), "{} should be rejected", label);
+        }
+    }
+
+    #[test]
+    fn rotates_by_multiples_of_90() {
+        let ops = vec![PageOperation::Rotate { pages: Some("2".to_string()), angle: -90 }, PageOperation::Rotate { pages: None, angle: 450 }];
+        let doc = apply(3, ops).unwrap().edited.unwrap();
+        assert_eq!(page_values(&doc, b"Rotate"), [180, 90, 180]);
+
+        for angle in [45, 100, -30] {
+            let err = apply(3, vec![PageOperation::Rotate { pages: None, angle }]).err().unwrap();
+            assert!(err.contains("multiple of 90"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn extracts_without_editing() {
+        let output = apply(3, vec![PageOperation::Extract { pages: "3,1".to_string(), output_name: Some("part".to_string()) }]).unwrap();
+        assert!(output.edited.is_none());
+        let (name, doc) = &output.extracted[0];
+        assert_eq!(name.as_deref(), Some("part"));
+        assert_eq!(page_values(doc, b"Tag"), [3, 1]);
+        assert!(apply(3, Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+// 页面树操作：合并、按页重建文档
+
+use lopdf::{dictionary, Document, Object, ObjectId};
+
+// 可从父 Pages 节点继承的页面属性
+const INHERITABLE: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+// 防止损坏文件中的 Parent 环
+const MAX_TREE_DEPTH: usize = 64;
+
+// 将继承属性复制到页面本身，页面脱离原页面树后仍能正确显示
+pub fn materialize_inherited(doc: &mut Document, page_id: ObjectId) -> Result<(), String> {
+    let page = doc.get_dictionary(page_id).map_err(|e| format!("Invalid page object: {}", e))?;
+    let mut missing: Vec<&[u8]> = INHERITABLE.iter().copied().filter(|k| !page.has(k)).collect();
+    let mut parent = page.get(b"Parent").and_then(Object::as_reference).ok();
+    let mut inherited: Vec<(&[u8], Object)> = Vec::new();
+
+    for _ in 0..MAX_TREE_DEPTH {
+        let (Some(pid), false) = (parent, missing.is_empty()) else { break };
+        let Ok(node) = doc.get_dictionary(pid) else { break };
+        missing.retain(|k| match node.get(k) {
+            Ok(v) => {
+                inherited.push((k, v.clone()));
+                false
+            }
+            Err(_) => true,
+        });
+        parent = node.get(b"Parent").and_then(Object::as_reference).ok();
+    }
+
+    let page = doc.get_dictionary_mut(page_id).map_err(|e| e.to_string())?;
+    for (k, v) in inherited {
+        page.set(k.to_vec(), v);
+    }
+    Ok(())
+}
+
+// 按给定页面 ID 顺序重建扁平页面树，未保留的页面及原页面树节点会被清除
+pub fn rebuild_page_tree(doc: &mut Document, keep: &[ObjectId]) -> Result<(), String> {
+    for id in keep {
+        materialize_inherited(doc, *id)?;
+    }
+
+    // 原有页面与 Pages 节点置空，避免被书签/链接引用而残留
+    let stale: Vec<ObjectId> = doc
+        .objects
+        .iter()
+        .filter(|(id, obj)| {
+            !keep.contains(id) && matches!(obj.as_dict().and_then(|d| d.type_name()), Ok("Page") | Ok("Pages"))
+        })
+        .map(|(id, _)| *id)
+        .collect();
+    for id in stale {
+        doc.objects.insert(id, Object::Null);
+    }
+
+    let pages_id = doc.new_object_id();
+    for id in keep {
+        doc.get_dictionary_mut(*id).map_err(|e| e.to_string())?.set("Parent", pages_id);
+    }
+    doc.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => keep.iter().map(|id| Object::Reference(*id)).collect::<Vec<_>>(),
+            "Count" => keep.len() as i64,
+        }),
+    );
+
+    let catalog = doc.catalog_mut().map_err(|e| format!("Missing document catalog: {}", e))?;
+    catalog.set("Pages", pages_id);
+    // 书签与结构树指向的页面可能已被移除
+    catalog.remove(b"Outlines");
+    catalog.remove(b"StructTreeRoot");
+
+    doc.prune_objects();
+    doc.renumber_objects();
+    Ok(())
+}
+
+// 以 1 起始的页码列表生成新文档
+pub fn subset_document(source: &Document, page_numbers: &[u32]) -> Result<Document, String> {
+    let mut doc = source.clone();
+    let pages = doc.get_pages();
+    let keep = page_numbers
+        .iter()
+        .map(|n| pages.get(n).copied().ok_or_else(|| format!("Page {} out of range (1-{})", n, pages.len())))
+        .collect::<Result<Vec<_>, _>>()?;
+    rebuild_page_tree(&mut doc, &keep)?;
+    Ok(doc)
+}
+
+// 按顺序合并多个文档
+pub fn merge_documents(docs: Vec<Document>) -> Result<Document, String> {
+    let mut merged = Document::with_version("1.5");
+    let mut next_id = 1;
+    let mut all_pages: Vec<ObjectId> = Vec::new();
+
+    for mut doc in docs {
+        doc.renumber_objects_with(next_id);
+        next_id = doc.max_id + 1;
+
+        let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        for id in &pages {
+            materialize_inherited(&mut doc, *id)?;
+        }
+        for (id, object) in doc.objects {
+            let type_name = object.as_dict().and_then(|d| d.type_name()).unwrap_or("");
+            if !matches!(type_name, "Catalog" | "Pages" | "Outlines" | "Outline") {
+                merged.objects.insert(id, object);
+            }
+        }
+        all_pages.extend(pages);
+    }
+
+    if all_pages.is_empty() {
+        return Err("No pages found in input files".to_string());
+    }
+
+    merged.max_id = next_id;
+    let pages_id = merged.new_object_id();
+    for id in &all_pages {
+        merged.get_dictionary_mut(*id).map_err(|e| e.to_string())?.set("Parent", pages_id);
+    }
+    merged.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => all_pages.iter().map(|id| Object::Reference(*id)).collect::<Vec<_>>(),
+            "Count" => all_pages.len() as i64,
+        }),
+    );
+    let catalog_id = merged.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    merged.trailer.set("Root", catalog_id);
+
+    merged.prune_objects();
+    merged.renumber_objects();
+    Ok(merged)
+}
+
+// 解析页码范围，如 "1-3,5,8-" (省略结尾表示到最后一页)
+pub fn parse_ranges(spec: &str, total: u32) -> Result<Vec<(u32, u32)>, String> {
+    let mut ranges = Vec::new();
+    for part in spec.split([',', ';']).map(str::trim).filter(|p| !p.is_empty()) {
+        let (start, end) = match part.split_once('-') {
+            Some((s, e)) => {
+                let start = if s.trim().is_empty() { 1 } else { parse_page(s)? };
+                let end = if e.trim().is_empty() { total } else { parse_page(e)? };
+                (start, end)
+            }
+            None => {
+                let p = parse_page(part)?;
+                (p, p)
+            }
+        };
+        if start == 0 || end > total || start > end {
+            return Err(format!("Invalid page range '{}' (document has {} pages)", part, total));
+        }
+        ranges.push((start, end));
+    }
+    if ranges.is_empty() {
+        return Err("Page range is empty".to_string());
+    }
+    Ok(ranges)
+}
+
+// 展开为页码列表 (保持书写顺序并去重)
+pub fn expand_ranges(spec: &str, total: u32) -> Result<Vec<u32>, String> {
+    let mut pages = Vec::new();
+    for (start, end) in parse_ranges(spec, total)? {
+        for p in start..=end {
+            if !pages.contains(&p) {
+                pages.push(p);
+            }
+        }
+    }
+    Ok(pages)
+}
+
+fn parse_page(s: &str) -> Result<u32, String> {
+    s.trim().parse().map_err(|_| format!("Invalid page number '{}'", s.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_single_pages_and_ranges() {
+        assert_eq!(parse_ranges("1-3, 5;8-", 10).unwrap(), [(1, 3), (5, 5), (8, 10)]);
+        assert_eq!(parse_ranges("-2", 10).unwrap(), [(1, 2)]);
+        assert_eq!(parse_ranges(" 4 - 4 ,", 10).unwrap(), [(4, 4)]);
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_and_malformed_ranges() {
+        for spec in ["0", "5-3", "11", "9-11", "a", "1-b", "", " , "] {
+            assert!(parse_ranges(spec, 10).is_err(), "{:?} should be rejected", spec);
+        }
+    }
+
+    #[test]
+    fn expands_in_written_order_without_duplicates() {
+        assert_eq!(expand_ranges("5,1-3,2-6", 10).unwrap(), [5, 1, 2, 3, 4, 6]);
+        assert_eq!(expand_ranges("9-", 10).unwrap(), [9, 10]);
+    }
+}
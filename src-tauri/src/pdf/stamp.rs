@@ -0,0 +1,348 @@
+// 文字水印与页码：以叠加内容流绘制，不栅格化原页面
+
+use super::pages::{expand_ranges, materialize_inherited};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, StringFormat, Stream};
+use serde::Deserialize;
+
+// 单组叠加文字，stamp 模式可同时叠加多组 (如水印 + 页脚)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StampOptions {
+    // 支持 {page} 与 {total} 占位符，如 "Page {page} of {total}"
+    pub text: String,
+    pub font_size: Option<f32>,
+    // 0-1，默认 0.3
+    pub opacity: Option<f32>,
+    // 逆时针角度，对角水印一般为 45
+    pub rotation: Option<f32>,
+    // center / top / bottom / left / right / top-left / top-right / bottom-left / bottom-right
+    pub position: Option<String>,
+    // 距页面边缘 (pt)，默认 36
+    pub margin: Option<f32>,
+    // #RRGGBB，默认灰色
+    pub color: Option<String>,
+    // 页码范围，省略时为全部页面
+    pub pages: Option<String>,
+}
+
+// Helvetica 字宽 (1/1000 em)，对应 ASCII 32-126
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556, 556, 556, 556, 556,
+    556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833,
+    722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556,
+    556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334,
+    260, 334, 584,
+];
+
+// 标准 14 字体仅支持 WinAnsi 字符，其余字符需嵌入字体
+fn encode_text(text: &str) -> Result<Vec<u8>, String> {
+    text.chars()
+        .map(|c| match c as u32 {
+            0x20..=0x7E | 0xA0..=0xFF => Ok(c as u8),
+            _ => Err(format!("Stamp text only supports Latin characters, got '{}'", c)),
+        })
+        .collect()
+}
+
+fn text_width(bytes: &[u8], font_size: f32) -> f32 {
+    let units: u32 = bytes
+        .iter()
+        .map(|b| match b {
+            0x20..=0x7E => HELVETICA_WIDTHS[(*b - 0x20) as usize] as u32,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * font_size / 1000.0
+}
+
+fn parse_color(color: Option<&str>) -> Result<[f32; 3], String> {
+    let Some(hex) = color.map(|c| c.trim().trim_start_matches('#')).filter(|c| !c.is_empty()) else {
+        return Ok([0.5, 0.5, 0.5]);
+    };
+    if hex.len() != 6 {
+        return Err(format!("Invalid color '{}', expected #RRGGBB", hex));
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16)
+            .map(|v| v as f32 / 255.0)
+            .map_err(|_| format!("Invalid color '{}', expected #RRGGBB", hex))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+// 返回 (水平, 垂直) 对齐：-1 左/下，0 居中，1 右/上
+fn parse_position(position: Option<&str>) -> Result<(i8, i8), String> {
+    let pos = position.map(|p| p.trim().to_lowercase()).unwrap_or_default();
+    Ok(match pos.as_str() {
+        "" | "center" => (0, 0),
+        "top" => (0, 1),
+        "bottom" => (0, -1),
+        "left" => (-1, 0),
+        "right" => (1, 0),
+        "top-left" => (-1, 1),
+        "top-right" => (1, 1),
+        "bottom-left" => (-1, -1),
+        "bottom-right" => (1, -1),
+        _ => return Err(format!("Unsupported stamp position: {}", pos)),
+    })
+}
+
+// 在资源子字典中找一个未占用的名称
+fn free_name(dict: &Dictionary, prefix: &str) -> String {
+    (1..).map(|i| format!("{}{}", prefix, i)).find(|n| !dict.has(n.as_bytes())).unwrap_or_default()
+}
+
+// 取出 (可能为引用的) 字典副本
+fn owned_dict(doc: &Document, object: Option<&Object>) -> Dictionary {
+    object
+        .and_then(|o| doc.dereference(o).ok())
+        .and_then(|(_, o)| o.as_dict().ok().cloned())
+        .unwrap_or_default()
+}
+
+// 页面可见区域 (x0, y0, 宽, 高) 与旋转角度
+fn page_box(page: &Dictionary) -> ([f32; 4], i64) {
+    let rect: Vec<f32> = page
+        .get(b"CropBox")
+        .or_else(|_| page.get(b"MediaBox"))
+        .and_then(Object::as_array)
+        .map(|a| a.iter().filter_map(|v| v.as_float().ok()).collect())
+        .unwrap_or_default();
+    let rect = match rect.as_slice() {
+        [x0, y0, x1, y1] => [x0.min(*x1), y0.min(*y1), (x1 - x0).abs(), (y1 - y0).abs()],
+        _ => [0.0, 0.0, 612.0, 792.0],
+    };
+    let rotate = page.get(b"Rotate").and_then(Object::as_i64).unwrap_or(0).rem_euclid(360);
+    (rect, rotate)
+}
+
+// 将显示坐标映射到页面用户坐标，使旋转过的页面上文字仍保持正向
+fn display_matrix(rect: [f32; 4], rotate: i64) -> ([f32; 6], f32, f32) {
+    let [x0, y0, w, h] = rect;
+    match rotate {
+        90 => ([0.0, 1.0, -1.0, 0.0, x0 + w, y0], h, w),
+        180 => ([-1.0, 0.0, 0.0, -1.0, x0 + w, y0 + h], w, h),
+        270 => ([0.0, -1.0, 1.0, 0.0, x0, y0 + h], h, w),
+        _ => ([1.0, 0.0, 0.0, 1.0, x0, y0], w, h),
+    }
+}
+
+struct PreparedStamp<'a> {
+    options: &'a StampOptions,
+    font_size: f32,
+    opacity: f32,
+    color: [f32; 3],
+    align: (i8, i8),
+    margin: f32,
+    pages: Vec<u32>,
+}
+
+fn prepare(options: &StampOptions, total: u32) -> Result<PreparedStamp<'_>, String> {
+    if options.text.trim().is_empty() {
+        return Err("Stamp text is empty".to_string());
+    }
+    encode_text(&options.text)?;
+    let font_size = options.font_size.unwrap_or(12.0);
+    if font_size.is_nan() || font_size <= 0.0 {
+        return Err(format!("Invalid font size: {}", font_size));
+    }
+    Ok(PreparedStamp {
+        options,
+        font_size,
+        opacity: options.opacity.unwrap_or(0.3).clamp(0.0, 1.0),
+        color: parse_color(options.color.as_deref())?,
+        align: parse_position(options.position.as_deref())?,
+        margin: options.margin.unwrap_or(36.0).max(0.0),
+        pages: match options.pages.as_deref() {
+            Some(spec) if !spec.trim().is_empty() => expand_ranges(spec, total)?,
+            _ => (1..=total).collect(),
+        },
+    })
+}
+
+fn stamp_operations(stamp: &PreparedStamp, text: &[u8], page_box: ([f32; 4], i64), font: &str, gs: &str) -> Vec<Operation> {
+    let (cm, width, height) = display_matrix(page_box.0, page_box.1);
+    let size = stamp.font_size;
+    let text_w = text_width(text, size);
+    let (h, v) = stamp.align;
+
+    // 锚点与文字相对锚点的偏移，旋转围绕锚点进行
+    let ax = match h {
+        -1 => stamp.margin,
+        1 => width - stamp.margin,
+        _ => width / 2.0,
+    };
+    let ay = match v {
+        -1 => stamp.margin,
+        1 => height - stamp.margin - size * 0.7,
+        _ => height / 2.0,
+    };
+    let dx = match h {
+        -1 => 0.0,
+        1 => -text_w,
+        _ => -text_w / 2.0,
+    };
+    let dy = if v == 0 { -size * 0.35 } else { 0.0 };
+    let angle = stamp.options.rotation.unwrap_or(0.0).to_radians();
+    let (sin, cos) = angle.sin_cos();
+
+    let nums = |vals: &[f32]| vals.iter().map(|v| Object::Real(*v)).collect::<Vec<_>>();
+    vec![
+        Operation::new("q", vec![]),
+        Operation::new("cm", nums(&cm)),
+        Operation::new("gs", vec![Object::Name(gs.as_bytes().to_vec())]),
+        Operation::new("rg", nums(&stamp.color)),
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec![Object::Name(font.as_bytes().to_vec()), Object::Real(size)]),
+        Operation::new("Tm", nums(&[cos, sin, -sin, cos, ax, ay])),
+        Operation::new("Td", nums(&[dx, dy])),
+        Operation::new("Tj", vec![Object::String(text.to_vec(), StringFormat::Literal)]),
+        Operation::new("ET", vec![]),
+        Operation::new("Q", vec![]),
+    ]
+}
+
+fn stamp_page(doc: &mut Document, page_id: ObjectId, stamps: &[(&PreparedStamp, Vec<u8>)]) -> Result<(), String> {
+    materialize_inherited(doc, page_id)?;
+    let page = doc.get_dictionary(page_id).map_err(|e| e.to_string())?;
+    let bbox = page_box(page);
+
+    // 资源字典可能被多页共享，复制后写回本页
+    let mut resources = owned_dict(doc, page.get(b"Resources").ok());
+    let mut fonts = owned_dict(doc, resources.get(b"Font").ok());
+    let mut states = owned_dict(doc, resources.get(b"ExtGState").ok());
+    let existing = match page.get(b"Contents") {
+        Ok(Object::Array(items)) => items.clone(),
+        Ok(obj @ Object::Reference(_)) => vec![obj.clone()],
+        _ => Vec::new(),
+    };
+
+    let mut operations = vec![Operation::new("Q", vec![])];
+    for (stamp, text) in stamps {
+        let font = free_name(&fonts, "FStamp");
+        fonts.set(font.clone(), dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+            "Encoding" => "WinAnsiEncoding",
+        });
+        let gs = free_name(&states, "GSStamp");
+        states.set(gs.clone(), dictionary! {
+            "Type" => "ExtGState",
+            "ca" => stamp.opacity,
+            "CA" => stamp.opacity,
+        });
+        operations.extend(stamp_operations(stamp, text, bbox, &font, &gs));
+    }
+    resources.set("Font", fonts);
+    resources.set("ExtGState", states);
+
+    let encode = |ops: Vec<Operation>| Content { operations: ops }.encode().map_err(|e| e.to_string());
+    // 原内容包在 q/Q 中，避免其图形状态影响叠加层
+    let mut contents = Vec::new();
+    if !existing.is_empty() {
+        contents.push(Object::Reference(doc.add_object(Stream::new(dictionary! {}, encode(vec![Operation::new("q", vec![])])?))));
+        contents.extend(existing);
+    } else {
+        operations.remove(0);
+    }
+    contents.push(Object::Reference(doc.add_object(Stream::new(dictionary! {}, encode(operations)?))));
+
+    let page = doc.get_dictionary_mut(page_id).map_err(|e| e.to_string())?;
+    page.set("Resources", resources);
+    page.set("Contents", contents);
+    Ok(())
+}
+
+// progress(页码, 总页数) 返回错误时中止 (用于取消)
+pub fn stamp_document(
+    doc: &mut Document,
+    stamps: &[StampOptions],
+    mut progress: impl FnMut(u32, u32) -> Result<(), String>,
+) -> Result<(), String> {
+    if stamps.is_empty() {
+        return Err("No stamps given".to_string());
+    }
+    let pages = doc.get_pages();
+    let total = pages.len() as u32;
+    let prepared = stamps
+        .iter()
+        .enumerate()
+        .map(|(i, s)| prepare(s, total).map_err(|e| format!("Stamp #{}: {}", i + 1, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (number, page_id) in pages {
+        progress(number, total)?;
+        let texts = prepared
+            .iter()
+            .filter(|s| s.pages.contains(&number))
+            .map(|s| {
+                let text = s.options.text.replace("{page}", &number.to_string()).replace("{total}", &total.to_string());
+                encode_text(&text).map(|bytes| (s, bytes))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !texts.is_empty() {
+            stamp_page(doc, page_id, &texts)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_latin_text_as_win_ansi() {
+        assert_eq!(encode_text("Page 1 of 2").unwrap(), b"Page 1 of 2");
+        assert_eq!(encode_text("Café ©").unwrap(), [b'C', b'a', b'f', 0xE9, b' ', 0xA9]);
+        for text in ["机密", "a\tb", "€", "\u{7F}"] {
+            assert!(encode_text(text).is_err(), "{:?} should be rejected", text);
+        }
+    }
+
+    #[test]
+    fn parses_positions() {
+        let cases = [
+            (None, (0, 0)),
+            (Some(""), (0, 0)),
+            (Some(" Center "), (0, 0)),
+            (Some("top"), (0, 1)),
+            (Some("bottom"), (0, -1)),
+            (Some("LEFT"), (-1, 0)),
+            (Some("right"), (1, 0)),
+            (Some("top-left"), (-1, 1)),
+            (Some("bottom-right"), (1, -1)),
+        ];
+        for (position, expected) in cases {
+            assert_eq!(parse_position(position).unwrap(), expected, "{:?}", position);
+        }
+        for position in ["middle", "top left", "topleft"] {
+            assert!(parse_position(Some(position)).is_err(), "{:?} should be rejected", position);
+        }
+    }
+
+    // 显示坐标 (x, y) 经矩阵映射到页面坐标
+    fn map(m: [f32; 6], x: f32, y: f32) -> (f32, f32) {
+        (m[0] * x + m[2] * y + m[4], m[1] * x + m[3] * y + m[5])
+    }
+
+    #[test]
+    fn display_matrix_maps_the_visible_corners_onto_the_page() {
+        let rect = [10.0, 20.0, 200.0, 100.0];
+        let cases = [
+            // (旋转, 显示宽高, 显示左下角与右上角对应的页面坐标)
+            (0, (200.0, 100.0), (10.0, 20.0), (210.0, 120.0)),
+            (90, (100.0, 200.0), (210.0, 20.0), (10.0, 120.0)),
+            (180, (200.0, 100.0), (210.0, 120.0), (10.0, 20.0)),
+            (270, (100.0, 200.0), (10.0, 120.0), (210.0, 20.0)),
+        ];
+        for (rotate, size, origin, corner) in cases {
+            let (m, w, h) = display_matrix(rect, rotate);
+            assert_eq!((w, h), size, "rotate {}", rotate);
+            assert_eq!(map(m, 0.0, 0.0), origin, "rotate {}", rotate);
+            assert_eq!(map(m, w, h), corner, "rotate {}", rotate);
+        }
+    }
+}
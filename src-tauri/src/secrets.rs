@@ -0,0 +1,443 @@
+// 密钥存储：数据库/调度/服务密码只保存在后端，前端仅持有句柄
+// Windows/macOS 使用系统钥匙串，Linux 使用 Secret Service；钥匙串不可用时使用主密钥加密的本地文件
+// 密钥只能展开到登记的主机/源的 HTTP 请求中
+
+use crate::AppState;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+const INDEX_FILE: &str = "secrets.json";
+const MASTER_KEY_FILE: &str = "secrets.key";
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+const KEYCHAIN_SERVICE: &str = "devtoolbox";
+// 文本中引用密钥的占位符，如 Authorization: Bearer {{secret:sec-xxx}}
+const PLACEHOLDER_PREFIX: &str = "{{secret:";
+const PLACEHOLDER_SUFFIX: &str = "}}";
+// 数据库密码的 kind，只用于数据库连接，不能展开到 HTTP 请求
+pub const DB_KIND: &str = "db";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretBackend {
+    Keychain,
+    File,
+}
+
+// 返回给前端的密钥信息 (不含密文)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretInfo {
+    pub handle: String,
+    // db / dolphinscheduler / seatunnel / dataservice 等
+    pub kind: String,
+    pub label: Option<String>,
+    // 允许展开该密钥的 HTTP 主机或源，如 api.example.com、10.0.0.1:8080、https://ds.example.com
+    // 为空时密钥不能用于 HTTP 请求
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    pub backend: SecretBackend,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretEntry {
+    #[serde(flatten)]
+    info: SecretInfo,
+    // 文件存储时为 base64(nonce + 密文)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ciphertext: Option<String>,
+}
+
+#[derive(Default)]
+struct Store {
+    dir: Option<PathBuf>,
+    key: Option<Key<Aes256Gcm>>,
+    keychain: bool,
+    entries: Vec<SecretEntry>,
+}
+
+#[derive(Default)]
+pub struct SecretStore {
+    inner: Mutex<Store>,
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos", target_os = "linux"))]
+mod keychain {
+    use super::KEYCHAIN_SERVICE;
+
+    fn entry(handle: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, handle).map_err(|e| format!("Keychain error: {}", e))
+    }
+
+    // 读取一个不存在的探测项确认钥匙串可用，不写入以免触发系统授权提示 (Linux 下未运行 Secret Service 时不可用)
+    pub fn available() -> bool {
+        let Ok(probe) = entry("__probe__") else { return false };
+        matches!(probe.get_password(), Ok(_) | Err(keyring::Error::NoEntry))
+    }
+
+    pub fn set(handle: &str, value: &str) -> Result<(), String> {
+        entry(handle)?.set_password(value).map_err(|e| format!("Keychain error: {}", e))
+    }
+
+    pub fn get(handle: &str) -> Result<String, String> {
+        entry(handle)?.get_password().map_err(|e| format!("Keychain error: {}", e))
+    }
+
+    pub fn delete(handle: &str) -> Result<(), String> {
+        match entry(handle)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Keychain error: {}", e)),
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod keychain {
+    pub fn available() -> bool {
+        false
+    }
+
+    pub fn set(_handle: &str, _value: &str) -> Result<(), String> {
+        Err("Keychain is not available on this platform".to_string())
+    }
+
+    pub fn get(_handle: &str) -> Result<String, String> {
+        Err("Keychain is not available on this platform".to_string())
+    }
+
+    pub fn delete(_handle: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+fn new_handle() -> String {
+    let bytes: [u8; 12] = Aes256Gcm::generate_nonce(&mut OsRng).into();
+    format!("sec-{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+// 主密钥文件不存在时生成；create_new 保证不覆盖已有文件，Unix 下创建时即为 0600
+fn load_master_key(dir: &Path) -> Result<Key<Aes256Gcm>, String> {
+    let path = dir.join(MASTER_KEY_FILE);
+    match std::fs::read_to_string(&path) {
+        Ok(text) => {
+            let bytes = B64.decode(text.trim()).map_err(|_| format!("Invalid master key file: {}", path.display()))?;
+            if bytes.len() != 32 {
+                return Err(format!("Invalid master key file: {}", path.display()));
+            }
+            Ok(*Key::<Aes256Gcm>::from_slice(&bytes))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = match options.open(&path) {
+                Ok(file) => file,
+                // 其他进程刚刚创建，改为读取
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return load_master_key(dir),
+                Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+            };
+            let key = Aes256Gcm::generate_key(OsRng);
+            file.write_all(B64.encode(key).as_bytes())
+                .and_then(|_| file.sync_all())
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+// 规范化登记的主机：带协议的按源 (协议 + 主机 + 端口) 匹配，否则按主机或 主机:端口 匹配
+fn normalize_host(host: &str) -> Result<String, String> {
+    let host = host.trim().trim_end_matches('/').to_lowercase();
+    if host.is_empty() || (host.contains('/') && !host.contains("://")) {
+        return Err(format!("Invalid secret host: {}", host));
+    }
+    if host.contains("://") {
+        let url = reqwest::Url::parse(&host).map_err(|_| format!("Invalid secret host: {}", host))?;
+        return Ok(url.origin().ascii_serialization());
+    }
+    Ok(host)
+}
+
+// 登记的 主机 或 主机:端口 是否命中
+fn host_matches(allowed: &str, host: &str, port: Option<u16>) -> bool {
+    match allowed.rsplit_once(':').filter(|(_, p)| p.parse::<u16>().is_ok()) {
+        Some((name, p)) => name.eq_ignore_ascii_case(host) && p.parse::<u16>().ok() == port,
+        None => allowed.eq_ignore_ascii_case(host),
+    }
+}
+
+// 请求地址是否命中登记的主机/源
+fn host_allowed(hosts: &[String], url: &reqwest::Url) -> bool {
+    let Some(host) = url.host_str() else { return false };
+    let origin = url.origin().ascii_serialization();
+    let port = url.port_or_known_default();
+    hosts.iter().any(|allowed| if allowed.contains("://") { *allowed == origin } else { host_matches(allowed, host, port) })
+}
+
+impl Store {
+    fn dir(&self) -> Result<&Path, String> {
+        self.dir.as_deref().ok_or_else(|| "Secret store is not loaded".to_string())
+    }
+
+    fn cipher(&mut self) -> Result<Aes256Gcm, String> {
+        if self.key.is_none() {
+            self.key = Some(load_master_key(self.dir()?)?);
+        }
+        Ok(Aes256Gcm::new(self.key.as_ref().unwrap()))
+    }
+
+    fn encrypt(&mut self, value: &str) -> Result<String, String> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut out = nonce.to_vec();
+        out.extend(self.cipher()?.encrypt(&nonce, value.as_bytes()).map_err(|_| "Failed to encrypt secret".to_string())?);
+        Ok(B64.encode(out))
+    }
+
+    fn decrypt(&mut self, data: &str) -> Result<String, String> {
+        let bytes = B64.decode(data).map_err(|_| "Corrupt secret data".to_string())?;
+        if bytes.len() < 12 {
+            return Err("Corrupt secret data".to_string());
+        }
+        let (nonce, ciphertext) = bytes.split_at(12);
+        let plain = self
+            .cipher()?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt secret (master key changed?)".to_string())?;
+        String::from_utf8(plain).map_err(|_| "Corrupt secret data".to_string())
+    }
+
+    fn find(&self, handle: &str) -> Result<usize, String> {
+        self.entries
+            .iter()
+            .position(|e| e.info.handle == handle)
+            .ok_or_else(|| format!("Secret not found: {}", handle))
+    }
+
+    // 按当前首选后端写入密钥值
+    fn write_value(&mut self, handle: &str, value: &str) -> Result<(SecretBackend, Option<String>), String> {
+        if self.keychain {
+            keychain::set(handle, value)?;
+            Ok((SecretBackend::Keychain, None))
+        } else {
+            Ok((SecretBackend::File, Some(self.encrypt(value)?)))
+        }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let dir = self.dir()?;
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let path = dir.join(INDEX_FILE);
+        let text = serde_json::to_string_pretty(&self.entries).map_err(|e| e.to_string())?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, text).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+impl SecretStore {
+    pub fn load(&self, dir: &Path) -> Result<(), String> {
+        let path = dir.join(INDEX_FILE);
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| format!("Invalid {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let mut store = self.inner.lock().unwrap();
+        store.dir = Some(dir.to_path_buf());
+        store.keychain = keychain::available();
+        store.entries = entries;
+        Ok(())
+    }
+
+    pub fn set(&self, kind: &str, label: Option<String>, hosts: Vec<String>, value: &str) -> Result<SecretInfo, String> {
+        let hosts = hosts.iter().map(|h| normalize_host(h)).collect::<Result<Vec<_>, _>>()?;
+        let mut store = self.inner.lock().unwrap();
+        let handle = new_handle();
+        let (backend, ciphertext) = store.write_value(&handle, value)?;
+        let info = SecretInfo { handle, kind: kind.to_string(), label, hosts, backend, updated_at: now() };
+        store.entries.push(SecretEntry { info: info.clone(), ciphertext });
+        store.persist()?;
+        Ok(info)
+    }
+
+    // 替换密钥值，句柄不变
+    pub fn rotate(&self, handle: &str, value: &str) -> Result<SecretInfo, String> {
+        let mut store = self.inner.lock().unwrap();
+        let idx = store.find(handle)?;
+        let previous = store.entries[idx].info.backend;
+        let (backend, ciphertext) = store.write_value(handle, value)?;
+        if previous == SecretBackend::Keychain && backend == SecretBackend::File {
+            keychain::delete(handle)?;
+        }
+        let entry = &mut store.entries[idx];
+        entry.info.backend = backend;
+        entry.info.updated_at = now();
+        entry.ciphertext = ciphertext;
+        let info = entry.info.clone();
+        store.persist()?;
+        Ok(info)
+    }
+
+    // 替换允许使用该密钥的主机列表；只由后端在保存连接时调用，不开放给前端
+    pub fn set_hosts(&self, handle: &str, hosts: Vec<String>) -> Result<SecretInfo, String> {
+        let hosts = hosts.iter().map(|h| normalize_host(h)).collect::<Result<Vec<_>, _>>()?;
+        let mut store = self.inner.lock().unwrap();
+        let idx = store.find(handle)?;
+        let entry = &mut store.entries[idx];
+        entry.info.hosts = hosts;
+        entry.info.updated_at = now();
+        let info = entry.info.clone();
+        store.persist()?;
+        Ok(info)
+    }
+
+    // 仅供后端内部使用；发往外部的密钥应通过 get_for_host / get_of_kind 读取
+    pub fn get(&self, handle: &str) -> Result<String, String> {
+        let mut store = self.inner.lock().unwrap();
+        let idx = store.find(handle)?;
+        match store.entries[idx].ciphertext.clone() {
+            Some(data) => store.decrypt(&data),
+            None => keychain::get(handle),
+        }
+    }
+
+    // 读取用于连接 host:port 的密钥，密钥未登记该主机时拒绝 (数据库连接)
+    pub fn get_for_host(&self, handle: &str, host: &str, port: Option<u16>) -> Result<String, String> {
+        let allowed = {
+            let store = self.inner.lock().unwrap();
+            let idx = store.find(handle)?;
+            store.entries[idx].info.hosts.iter().any(|h| !h.contains("://") && host_matches(h, host.trim(), port))
+        };
+        if !allowed {
+            return Err(format!("Secret {} is not allowed for host {}", handle, host.trim()));
+        }
+        self.get(handle)
+    }
+
+    // 读取指定用途的密钥，如 SeaTunnel 配置只能引用 seatunnel 密钥
+    pub fn get_of_kind(&self, handle: &str, kind: &str) -> Result<String, String> {
+        let matches = {
+            let store = self.inner.lock().unwrap();
+            let idx = store.find(handle)?;
+            store.entries[idx].info.kind == kind
+        };
+        if !matches {
+            return Err(format!("Secret {} is not a {} secret", handle, kind));
+        }
+        self.get(handle)
+    }
+
+    // 返回密钥是否存在
+    pub fn delete(&self, handle: &str) -> Result<bool, String> {
+        let mut store = self.inner.lock().unwrap();
+        let Ok(idx) = store.find(handle) else { return Ok(false) };
+        if store.entries[idx].info.backend == SecretBackend::Keychain {
+            keychain::delete(handle)?;
+        }
+        store.entries.remove(idx);
+        store.persist()?;
+        Ok(true)
+    }
+
+    pub fn list(&self) -> Vec<SecretInfo> {
+        self.inner.lock().unwrap().entries.iter().map(|e| e.info.clone()).collect()
+    }
+
+    // 将发往 url 的请求文本中的 {{secret:句柄}} 替换为密钥值，密钥未登记该主机或为数据库密码时拒绝
+    pub fn expand(&self, text: &str, url: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(PLACEHOLDER_PREFIX) {
+            let after = &rest[start + PLACEHOLDER_PREFIX.len()..];
+            let Some(end) = after.find(PLACEHOLDER_SUFFIX) else { break };
+            let handle = after[..end].trim();
+            let target = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL {}: {}", url, e))?;
+            let allowed = {
+                let store = self.inner.lock().unwrap();
+                let idx = store.find(handle)?;
+                let info = &store.entries[idx].info;
+                info.kind != DB_KIND && host_allowed(&info.hosts, &target)
+            };
+            if !allowed {
+                return Err(format!("Secret {} is not allowed for host {}", handle, target.host_str().unwrap_or(url)));
+            }
+            out.push_str(&rest[..start]);
+            out.push_str(&self.get(handle)?);
+            rest = &after[end + PLACEHOLDER_SUFFIX.len()..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+#[tauri::command]
+pub fn secret_list(state: State<'_, AppState>) -> Vec<SecretInfo> {
+    state.secrets.list()
+}
+
+#[tauri::command]
+pub fn secret_set(
+    state: State<'_, AppState>,
+    kind: String,
+    label: Option<String>,
+    hosts: Option<Vec<String>>,
+    value: String,
+) -> Result<SecretInfo, String> {
+    state.secrets.set(&kind, label, hosts.unwrap_or_default(), &value)
+}
+
+
+#[tauri::command]
+pub fn secret_rotate(state: State<'_, AppState>, handle: String, value: String) -> Result<SecretInfo, String> {
+    state.secrets.rotate(&handle, &value)
+}
+
+#[tauri::command]
+pub fn secret_delete(state: State<'_, AppState>, handle: String) -> Result<bool, String> {
+    state.secrets.delete(&handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed(hosts: &[&str], url: &str) -> bool {
+        let hosts: Vec<String> = hosts.iter().map(|h| normalize_host(h).unwrap()).collect();
+        host_allowed(&hosts, &reqwest::Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn matches_host_port_and_origin() {
+        assert!(allowed(&["API.example.com"], "https://api.example.com/v1"));
+        assert!(!allowed(&["api.example.com"], "https://evil.example.com/v1"));
+        assert!(allowed(&["10.0.0.1:12345"], "http://10.0.0.1:12345/ds"));
+        assert!(!allowed(&["10.0.0.1:12345"], "http://10.0.0.1:8080/ds"));
+        assert!(allowed(&["https://ds.example.com/"], "https://ds.example.com/api"));
+        assert!(!allowed(&["https://ds.example.com"], "http://ds.example.com/api"));
+        assert!(!allowed(&[], "https://api.example.com"));
+    }
+
+    #[test]
+    fn rejects_paths_without_scheme() {
+        assert!(normalize_host("example.com/api").is_err());
+        assert!(normalize_host("  ").is_err());
+    }
+}
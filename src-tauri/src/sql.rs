@@ -0,0 +1,484 @@
+// SQL 方言辅助：标识符/字面量转义、多语句拆分
+
+use std::io::BufRead;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    MySql,
+    Doris,
+    Postgres,
+    // 标识符与字符串同标准 SQL；只由驱动层按连接类型构造，Excel 生成 SQL 不支持
+    Sqlite,
+}
+
+impl Dialect {
+    // 根据前端传入的 db_type 解析方言 (不含 SQLite)
+    pub fn parse(db_type: &str) -> Option<Dialect> {
+        match db_type.trim().to_lowercase().as_str() {
+            "mysql" | "mariadb" | "tidb" => Some(Dialect::MySql),
+            "doris" | "starrocks" => Some(Dialect::Doris),
+            "postgres" | "postgresql" | "pg" => Some(Dialect::Postgres),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::MySql => "MySQL",
+            Dialect::Doris => "Doris",
+            Dialect::Postgres => "PostgreSQL",
+            Dialect::Sqlite => "SQLite",
+        }
+    }
+
+    // 标识符加引号 (MySQL/Doris 用反引号，PostgreSQL、SQLite 用双引号)
+    pub fn quote_ident(&self, name: &str) -> String {
+        match self {
+            Dialect::MySql | Dialect::Doris => format!("`{}`", name.replace('`', "``")),
+            Dialect::Postgres | Dialect::Sqlite => format!("\"{}\"", name.replace('"', "\"\"")),
+        }
+    }
+
+    // 字符串字面量 (MySQL 系默认开启反斜杠转义，PostgreSQL、SQLite 使用标准字符串)
+    pub fn quote_str(&self, value: &str) -> String {
+        match self {
+            Dialect::MySql | Dialect::Doris => {
+                let mut out = String::with_capacity(value.len() + 2);
+                out.push('\'');
+                for c in value.chars() {
+                    match c {
+                        '\'' => out.push_str("''"),
+                        '\\' => out.push_str("\\\\"),
+                        '\0' => out.push_str("\\0"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\x1a' => out.push_str("\\Z"),
+                        _ => out.push(c),
+                    }
+                }
+                out.push('\'');
+                out
+            }
+            Dialect::Postgres | Dialect::Sqlite => format!("'{}'", value.replace('\0', "").replace('\'', "''")),
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+// 复制从 start 处的开引号到闭引号 close 之间的内容，返回其后的位置；重复的闭引号视为转义
+fn copy_quoted(chars: &[char], start: usize, close: char, backslash: bool, out: &mut String) -> usize {
+    out.push(chars[start]);
+    let mut i = start + 1;
+    while i < chars.len() {
+        let q = chars[i];
+        out.push(q);
+        i += 1;
+        if q == '\\' && backslash && i < chars.len() {
+            out.push(chars[i]);
+            i += 1;
+        } else if q == close {
+            if chars.get(i) == Some(&close) {
+                out.push(close);
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    i
+}
+
+// PostgreSQL 的 $tag$ 引用开始标记 (tag 可为空)；$1 等参数占位符与标识符中的 $ 不是
+fn dollar_tag(chars: &[char], start: usize) -> Option<String> {
+    if start > 0 && is_ident_char(chars[start - 1]) {
+        return None;
+    }
+    let mut end = start + 1;
+    while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+        end += 1;
+    }
+    if chars.get(end) != Some(&'$') || chars.get(start + 1).is_some_and(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(chars[start..=end].iter().collect())
+}
+
+// 按分号拆分多条语句，忽略引号与注释中的分号 (不支持 DELIMITER)
+// MySQL 系：反斜杠转义、反引号、# 注释与 /*! */ 可执行注释，-- 后须有空白
+// PostgreSQL：$tag$ 引用、只有 E'' 字符串支持反斜杠转义、块注释可嵌套
+// SQLite：反引号与 [ ] 标识符，不支持反斜杠转义
+pub fn split_statements(sql: &str, dialect: Dialect) -> Vec<String> {
+    let mysql = matches!(dialect, Dialect::MySql | Dialect::Doris);
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    // 当前语句是否含注释以外的内容
+    let mut has_code = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\'' | '"' => {
+                // PostgreSQL 的 E'...' 转义字符串
+                let escape_string = dialect == Dialect::Postgres
+                    && c == '\''
+                    && i > 0
+                    && chars[i - 1].eq_ignore_ascii_case(&'e')
+                    && (i < 2 || !is_ident_char(chars[i - 2]));
+                i = copy_quoted(&chars, i, c, mysql || escape_string, &mut current);
+                has_code = true;
+                continue;
+            }
+            '`' if dialect != Dialect::Postgres => {
+                i = copy_quoted(&chars, i, c, false, &mut current);
+                has_code = true;
+                continue;
+            }
+            '[' if dialect == Dialect::Sqlite => {
+                i = copy_quoted(&chars, i, ']', false, &mut current);
+                has_code = true;
+                continue;
+            }
+            '$' if dialect == Dialect::Postgres => {
+                if let Some(tag) = dollar_tag(&chars, i) {
+                    let body = i + tag.chars().count();
+                    let tag: Vec<char> = tag.chars().collect();
+                    let end = (body..chars.len())
+                        .find(|&j| chars[j..].starts_with(&tag))
+                        .map_or(chars.len(), |j| j + tag.len());
+                    current.extend(&chars[i..end]);
+                    has_code = true;
+                    i = end;
+                    continue;
+                }
+                has_code = true;
+                current.push(c);
+            }
+            '-' if next == Some('-') && (!mysql || chars.get(i + 2).is_none_or(|c| c.is_whitespace())) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    current.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            '#' if mysql => {
+                while i < chars.len() && chars[i] != '\n' {
+                    current.push(chars[i]);
+                    i += 1;
+                }
+                continue;
+            }
+            '/' if next == Some('*') => {
+                // /*! ... */ 为 MySQL 可执行注释，视为语句内容
+                has_code |= mysql && chars.get(i + 2) == Some(&'!');
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == '/' && chars.get(i + 1) == Some(&'*') && (depth == 0 || dialect == Dialect::Postgres) {
+                        depth += 1;
+                        current.push_str("/*");
+                        i += 2;
+                    } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                        depth -= 1;
+                        current.push_str("*/");
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        current.push(chars[i]);
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            ';' => {
+                if has_code {
+                    statements.push(current.trim().to_string());
+                }
+                current.clear();
+                has_code = false;
+            }
+            _ => {
+                has_code |= !c.is_whitespace();
+                current.push(c);
+            }
+        }
+        i += 1;
+    }
+    if has_code {
+        statements.push(current.trim().to_string());
+    }
+    statements
+}
+
+enum Scan {
+    // 结束符的字节位置
+    Complete(usize),
+    // 需要更多输入；safe 之前的内容不在引号、注释中，可从该处继续扫描
+    Incomplete { safe: usize },
+}
+
+// 单行注释结束后的位置 (换行符之后)，行尚未读完时为 None
+fn line_end(bytes: &[u8], start: usize) -> Option<usize> {
+    bytes[start..].iter().position(|b| *b == b'\n').map(|end| start + end + 1)
+}
+
+// 从 start 处查找引号、注释之外的语句结束符，规则同 split_statements
+// 特殊字符均为 ASCII，按字节扫描不会切断 UTF-8 字符
+fn scan(text: &str, start: usize, delimiter: &str, has_code: &mut bool) -> Scan {
+    let bytes = text.as_bytes();
+    let mut i = start;
+    while i < bytes.len() {
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied();
+        match c {
+            b'\'' | b'"' | b'`' => {
+                let mut j = i + 1;
+                loop {
+                    let Some(&q) = bytes.get(j) else {
+                        return Scan::Incomplete { safe: i };
+                    };
+                    j += 1;
+                    if q == b'\\' && c != b'`' {
+                        j += 1;
+                    } else if q == c {
+                        if bytes.get(j) == Some(&c) {
+                            j += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                *has_code = true;
+                i = j;
+            }
+            b'-' if next == Some(b'-') && bytes.get(i + 2).is_none_or(|c| c.is_ascii_whitespace()) => match line_end(bytes, i) {
+                Some(end) => i = end,
+                None => return Scan::Incomplete { safe: i },
+            },
+            b'#' => match line_end(bytes, i) {
+                Some(end) => i = end,
+                None => return Scan::Incomplete { safe: i },
+            },
+            b'/' if next == Some(b'*') => match text[i + 2..].find("*/") {
+                Some(end) => {
+                    // /*! ... */ 为 MySQL 可执行注释，视为语句内容
+                    *has_code |= bytes.get(i + 2) == Some(&b'!');
+                    i += end + 4;
+                }
+                None => return Scan::Incomplete { safe: i },
+            },
+            _ if bytes[i..].starts_with(delimiter.as_bytes()) => return Scan::Complete(i),
+            _ => {
+                *has_code |= !c.is_ascii_whitespace();
+                i += 1;
+            }
+        }
+    }
+    Scan::Incomplete { safe: bytes.len() }
+}
+
+// 解析 mysql 客户端的 DELIMITER 命令，返回新的结束符
+fn parse_delimiter(line: &str) -> Option<String> {
+    let line = line.trim();
+    let keyword = line.get(..10)?;
+    if !keyword.eq_ignore_ascii_case("DELIMITER ") {
+        return None;
+    }
+    Some(line[10..].trim().to_string()).filter(|d| !d.is_empty())
+}
+
+// 从文件逐条读取语句，支持转储文件中的 DELIMITER 命令 (触发器、存储过程)
+// 按行读取并在行尾继续扫描，大文件无需整体载入
+pub struct StatementReader<R: BufRead> {
+    reader: R,
+    delimiter: String,
+    pending: String,
+    // pending 中已扫描且不在引号、注释中的位置
+    scanned: usize,
+    has_code: bool,
+    // pending 首字节所在行号 (从 1 开始)
+    pending_line: usize,
+    // 最近返回语句的起始行号
+    start_line: usize,
+    eof: bool,
+}
+
+impl<R: BufRead> StatementReader<R> {
+    pub fn new(reader: R) -> StatementReader<R> {
+        StatementReader {
+            reader,
+            delimiter: ";".to_string(),
+            pending: String::new(),
+            scanned: 0,
+            has_code: false,
+            pending_line: 1,
+            start_line: 0,
+            eof: false,
+        }
+    }
+
+    // 最近一次返回的语句所在行号
+    pub fn line(&self) -> usize {
+        self.start_line
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    fn take(&mut self, end: usize, skip: usize) -> String {
+        let text = &self.pending[..end];
+        let leading = &text[..text.len() - text.trim_start().len()];
+        self.start_line = self.pending_line + leading.matches('\n').count();
+        let statement = text.trim().to_string();
+        self.pending_line += self.pending[..end + skip].matches('\n').count();
+        self.pending.drain(..end + skip);
+        self.scanned = 0;
+        self.has_code = false;
+        statement
+    }
+}
+
+impl<R: BufRead> Iterator for StatementReader<R> {
+    type Item = std::io::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let mut has_code = self.has_code;
+            match scan(&self.pending, self.scanned, &self.delimiter, &mut has_code) {
+                Scan::Complete(end) => {
+                    let statement = self.take(end, self.delimiter.len());
+                    if has_code {
+                        return Some(Ok(statement));
+                    }
+                    continue;
+                }
+                Scan::Incomplete { safe } => {
+                    self.scanned = safe;
+                    self.has_code = has_code;
+                }
+            }
+            if self.eof {
+                // 末尾未以结束符结尾的语句 (含未闭合的引号时交给服务端报错)
+                let end = self.pending.len();
+                let has_code = self.has_code || self.pending[self.scanned..].starts_with(['\'', '"', '`']);
+                let statement = self.take(end, 0);
+                return (has_code && !statement.is_empty()).then_some(Ok(statement));
+            }
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => self.eof = true,
+                Ok(_) => {
+                    // DELIMITER 为客户端命令，只在语句开头出现
+                    if !self.has_code && self.scanned == self.pending.len() {
+                        if let Some(delimiter) = parse_delimiter(&line) {
+                            self.delimiter = delimiter;
+                            self.pending_line += self.pending.matches('\n').count() + 1;
+                            self.pending.clear();
+                            self.scanned = 0;
+                            continue;
+                        }
+                    }
+                    self.pending.push_str(&line);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(text: &str) -> Vec<String> {
+        StatementReader::new(text.as_bytes()).collect::<std::io::Result<Vec<_>>>().unwrap()
+    }
+
+    #[test]
+    fn split_ignores_delimiters_in_quotes() {
+        assert_eq!(
+            split_statements("INSERT INTO t VALUES ('a;b', \"c;d\", `e;f`); SELECT 2", Dialect::MySql),
+            vec!["INSERT INTO t VALUES ('a;b', \"c;d\", `e;f`)", "SELECT 2"]
+        );
+        assert_eq!(split_statements("SELECT 'it''s; ok'; SELECT 'x\\';y'", Dialect::MySql), vec!["SELECT 'it''s; ok'", "SELECT 'x\\';y'"]);
+    }
+
+    #[test]
+    fn split_skips_comments() {
+        assert_eq!(
+            split_statements("-- a; b\nSELECT 1; # c; d\nSELECT 2; /* e; f */ SELECT 3;", Dialect::MySql),
+            vec!["-- a; b\nSELECT 1", "# c; d\nSELECT 2", "/* e; f */ SELECT 3"]
+        );
+        assert!(split_statements("-- only\n; /* x */;", Dialect::MySql).is_empty());
+        // 没有空白的 -- 不是注释
+        assert_eq!(split_statements("SELECT 1--1; SELECT 2", Dialect::MySql), vec!["SELECT 1--1", "SELECT 2"]);
+    }
+
+    #[test]
+    fn split_keeps_executable_comments() {
+        assert_eq!(split_statements("/*!40101 SET NAMES utf8 */; /* plain */;", Dialect::MySql), vec!["/*!40101 SET NAMES utf8 */"]);
+    }
+
+    #[test]
+    fn split_postgres_dollar_quotes_and_standard_strings() {
+        let sql = "CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql; DO $body$ BEGIN PERFORM 'a;'; END $body$; SELECT $1";
+        assert_eq!(
+            split_statements(sql, Dialect::Postgres),
+            vec!["CREATE FUNCTION f() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql", "DO $body$ BEGIN PERFORM 'a;'; END $body$", "SELECT $1"]
+        );
+        // 标准字符串中反斜杠不转义，E'' 字符串中转义
+        assert_eq!(split_statements("SELECT 'a\\'; SELECT 2", Dialect::Postgres), vec!["SELECT 'a\\'", "SELECT 2"]);
+        assert_eq!(split_statements("SELECT E'a\\';b'; SELECT 2", Dialect::Postgres), vec!["SELECT E'a\\';b'", "SELECT 2"]);
+    }
+
+    #[test]
+    fn split_postgres_comments() {
+        assert_eq!(
+            split_statements("SELECT 1 # 2; SELECT 3--x;\nFROM t; /* a /* b; */ c; */ SELECT 4", Dialect::Postgres),
+            vec!["SELECT 1 # 2", "SELECT 3--x;\nFROM t", "/* a /* b; */ c; */ SELECT 4"]
+        );
+    }
+
+    #[test]
+    fn split_sqlite_identifiers_and_strings() {
+        assert_eq!(
+            split_statements("SELECT [a;b], `c;d` FROM t; SELECT 'x\\'; # y", Dialect::Sqlite),
+            vec!["SELECT [a;b], `c;d` FROM t", "SELECT 'x\\'", "# y"]
+        );
+    }
+
+    #[test]
+    fn reader_switches_delimiter() {
+        let sql = "DELIMITER ;;\nCREATE TRIGGER tr BEFORE INSERT ON t FOR EACH ROW BEGIN SET NEW.a = 1; END;;\nDELIMITER ;\nSELECT 1;\n";
+        assert_eq!(read(sql), vec!["CREATE TRIGGER tr BEFORE INSERT ON t FOR EACH ROW BEGIN SET NEW.a = 1; END", "SELECT 1"]);
+    }
+
+    #[test]
+    fn reader_skips_line_comments() {
+        let mut reader = StatementReader::new("# it's; here\nSELECT 1;\n-- don't; split\nSELECT 2;\n".as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap(), "# it's; here\nSELECT 1");
+        assert_eq!(reader.line(), 1);
+        assert_eq!(reader.next().unwrap().unwrap(), "-- don't; split\nSELECT 2");
+        assert_eq!(reader.line(), 3);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn reader_handles_block_comments_and_quotes_across_lines() {
+        let sql = "/*!40101 SET NAMES utf8 */;\n/* a;\n b; */;\nINSERT INTO t VALUES ('a;\nb''c', \"d\"\"e;\");\nSELECT 2";
+        let mut reader = StatementReader::new(sql.as_bytes());
+        assert_eq!(reader.next().unwrap().unwrap(), "/*!40101 SET NAMES utf8 */");
+        assert_eq!(reader.next().unwrap().unwrap(), "INSERT INTO t VALUES ('a;\nb''c', \"d\"\"e;\")");
+        assert_eq!(reader.line(), 4);
+        assert_eq!(reader.next().unwrap().unwrap(), "SELECT 2");
+        assert_eq!(reader.line(), 6);
+        assert!(reader.next().is_none());
+    }
+}
@@ -53,6 +53,21 @@ export interface TableDetail extends TableInfo {
   ddl: string;
 }
 
+// db_query 的返回结果：列信息 + 按列名组成的行对象
+export interface QueryColumn {
+  name: string;
+  type: string;
+  flags: string[];
+  charset?: string | null;
+  length?: number | null;
+  decimals?: number | null;
+}
+
+export interface QueryResult {
+  columns: QueryColumn[];
+  rows: Record<string, any>[];
+}
+
 export interface PdfFile {
   id: string;
   name: string;
@@ -80,6 +95,7 @@ export interface User {
 export type ScriptType = 'seatunnel' | 'datax' | 'other';
 
 export interface JobConfig {
+  connId?: string; // 连接注册表中的 DbConnection.id
   type: string;
   host: string;
   port: string;